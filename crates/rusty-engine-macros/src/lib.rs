@@ -0,0 +1,69 @@
+//! Derive macros for `rusty-engine`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implements the `Component` marker trait for a type so it can be stored in a `World`.
+///
+/// Accepts an optional `#[component(immutable)]` attribute, which overrides
+/// `Component::IMMUTABLE` to `true` — see that const's doc comment for what it does.
+#[proc_macro_derive(Component, attributes(component))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let immutable = component_is_immutable(&input.attrs);
+
+    let expanded = quote! {
+        impl #impl_generics rusty_engine::ecs::component::Component for #name #ty_generics #where_clause {
+            const IMMUTABLE: bool = #immutable;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Implements the `Event` marker trait for a type so it can be routed through a `Broker`,
+/// and adds an inherent `register` helper that forwards to `Broker::register::<Self>()` so
+/// callers don't have to spell out the type parameter at the call site.
+#[proc_macro_derive(Event)]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics rusty_engine::ecs::event::Event for #name #ty_generics #where_clause {}
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Registers this event type with `broker`, returning the stable
+            /// `EventTypeId` future `Broker::send`/`Broker::stream` calls should reuse.
+            pub fn register(broker: &mut rusty_engine::ecs::event::Broker) -> rusty_engine::ecs::event::EventTypeId {
+                broker.register::<Self>()
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Looks for `#[component(immutable)]` among a derive input's attributes.
+fn component_is_immutable(attrs: &[syn::Attribute]) -> bool {
+    let mut immutable = false;
+    for attr in attrs {
+        if !attr.path().is_ident("component") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("immutable") {
+                immutable = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `component` attribute, expected `immutable`"))
+            }
+        })
+        .expect("invalid #[component(...)] attribute");
+    }
+    immutable
+}
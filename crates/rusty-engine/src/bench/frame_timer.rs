@@ -0,0 +1,117 @@
+//! Per-frame duration accumulation with percentile and worst-frame reporting.
+//!
+//! Like the rest of `bench` (see its module doc comment), this is deliberately not an HDR
+//! histogram library — it just keeps every recorded duration and sorts on demand, which is
+//! plenty fast for the frame counts one scenario run produces and needs no external
+//! dependency.
+
+use std::time::Duration;
+
+/// Accumulates a scenario run's per-frame durations and reports its distribution.
+#[derive(Debug, Default, Clone)]
+pub struct FrameTimer {
+    samples_ns: Vec<f64>,
+}
+
+impl FrameTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a timer already populated with `samples_ns` (nanoseconds per frame), e.g. from
+    /// `time_scenario_samples`.
+    pub fn from_samples_ns(samples_ns: &[f64]) -> Self {
+        Self {
+            samples_ns: samples_ns.to_vec(),
+        }
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        self.samples_ns.push(duration.as_nanos() as f64);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples_ns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples_ns.is_empty()
+    }
+
+    /// The `p`-th percentile frame duration, in nanoseconds (e.g. `percentile(50.0)` is the
+    /// median, `percentile(99.0)` is p99).
+    ///
+    /// # Panics
+    /// Panics if no frames have been recorded, or if `p` isn't within `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> f64 {
+        assert!(!self.samples_ns.is_empty(), "FrameTimer::percentile requires at least one recorded frame");
+        assert!((0.0..=100.0).contains(&p), "percentile must be within 0.0..=100.0, got {p}");
+        let mut sorted = self.samples_ns.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let rank = (p / 100.0 * (sorted.len() - 1) as f64).floor() as usize;
+        sorted[rank]
+    }
+
+    /// The `n` slowest recorded frames, in nanoseconds, slowest first. Shorter than `n` if
+    /// fewer than `n` frames were recorded.
+    pub fn worst(&self, n: usize) -> Vec<f64> {
+        let mut sorted = self.samples_ns.clone();
+        sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        sorted.truncate(n);
+        sorted
+    }
+
+    /// A one-line summary — p50/p95/p99 and the `worst_n` slowest frames, all in milliseconds —
+    /// meant for the scenario runner to print per scenario to spot frame spikes at a glance.
+    ///
+    /// # Panics
+    /// Panics if no frames have been recorded (see `percentile`).
+    pub fn summary(&self, worst_n: usize) -> String {
+        let worst_ms: Vec<f64> = self.worst(worst_n).into_iter().map(|ns| ns / 1e6).collect();
+        format!(
+            "p50={:.3}ms p95={:.3}ms p99={:.3}ms worst={worst_ms:.3?}ms",
+            self.percentile(50.0) / 1e6,
+            self.percentile(95.0) / 1e6,
+            self.percentile(99.0) / 1e6,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_match_known_durations() {
+        // 1..=100 ns: percentile(p) should land on the value at that rank.
+        let samples: Vec<f64> = (1..=100).map(|n| n as f64).collect();
+        let timer = FrameTimer::from_samples_ns(&samples);
+
+        assert_eq!(timer.percentile(0.0), 1.0);
+        assert_eq!(timer.percentile(50.0), 50.0);
+        assert_eq!(timer.percentile(99.0), 99.0);
+        assert_eq!(timer.percentile(100.0), 100.0);
+    }
+
+    #[test]
+    fn worst_returns_the_slowest_n_frames_descending() {
+        let timer = FrameTimer::from_samples_ns(&[5.0, 1.0, 9.0, 3.0, 7.0]);
+        assert_eq!(timer.worst(3), vec![9.0, 7.0, 5.0]);
+        assert_eq!(timer.worst(10), vec![9.0, 7.0, 5.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn record_appends_a_durations_nanoseconds() {
+        let mut timer = FrameTimer::new();
+        timer.record(Duration::from_millis(1));
+        timer.record(Duration::from_millis(2));
+        assert_eq!(timer.len(), 2);
+        assert_eq!(timer.percentile(100.0), 2_000_000.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one recorded frame")]
+    fn percentile_panics_with_no_recorded_frames() {
+        FrameTimer::new().percentile(50.0);
+    }
+}
@@ -0,0 +1,49 @@
+//! Lightweight, dependency-free micro-benchmark harness for the ECS.
+//!
+//! This is not a criterion-style statistical benchmark tool — it's a small in-repo harness
+//! for timing specific ECS workloads (`Scenario`s) so we can eyeball regressions without
+//! pulling in an external crate.
+
+pub mod frame_timer;
+pub mod regression;
+pub mod scenario;
+pub mod sweep;
+
+use std::time::{Duration, Instant};
+
+/// A named, repeatable ECS workload that can be timed.
+pub trait Scenario {
+    fn name(&self) -> &str;
+
+    /// Runs one iteration of the workload from a freshly reset state.
+    fn run_once(&mut self);
+
+    /// How many entities this run of the scenario operates over, if that's a meaningful axis
+    /// for it — used by `sweep::sweep` to label a run swept across sizes. `None` for
+    /// scenarios (e.g. `scenario::PackedVsTable`'s row count, or a table count) that scale
+    /// over something other than entities.
+    fn entity_count(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Times `iterations` runs of `scenario`, returning the total elapsed wall time.
+pub fn time_scenario(scenario: &mut dyn Scenario, iterations: usize) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        scenario.run_once();
+    }
+    start.elapsed()
+}
+
+/// Like `time_scenario`, but returns each iteration's own duration (in nanoseconds) instead
+/// of the total, so callers can compute statistics such as `regression::Metrics`.
+pub fn time_scenario_samples(scenario: &mut dyn Scenario, iterations: usize) -> Vec<f64> {
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            scenario.run_once();
+            start.elapsed().as_nanos() as f64
+        })
+        .collect()
+}
@@ -0,0 +1,148 @@
+//! Regression gate: compares a benchmark's `Metrics` against a saved baseline and reports
+//! anything that regressed beyond an allowed percentage.
+//!
+//! `bench` (see its module doc comment) is a small, dependency-free in-repo timer, not
+//! Criterion, so there's no Criterion report/baseline format to plug into here. The baseline
+//! file this module reads/writes is deliberately just as dependency-free: one
+//! `name<TAB>mean_ns` line per benchmark, rather than pulling in a JSON crate for a single
+//! flat map. `Metrics` likewise only tracks what `time_scenario` can already produce (mean,
+//! stddev) — capturing allocation counts would mean wiring up an allocator-tracking crate
+//! like dhat, which is a bigger step this module doesn't take on its own.
+
+use std::collections::HashMap;
+
+/// Summary statistics for repeated runs of one named benchmark, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    pub mean_ns: f64,
+    pub stddev_ns: f64,
+}
+
+impl Metrics {
+    /// Computes mean and population standard deviation from per-iteration durations.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    pub fn from_samples_ns(samples: &[f64]) -> Self {
+        assert!(!samples.is_empty(), "Metrics::from_samples_ns requires at least one sample");
+        let mean_ns = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples.iter().map(|&s| (s - mean_ns).powi(2)).sum::<f64>() / samples.len() as f64;
+        Self {
+            mean_ns,
+            stddev_ns: variance.sqrt(),
+        }
+    }
+}
+
+/// Saved per-benchmark baseline means, keyed by benchmark name.
+pub type Baseline = HashMap<String, f64>;
+
+/// Parses a baseline file: one `name<TAB>mean_ns` line per benchmark. Blank lines are
+/// skipped.
+pub fn parse_baseline(text: &str) -> Baseline {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let (name, mean_ns) = line.split_once('\t')?;
+            Some((name.to_string(), mean_ns.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Serializes `baseline` back into the `parse_baseline` format, sorted by name for a stable
+/// diff when it's committed.
+pub fn format_baseline(baseline: &Baseline) -> String {
+    let mut names: Vec<&String> = baseline.keys().collect();
+    names.sort();
+    names.into_iter().map(|name| format!("{name}\t{}\n", baseline[name])).collect()
+}
+
+/// A benchmark whose mean regressed beyond the allowed percentage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub name: String,
+    pub baseline_ns: f64,
+    pub current_ns: f64,
+    pub percent_over: f64,
+}
+
+/// Compares `current` against `baseline`, returning every benchmark whose mean is more than
+/// `allowed_percent` slower than its baseline. Benchmarks present in only one of the two
+/// maps are ignored — this only gates regressions against a baseline that still exists.
+pub fn compare(baseline: &Baseline, current: &HashMap<String, Metrics>, allowed_percent: f64) -> Vec<Regression> {
+    let mut regressions: Vec<Regression> = baseline
+        .iter()
+        .filter_map(|(name, &baseline_ns)| {
+            let metrics = current.get(name)?;
+            let percent_over = (metrics.mean_ns - baseline_ns) / baseline_ns * 100.0;
+            (percent_over > allowed_percent).then(|| Regression {
+                name: name.clone(),
+                baseline_ns,
+                current_ns: metrics.mean_ns,
+                percent_over,
+            })
+        })
+        .collect();
+    regressions.sort_by(|a, b| a.name.cmp(&b.name));
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_samples_computes_mean_and_stddev() {
+        let metrics = Metrics::from_samples_ns(&[10.0, 20.0, 30.0]);
+        assert_eq!(metrics.mean_ns, 20.0);
+        assert!((metrics.stddev_ns - 8.164_965_809_277_26).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compare_flags_only_benchmarks_over_the_allowed_percentage() {
+        let mut baseline = Baseline::new();
+        baseline.insert("spawn".to_string(), 100.0);
+        baseline.insert("iterate".to_string(), 200.0);
+        baseline.insert("removed_benchmark".to_string(), 50.0);
+
+        let mut current = HashMap::new();
+        current.insert(
+            "spawn".to_string(),
+            Metrics {
+                mean_ns: 106.0,
+                stddev_ns: 0.0,
+            },
+        ); // +6%, within a 10% allowance
+        current.insert(
+            "iterate".to_string(),
+            Metrics {
+                mean_ns: 260.0,
+                stddev_ns: 0.0,
+            },
+        ); // +30%, regression
+        current.insert(
+            "new_benchmark".to_string(),
+            Metrics {
+                mean_ns: 999.0,
+                stddev_ns: 0.0,
+            },
+        ); // no baseline, ignored
+
+        let regressions = compare(&baseline, &current, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].name, "iterate");
+        assert_eq!(regressions[0].baseline_ns, 200.0);
+        assert_eq!(regressions[0].current_ns, 260.0);
+        assert_eq!(regressions[0].percent_over, 30.0);
+    }
+
+    #[test]
+    fn baseline_round_trips_through_format_and_parse() {
+        let mut baseline = Baseline::new();
+        baseline.insert("spawn".to_string(), 100.5);
+        baseline.insert("iterate".to_string(), 200.25);
+
+        let parsed = parse_baseline(&format_baseline(&baseline));
+        assert_eq!(parsed, baseline);
+    }
+}
@@ -0,0 +1,619 @@
+//! Concrete `Scenario`s exercising specific ECS hot paths.
+
+use crate::bench::Scenario;
+use crate::ecs::World;
+use rusty_engine_macros::Component;
+
+// The payload itself is never read outside tests; it exists so this component has a
+// realistic, non-zero-sized layout to move around during migration.
+#[derive(Component)]
+struct Fragment(#[allow(dead_code)] u32);
+
+#[derive(Component)]
+struct Tag;
+
+/// Repeatedly adds and removes a marker component from a batch of entities, forcing each one
+/// to migrate between two archetypes every iteration. Meant to surface regressions in
+/// archetype/table churn (as opposed to steady-state iteration, which stays in one archetype).
+pub struct ArchetypeFragmentation {
+    world: World,
+    entities: Vec<crate::ecs::Entity>,
+    entity_count: usize,
+}
+
+impl ArchetypeFragmentation {
+    pub fn new(entity_count: usize) -> Self {
+        let mut world = World::new();
+        let entities = (0..entity_count).map(|i| world.spawn(Fragment(i as u32))).collect();
+        Self {
+            world,
+            entities,
+            entity_count,
+        }
+    }
+}
+
+impl Scenario for ArchetypeFragmentation {
+    fn name(&self) -> &str {
+        "archetype_fragmentation"
+    }
+
+    fn run_once(&mut self) {
+        // Despawn and respawn every entity through a different archetype shape, simulating
+        // the add/remove churn that fragments archetypes in a real game (e.g. toggling a
+        // `Stunned` marker on and off every frame).
+        for entity in self.entities.drain(..) {
+            self.world.despawn(entity);
+        }
+        self.entities = (0..self.entity_count).map(|i| self.world.spawn((Fragment(i as u32), Tag))).collect();
+    }
+
+    fn entity_count(&self) -> Option<usize> {
+        Some(self.entity_count)
+    }
+}
+
+/// Mass-spawns `entity_count` entities that all carry a single `Fragment`, comparing
+/// `World::spawn_column`'s bulk-copy path against spawning the same entities one at a time
+/// through `World::spawn`. There's no `spawn_many` in this crate to compare against directly,
+/// so `individual` stands in for it as the existing per-entity baseline.
+pub struct SpawnColumn {
+    entity_count: usize,
+    bulk: bool,
+}
+
+impl SpawnColumn {
+    pub fn bulk(entity_count: usize) -> Self {
+        Self { entity_count, bulk: true }
+    }
+
+    pub fn individual(entity_count: usize) -> Self {
+        Self { entity_count, bulk: false }
+    }
+}
+
+impl Scenario for SpawnColumn {
+    fn name(&self) -> &str {
+        if self.bulk {
+            "spawn_column_bulk"
+        } else {
+            "spawn_column_individual"
+        }
+    }
+
+    fn run_once(&mut self) {
+        let mut world = World::new();
+        if self.bulk {
+            let values: Vec<Fragment> = (0..self.entity_count).map(|i| Fragment(i as u32)).collect();
+            world.spawn_column(values);
+        } else {
+            for i in 0..self.entity_count {
+                world.spawn(Fragment(i as u32));
+            }
+        }
+    }
+
+    fn entity_count(&self) -> Option<usize> {
+        Some(self.entity_count)
+    }
+}
+
+/// Sums a component across every entity via `Query::iter`, for steady-state iteration
+/// throughput — as opposed to `ArchetypeFragmentation`, which stresses migration churn instead
+/// of settled-in reads.
+pub struct IterateQuery {
+    world: World,
+    entity_count: usize,
+}
+
+impl IterateQuery {
+    pub fn new(entity_count: usize) -> Self {
+        let mut world = World::new();
+        for i in 0..entity_count {
+            world.spawn(Fragment(i as u32));
+        }
+        Self { world, entity_count }
+    }
+}
+
+impl Scenario for IterateQuery {
+    fn name(&self) -> &str {
+        "iterate_query"
+    }
+
+    fn run_once(&mut self) {
+        let mut query: crate::ecs::query::Query<&Fragment> = crate::ecs::query::Query::new(&mut self.world);
+        let sum: u64 = query.iter().map(|fragment| fragment.0 as u64).sum();
+        std::hint::black_box(sum);
+    }
+
+    fn entity_count(&self) -> Option<usize> {
+        Some(self.entity_count)
+    }
+}
+
+/// Repeatedly adds then removes the same marker component from a batch of entities that never
+/// change archetype shape overall (each ends every iteration back where it started), unlike
+/// `ArchetypeFragmentation` which despawns/respawns through `World`'s general machinery. Meant
+/// to show off `Archetypes::add_edge`/`remove_edge`: after the first iteration, every migration
+/// hits the cached edge instead of rebuilding the target `Spec` and hashing it.
+pub struct ToggleComponent {
+    world: World,
+    entities: Vec<crate::ecs::Entity>,
+}
+
+impl ToggleComponent {
+    pub fn new(entity_count: usize) -> Self {
+        let mut world = World::new();
+        let entities = (0..entity_count).map(|i| world.spawn(Fragment(i as u32))).collect();
+        Self { world, entities }
+    }
+}
+
+impl Scenario for ToggleComponent {
+    fn name(&self) -> &str {
+        "toggle_component"
+    }
+
+    fn run_once(&mut self) {
+        for &entity in &self.entities {
+            self.world.add_component(entity, Tag);
+        }
+        for &entity in &self.entities {
+            self.world.remove_component::<Tag>(entity);
+        }
+    }
+
+    fn entity_count(&self) -> Option<usize> {
+        Some(self.entities.len())
+    }
+}
+
+#[derive(Component)]
+struct Position(#[allow(dead_code)] f32, #[allow(dead_code)] f32);
+
+#[derive(Component)]
+struct Velocity(#[allow(dead_code)] f32, #[allow(dead_code)] f32);
+
+/// Despawns and respawns a batch of entities that all carry the same three-component bundle,
+/// every iteration. Meant to show off `World::spec_cache`: only the very first spawn of
+/// `(Fragment, Position, Velocity)` in this scenario's lifetime pays for `Registry::register`
+/// and `Spec::new`'s sort/dedup/hash — every spawn after that, including every one in every
+/// later `run_once`, just clones the cached ids and archetype.
+pub struct SpawnSameBundle {
+    world: World,
+    entities: Vec<crate::ecs::Entity>,
+    entity_count: usize,
+}
+
+impl SpawnSameBundle {
+    pub fn new(entity_count: usize) -> Self {
+        let mut world = World::new();
+        let entities = (0..entity_count).map(|i| world.spawn((Fragment(i as u32), Position(0.0, 0.0), Velocity(0.0, 0.0)))).collect();
+        Self { world, entities, entity_count }
+    }
+}
+
+impl Scenario for SpawnSameBundle {
+    fn name(&self) -> &str {
+        "spawn_same_bundle"
+    }
+
+    fn run_once(&mut self) {
+        for entity in self.entities.drain(..) {
+            self.world.despawn(entity);
+        }
+        self.entities = (0..self.entity_count).map(|i| self.world.spawn((Fragment(i as u32), Position(0.0, 0.0), Velocity(0.0, 0.0)))).collect();
+    }
+
+    fn entity_count(&self) -> Option<usize> {
+        Some(self.entity_count)
+    }
+}
+
+#[cfg(feature = "packed-storage")]
+mod packed_vs_table {
+    use crate::bench::Scenario;
+    use crate::ecs::component::Registry;
+    use crate::ecs::storage::PackedTable;
+    use crate::ecs::storage::Table;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component)]
+    struct A(f32);
+    #[derive(Component)]
+    struct B(f32);
+    #[derive(Component)]
+    struct C(f32);
+
+    /// Compares multi-component iteration over `Table` (one allocation per column) against
+    /// `PackedTable` (one allocation per archetype), reading `(&A, &B, &C)` from every row.
+    ///
+    /// A prototype-vs-baseline scenario rather than a permanent one: it exists to answer
+    /// "does packing columns together actually help?", not to guard a shipped code path.
+    pub struct PackedVsTable {
+        table: Table,
+        packed: PackedTable,
+        ids: [crate::ecs::component::ComponentId; 3],
+        rows: usize,
+    }
+
+    impl PackedVsTable {
+        pub fn new(rows: usize) -> Self {
+            let mut registry = Registry::new();
+            let a = registry.register::<A>();
+            let b = registry.register::<B>();
+            let c = registry.register::<C>();
+            let ids = [a, b, c];
+
+            let mut table = Table::new(&registry, &ids);
+            let mut packed = PackedTable::new(&registry, &ids);
+            let mut entities = crate::ecs::entity::Entities::new();
+            for i in 0..rows {
+                let entity = entities.alloc();
+                // SAFETY: `A`/`B`/`C` have no drop glue, so letting `va`/`vb`/`vc` also go
+                // out of scope normally (rather than forgetting them) is harmless even
+                // though `table`/`packed` now own copies too.
+                unsafe {
+                    let va = A(i as f32);
+                    table.write_component(a, (&va as *const A).cast(), 0);
+                    let vb = B(i as f32);
+                    table.write_component(b, (&vb as *const B).cast(), 0);
+                    let vc = C(i as f32);
+                    table.write_component(c, (&vc as *const C).cast(), 0);
+                    table.finish_push(entity);
+
+                    let va = A(i as f32);
+                    packed.write_component(a, (&va as *const A).cast());
+                    let vb = B(i as f32);
+                    packed.write_component(b, (&vb as *const B).cast());
+                    let vc = C(i as f32);
+                    packed.write_component(c, (&vc as *const C).cast());
+                    packed.finish_push(entity);
+                }
+            }
+
+            Self { table, packed, ids, rows }
+        }
+
+        fn sum_table(&self) -> f32 {
+            let a = self.table.column(self.ids[0]).unwrap();
+            let b = self.table.column(self.ids[1]).unwrap();
+            let c = self.table.column(self.ids[2]).unwrap();
+            (0..self.rows)
+                .map(|row| unsafe {
+                    (*(a.get(row).unwrap() as *const A)).0 + (*(b.get(row).unwrap() as *const B)).0 + (*(c.get(row).unwrap() as *const C)).0
+                })
+                .sum()
+        }
+
+        fn sum_packed(&self) -> f32 {
+            (0..self.rows)
+                .map(|row| unsafe {
+                    (*(self.packed.get(self.ids[0], row).unwrap() as *const A)).0
+                        + (*(self.packed.get(self.ids[1], row).unwrap() as *const B)).0
+                        + (*(self.packed.get(self.ids[2], row).unwrap() as *const C)).0
+                })
+                .sum()
+        }
+    }
+
+    impl Scenario for PackedVsTable {
+        fn name(&self) -> &str {
+            "packed_vs_table"
+        }
+
+        fn run_once(&mut self) {
+            let table_sum = self.sum_table();
+            let packed_sum = self.sum_packed();
+            std::hint::black_box((table_sum, packed_sum));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::bench::time_scenario;
+
+        #[test]
+        fn packed_and_table_sums_agree() {
+            let mut scenario = PackedVsTable::new(32);
+            assert_eq!(scenario.sum_table(), scenario.sum_packed());
+            time_scenario(&mut scenario, 3);
+        }
+    }
+}
+
+#[cfg(feature = "packed-storage")]
+pub use packed_vs_table::PackedVsTable;
+
+mod wide_match {
+    use crate::bench::Scenario;
+    use crate::ecs::component::{ComponentId, ComponentMask, Registry};
+    use crate::ecs::storage::Table;
+    use rusty_engine_macros::Component;
+
+    // Defines `W0..W31` and a `register_wide_columns` helper returning their ids, in one
+    // macro invocation, so a "wide archetype" of up to 32 distinct component types doesn't
+    // need typing out 32 near-identical struct defs and 32 near-identical `register` calls.
+    macro_rules! wide_columns {
+        ($($name:ident),+ $(,)?) => {
+            $(
+                #[derive(Component)]
+                struct $name(#[allow(dead_code)] f32);
+            )+
+
+            fn register_wide_columns(registry: &mut Registry) -> Vec<ComponentId> {
+                vec![$(registry.register::<$name>()),+]
+            }
+        };
+    }
+
+    wide_columns!(
+        W0, W1, W2, W3, W4, W5, W6, W7, W8, W9, W10, W11, W12, W13, W14, W15, W16, W17, W18, W19, W20, W21, W22, W23, W24, W25, W26, W27, W28,
+        W29, W30, W31,
+    );
+
+    /// Compares two ways of matching a many-component query against many wide archetypes:
+    /// `linear` checks each query id against a table with `Table::has_column` (one `HashMap`
+    /// lookup per id per table), while the bitset path builds a `ComponentMask` once and
+    /// checks each table with `Table::matches` (one word-AND per word of the query mask per
+    /// table). Every third table is missing one of the queried columns, so both paths have to
+    /// do real work rather than short-circuiting on the first id.
+    pub struct WideArchetypeQueryMatch {
+        tables: Vec<Table>,
+        query_ids: Vec<ComponentId>,
+        linear: bool,
+    }
+
+    impl WideArchetypeQueryMatch {
+        fn build(table_count: usize, linear: bool) -> Self {
+            let mut registry = Registry::new();
+            let columns = register_wide_columns(&mut registry);
+            let query_ids = columns[..columns.len() / 2].to_vec();
+
+            let tables = (0..table_count)
+                .map(|i| {
+                    let excluded = i % columns.len();
+                    let ids: Vec<ComponentId> = columns.iter().copied().enumerate().filter(|&(j, _)| j != excluded).map(|(_, id)| id).collect();
+                    Table::new(&registry, &ids)
+                })
+                .collect();
+
+            Self { tables, query_ids, linear }
+        }
+
+        pub fn linear_scan(table_count: usize) -> Self {
+            Self::build(table_count, true)
+        }
+
+        pub fn bitset(table_count: usize) -> Self {
+            Self::build(table_count, false)
+        }
+
+        fn matching_table_count(&self) -> usize {
+            if self.linear {
+                self.tables.iter().filter(|table| self.query_ids.iter().all(|&id| table.has_column(id))).count()
+            } else {
+                let mask = ComponentMask::from_ids(self.query_ids.iter().copied());
+                self.tables.iter().filter(|table| table.matches(&mask)).count()
+            }
+        }
+    }
+
+    impl Scenario for WideArchetypeQueryMatch {
+        fn name(&self) -> &str {
+            if self.linear {
+                "wide_archetype_query_match_linear"
+            } else {
+                "wide_archetype_query_match_bitset"
+            }
+        }
+
+        fn run_once(&mut self) {
+            std::hint::black_box(self.matching_table_count());
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::bench::time_scenario;
+
+        #[test]
+        fn linear_and_bitset_agree_on_which_tables_match() {
+            let mut linear = WideArchetypeQueryMatch::linear_scan(48);
+            let mut bitset = WideArchetypeQueryMatch::bitset(48);
+            assert_eq!(linear.matching_table_count(), bitset.matching_table_count());
+            assert!(linear.matching_table_count() > 0);
+            assert!(linear.matching_table_count() < 48);
+
+            time_scenario(&mut linear, 3);
+            time_scenario(&mut bitset, 3);
+        }
+    }
+}
+
+pub use wide_match::WideArchetypeQueryMatch;
+
+mod query_column_cache {
+    use crate::bench::Scenario;
+    use crate::ecs::component::ComponentMask;
+    use crate::ecs::storage::Table;
+    use crate::ecs::World;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component)]
+    struct A(f32);
+    #[derive(Component)]
+    struct B(f32);
+    #[derive(Component)]
+    struct C(f32);
+
+    /// Compares two ways of reading `(&A, &mut B, &C)` over every row of a table: `naive`
+    /// re-resolves each component's column with a fresh `Table::column`/`column_mut` call (a
+    /// `HashMap` lookup) every row, the way `QueryData::fetch` used to; `cached` resolves each
+    /// column once per table up front and then only does pointer arithmetic per row, the way
+    /// `query::result::resolve_columns` does for `Query::iter` today. Both share the exact
+    /// same table-matching and row-walking shape, so the only thing being measured is the
+    /// column-resolution strategy itself.
+    ///
+    /// A prototype-vs-baseline scenario rather than a permanent one, same as `PackedVsTable`:
+    /// it exists to show the column-cache is actually worth what it costs in complexity, not
+    /// to guard a shipped code path.
+    pub struct MultiComponentQueryIteration {
+        world: World,
+        entity_count: usize,
+        naive: bool,
+    }
+
+    impl MultiComponentQueryIteration {
+        fn build(entity_count: usize, naive: bool) -> Self {
+            let mut world = World::new();
+            for i in 0..entity_count {
+                world.spawn((A(i as f32), B(0.0), C(i as f32)));
+            }
+            Self { world, entity_count, naive }
+        }
+
+        pub fn naive(entity_count: usize) -> Self {
+            Self::build(entity_count, true)
+        }
+
+        pub fn cached(entity_count: usize) -> Self {
+            Self::build(entity_count, false)
+        }
+
+        fn matching_tables(&self, ids: [crate::ecs::component::ComponentId; 3]) -> Vec<*mut Table> {
+            let mask = ComponentMask::from_ids(ids.iter().copied());
+            self.world
+                .archetypes()
+                .iter()
+                .filter(|(_, table)| table.matches(&mask))
+                // SAFETY: this scenario never runs concurrently with any other access to
+                // `self.world`, so a `&Table` reborrowed as `*mut Table` here is exclusive in
+                // practice — the same reasoning `query::Result::current_table` relies on.
+                .map(|(_, table)| table as *const Table as *mut Table)
+                .collect()
+        }
+
+        fn run_naive(&mut self) {
+            let ids = [
+                self.world.registry().id_of::<A>().unwrap(),
+                self.world.registry().id_of::<B>().unwrap(),
+                self.world.registry().id_of::<C>().unwrap(),
+            ];
+            for table in self.matching_tables(ids) {
+                // SAFETY: `table` came from `matching_tables` above and every id in `ids`
+                // matched its mask, so every column lookup below is present.
+                unsafe {
+                    for row in 0..(*table).len() {
+                        let a = (*table).column(ids[0]).unwrap().get(row).unwrap() as *const A;
+                        let b = (*table).column_mut(ids[1]).unwrap().get_mut(row).unwrap() as *mut B;
+                        let c = (*table).column(ids[2]).unwrap().get(row).unwrap() as *const C;
+                        (*b).0 = (*a).0 + (*c).0;
+                    }
+                }
+            }
+        }
+
+        fn run_cached(&mut self) {
+            let ids = [
+                self.world.registry().id_of::<A>().unwrap(),
+                self.world.registry().id_of::<B>().unwrap(),
+                self.world.registry().id_of::<C>().unwrap(),
+            ];
+            for table in self.matching_tables(ids) {
+                // SAFETY: same as `run_naive` — resolved once per table instead of once per
+                // row, matching `query::result::resolve_columns`.
+                unsafe {
+                    let a_column = (*table).column(ids[0]).unwrap() as *const crate::ecs::storage::column::Column;
+                    let b_column = (*table).column_mut(ids[1]).unwrap() as *mut crate::ecs::storage::column::Column;
+                    let c_column = (*table).column(ids[2]).unwrap() as *const crate::ecs::storage::column::Column;
+                    for row in 0..(*table).len() {
+                        let a = (*a_column).get(row).unwrap() as *const A;
+                        let b = (*b_column).get_mut(row).unwrap() as *mut B;
+                        let c = (*c_column).get(row).unwrap() as *const C;
+                        (*b).0 = (*a).0 + (*c).0;
+                    }
+                }
+            }
+        }
+    }
+
+    impl Scenario for MultiComponentQueryIteration {
+        fn name(&self) -> &str {
+            if self.naive {
+                "multi_component_query_naive"
+            } else {
+                "multi_component_query_cached"
+            }
+        }
+
+        fn run_once(&mut self) {
+            if self.naive {
+                self.run_naive();
+            } else {
+                self.run_cached();
+            }
+        }
+
+        fn entity_count(&self) -> Option<usize> {
+            Some(self.entity_count)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::bench::time_scenario;
+        use crate::ecs::query::Query;
+
+        #[test]
+        fn naive_and_cached_settle_on_the_same_values() {
+            let mut naive = MultiComponentQueryIteration::naive(64);
+            let mut cached = MultiComponentQueryIteration::cached(64);
+            time_scenario(&mut naive, 1);
+            time_scenario(&mut cached, 1);
+
+            let mut naive_query: Query<&B> = Query::new(&mut naive.world);
+            let mut naive_values: Vec<f32> = naive_query.iter().map(|b| b.0).collect();
+            let mut cached_query: Query<&B> = Query::new(&mut cached.world);
+            let mut cached_values: Vec<f32> = cached_query.iter().map(|b| b.0).collect();
+            naive_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            cached_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(naive_values, cached_values);
+        }
+    }
+}
+
+pub use query_column_cache::MultiComponentQueryIteration;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::time_scenario;
+
+    #[test]
+    fn archetype_fragmentation_scenario_runs_without_panicking() {
+        let mut scenario = ArchetypeFragmentation::new(64);
+        time_scenario(&mut scenario, 3);
+        assert_eq!(scenario.entities.len(), 64);
+
+        let last = scenario.entities.last().copied().unwrap();
+        let fragment = scenario.world.entity_ref(last).unwrap().get::<Fragment>().unwrap();
+        assert_eq!(fragment.0, 63);
+    }
+
+    #[test]
+    fn toggle_component_scenario_leaves_entities_without_the_tag() {
+        let mut scenario = ToggleComponent::new(32);
+        time_scenario(&mut scenario, 3);
+
+        for &entity in &scenario.entities {
+            let r = scenario.world.entity_ref(entity).unwrap();
+            assert!(r.get::<Fragment>().is_some());
+            assert!(r.get::<Tag>().is_none());
+        }
+    }
+}
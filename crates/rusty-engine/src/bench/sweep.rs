@@ -0,0 +1,72 @@
+//! Runs a `Scenario` at several sizes to see how its timing scales, rather than at the one
+//! fixed size each `scenario` constructor happens to be called with elsewhere.
+//!
+//! Stands in for a Criterion `bench_with_input` group swept across an input range — this
+//! crate's harness is deliberately not Criterion (see `bench`'s module doc comment), so this
+//! reuses the same dependency-free `time_scenario_samples`/`Metrics` this module already has.
+
+use crate::bench::regression::Metrics;
+use crate::bench::{time_scenario_samples, Scenario};
+
+/// A reasonable default spread for eyeballing a scaling curve: an order of magnitude apart,
+/// small enough that the smallest size still shows fixed overhead and large enough that the
+/// largest one is dominated by per-entity cost.
+pub const DEFAULT_SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// One size's result from `sweep`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepPoint {
+    pub entity_count: usize,
+    pub metrics: Metrics,
+}
+
+/// Builds a scenario via `build(size)` for every size in `sizes`, times `iterations` runs of
+/// each with `time_scenario_samples`, and labels each result `"{name}/{entity_count}"` —
+/// mirroring how a Criterion `BenchmarkId` groups a benchmark's name with its input. Uses the
+/// built scenario's own `Scenario::entity_count()` rather than `size` directly, since a
+/// constructor is free to round or clamp its input; falls back to `size` for a scenario that
+/// doesn't report one.
+pub fn sweep(sizes: &[usize], build: impl Fn(usize) -> Box<dyn Scenario>, iterations: usize) -> Vec<(String, SweepPoint)> {
+    sizes
+        .iter()
+        .map(|&size| {
+            let mut scenario = build(size);
+            let entity_count = scenario.entity_count().unwrap_or(size);
+            let samples = time_scenario_samples(scenario.as_mut(), iterations);
+            let label = format!("{}/{entity_count}", scenario.name());
+            (label, SweepPoint {
+                entity_count,
+                metrics: Metrics::from_samples_ns(&samples),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::scenario::{IterateQuery, SpawnColumn};
+
+    // Small stand-ins for `DEFAULT_SIZES` — a real scaling curve wants 1k/10k/100k, but that
+    // would make the test suite noticeably slower for no correctness benefit.
+    const TEST_SIZES: [usize; 3] = [8, 16, 32];
+
+    #[test]
+    fn sweep_labels_each_point_by_name_and_entity_count() {
+        let points = sweep(&TEST_SIZES, |size| Box::new(SpawnColumn::bulk(size)), 3);
+
+        let labels: Vec<&str> = points.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["spawn_column_bulk/8", "spawn_column_bulk/16", "spawn_column_bulk/32"]);
+        for (i, (_, point)) in points.iter().enumerate() {
+            assert_eq!(point.entity_count, TEST_SIZES[i]);
+        }
+    }
+
+    #[test]
+    fn sweep_covers_the_iterate_microbenchmark_too() {
+        let points = sweep(&TEST_SIZES, |size| Box::new(IterateQuery::new(size)), 3);
+
+        let labels: Vec<&str> = points.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["iterate_query/8", "iterate_query/16", "iterate_query/32"]);
+    }
+}
@@ -0,0 +1,69 @@
+//! `cargo run --features regression_gate --bin regression_gate -- <baseline-file> [allowed-percent] [iterations]`
+//!
+//! Runs this crate's bench scenarios, compares their mean timing against a saved baseline,
+//! and exits non-zero if any regressed beyond the allowed percentage. Stands in for `cargo
+//! bench` pass/fail gating since this crate's harness (see `bench`'s module doc comment)
+//! isn't Criterion and has no built-in gate of its own.
+
+use rusty_engine::bench::frame_timer::FrameTimer;
+use rusty_engine::bench::regression::{self, Metrics};
+use rusty_engine::bench::scenario::{ArchetypeFragmentation, MultiComponentQueryIteration, SpawnColumn, SpawnSameBundle, ToggleComponent};
+use rusty_engine::bench::{time_scenario_samples, Scenario};
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+const DEFAULT_ALLOWED_PERCENT: f64 = 10.0;
+const DEFAULT_ITERATIONS: usize = 50;
+const WORST_FRAMES_SHOWN: usize = 3;
+
+fn scenarios() -> Vec<Box<dyn Scenario>> {
+    vec![
+        Box::new(ArchetypeFragmentation::new(256)),
+        Box::new(SpawnColumn::bulk(1000)),
+        Box::new(SpawnColumn::individual(1000)),
+        Box::new(ToggleComponent::new(256)),
+        Box::new(SpawnSameBundle::new(1000)),
+        Box::new(MultiComponentQueryIteration::naive(100_000)),
+        Box::new(MultiComponentQueryIteration::cached(100_000)),
+    ]
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(baseline_path) = args.first() else {
+        eprintln!("usage: regression_gate <baseline-file> [allowed-percent] [iterations]");
+        return ExitCode::FAILURE;
+    };
+    let allowed_percent = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(DEFAULT_ALLOWED_PERCENT);
+    let iterations = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(DEFAULT_ITERATIONS);
+
+    let baseline_text = match std::fs::read_to_string(baseline_path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("failed to read baseline {baseline_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let baseline = regression::parse_baseline(&baseline_text);
+
+    let mut current: HashMap<String, Metrics> = HashMap::new();
+    for mut scenario in scenarios() {
+        let samples = time_scenario_samples(scenario.as_mut(), iterations);
+        println!("{}: {}", scenario.name(), FrameTimer::from_samples_ns(&samples).summary(WORST_FRAMES_SHOWN));
+        current.insert(scenario.name().to_string(), Metrics::from_samples_ns(&samples));
+    }
+
+    let regressions = regression::compare(&baseline, &current, allowed_percent);
+    if regressions.is_empty() {
+        println!("no regressions beyond {allowed_percent}% across {} benchmark(s)", current.len());
+        ExitCode::SUCCESS
+    } else {
+        for regression in &regressions {
+            println!(
+                "REGRESSION: {} baseline={:.0}ns current={:.0}ns (+{:.1}%)",
+                regression.name, regression.baseline_ns, regression.current_ns, regression.percent_over
+            );
+        }
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,123 @@
+//! `Context` and `Logic`: gameplay code that runs against the `World` without reaching for
+//! global statics.
+
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::world::World;
+
+/// What a `Logic` implementation sees on each `on_init`/`on_update` call. Borrowed for the
+/// duration of that single call, so it can't be stashed and used after the `World` it wraps
+/// stops being borrowed.
+pub struct Context<'w> {
+    world: &'w mut World,
+}
+
+impl<'w> Context<'w> {
+    pub(crate) fn new(world: &'w mut World) -> Self {
+        Self { world }
+    }
+
+    pub fn world(&self) -> &World {
+        self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.world
+    }
+}
+
+/// Gameplay logic driven by the `Engine`'s schedule: `on_init` runs once before this
+/// `Logic`'s first `on_update`, then `on_update` runs once per invocation of whichever
+/// `Phase` it's added to.
+///
+/// This engine has no delta-time clock resource yet (see the backlog), so `Context` only
+/// exposes the `World` for now — a `time` accessor can be added to it later without changing
+/// `Logic`'s signature.
+pub trait Logic: Send + 'static {
+    /// Runs once, before this `Logic`'s first `on_update`. Default: does nothing.
+    fn on_init(&mut self, ctx: &mut Context) {
+        let _ = ctx;
+    }
+
+    /// Runs once per invocation of the phase this `Logic` was added to.
+    fn on_update(&mut self, ctx: &mut Context);
+}
+
+/// Adapts a `Logic` into a `System`, running `on_init` once on its first `run` and
+/// `on_update` on every `run` after that (including the first).
+pub struct LogicSystem<L> {
+    logic: L,
+    initialized: bool,
+}
+
+impl<L: Logic> LogicSystem<L> {
+    pub fn new(logic: L) -> Self {
+        Self { logic, initialized: false }
+    }
+}
+
+impl<L: Logic> System for LogicSystem<L> {
+    type Out = ();
+
+    fn run(&mut self, world: &mut World) {
+        let mut ctx = Context::new(world);
+        if !self.initialized {
+            self.logic.on_init(&mut ctx);
+            self.initialized = true;
+        }
+        self.logic.on_update(&mut ctx);
+    }
+}
+
+/// Marker type for the blanket `Logic` `IntoSystem` impl, so `Phase::add_system` (and its
+/// siblings) accept a `Logic` value directly, the same way they accept a plain closure.
+pub struct IsLogicSystem;
+
+impl<L: Logic> IntoSystem<IsLogicSystem> for L {
+    type System = LogicSystem<L>;
+
+    fn into_system(self) -> Self::System {
+        LogicSystem::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+    use crate::ecs::schedule::Phase;
+    use rusty_engine_macros::Component;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Marker(u32);
+
+    struct SpawnsThenCounts {
+        seen: Arc<Mutex<usize>>,
+    }
+
+    impl Logic for SpawnsThenCounts {
+        fn on_init(&mut self, ctx: &mut Context) {
+            ctx.world_mut().spawn(Marker(7));
+        }
+
+        fn on_update(&mut self, ctx: &mut Context) {
+            let mut query: Query<&Marker> = Query::new(ctx.world_mut());
+            *self.seen.lock().unwrap() = query.iter().count();
+        }
+    }
+
+    #[test]
+    fn logic_spawns_in_on_init_and_sees_it_in_on_update() {
+        let seen = Arc::new(Mutex::new(0));
+        let mut phase = Phase::new("update");
+        phase.add_system(SpawnsThenCounts { seen: seen.clone() });
+
+        let mut world = World::new();
+        phase.run(&mut world);
+        assert_eq!(*seen.lock().unwrap(), 1);
+
+        // on_init doesn't run again on a second tick, so the entity count stays put.
+        phase.run(&mut world);
+        assert_eq!(*seen.lock().unwrap(), 1);
+    }
+}
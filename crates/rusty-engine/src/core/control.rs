@@ -0,0 +1,106 @@
+//! Runtime control for `Engine::looped`: pause, single-step, resume, and quit requests
+//! observed between frames.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+const RUNNING: u8 = 0;
+const PAUSED: u8 = 1;
+const STEPPING: u8 = 2;
+const QUIT: u8 = 3;
+
+/// What `Engine::tick` should do this frame, decided from the current `Control` state.
+pub(crate) enum Tick {
+    /// Run every phase.
+    Full,
+    /// Paused: only `Render`-tagged phases run, so the frame still redraws. Everything else
+    /// (physics, gameplay) sits still until `step` or `resume`.
+    RenderOnly,
+    Quit,
+}
+
+/// A cheaply cloneable handle for requesting pause/step/resume/quit on a running `Engine`
+/// loop, e.g. from a CLI thread reading commands off stdin while the loop runs on its own
+/// thread.
+#[derive(Clone)]
+pub struct Control {
+    state: Arc<AtomicU8>,
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Control {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(AtomicU8::new(RUNNING)),
+        }
+    }
+
+    /// Pauses the loop: subsequent frames only run `Render`-tagged phases until `step` or
+    /// `resume` is called.
+    pub fn pause(&self) {
+        self.state.store(PAUSED, Ordering::SeqCst);
+    }
+
+    /// Resumes a paused loop, running every phase again from the next frame.
+    pub fn resume(&self) {
+        self.state.store(RUNNING, Ordering::SeqCst);
+    }
+
+    /// Requests exactly one full frame while paused, then returns to paused. A no-op if the
+    /// loop isn't currently paused.
+    pub fn step(&self) {
+        let _ = self.state.compare_exchange(PAUSED, STEPPING, Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    /// Requests the loop stop after its current frame.
+    pub fn quit(&self) {
+        self.state.store(QUIT, Ordering::SeqCst);
+    }
+
+    pub fn is_quit(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == QUIT
+    }
+
+    /// Consumes the current state into what this frame should do. `Stepping` reverts to
+    /// `Paused` immediately, so a single `step()` call only ever lets one frame through.
+    pub(crate) fn tick(&self) -> Tick {
+        match self.state.load(Ordering::SeqCst) {
+            RUNNING => Tick::Full,
+            STEPPING => {
+                self.state.store(PAUSED, Ordering::SeqCst);
+                Tick::Full
+            }
+            QUIT => Tick::Quit,
+            _ => Tick::RenderOnly,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_lets_exactly_one_frame_through_while_paused() {
+        let control = Control::new();
+        control.pause();
+        assert!(matches!(control.tick(), Tick::RenderOnly));
+
+        control.step();
+        assert!(matches!(control.tick(), Tick::Full));
+        assert!(matches!(control.tick(), Tick::RenderOnly));
+    }
+
+    #[test]
+    fn quit_is_sticky() {
+        let control = Control::new();
+        control.quit();
+        assert!(control.is_quit());
+        assert!(matches!(control.tick(), Tick::Quit));
+    }
+}
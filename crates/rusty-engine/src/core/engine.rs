@@ -0,0 +1,253 @@
+//! `Engine`: owns a `World` and `Schedule` and drives them frame by frame under a `Control`.
+
+use crate::core::control::{Control, Tick};
+use crate::core::service::Service;
+use crate::core::state::{State, StateMachine, TransitionError};
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::{Phase, Schedule, SystemId, World};
+
+/// The name a `Phase` must use to keep running while the loop is paused. Not a real
+/// run-condition system yet (see the backlog for that) — just enough tagging for `pause` to
+/// mean "stop simulating, keep drawing".
+pub const RENDER_PHASE: &str = "render";
+
+/// Runs a `Schedule` against a `World` once per `tick`, observing a `Control` handle so an
+/// external thread (a CLI, a debugger) can pause, single-step, resume, or quit the loop.
+pub struct Engine {
+    world: World,
+    schedule: Schedule,
+    control: Control,
+    state: StateMachine,
+}
+
+impl Engine {
+    pub fn new(control: Control) -> Self {
+        Self {
+            world: World::new(),
+            schedule: Schedule::new(),
+            control,
+            state: StateMachine::new(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state.current()
+    }
+
+    /// Moves from `Dead` (or a prior `Stopped`) through `Starting` into `Running`, so a bug
+    /// that calls `start` twice in a row without an intervening `stop` is caught here rather
+    /// than silently re-running setup. Services are added via `add_service` before this, the
+    /// same as today — `start` only tracks the lifecycle stage, it doesn't itself call into
+    /// any service.
+    pub fn start(&mut self) -> std::result::Result<(), TransitionError> {
+        self.state.try_transition(State::Starting)?;
+        self.state.try_transition(State::Running)
+    }
+
+    /// Moves from `Running` through `Stopping` into `Stopped`. Fails if the engine was never
+    /// started (or was already stopped) rather than silently doing nothing.
+    pub fn stop(&mut self) -> std::result::Result<(), TransitionError> {
+        self.state.try_transition(State::Stopping)?;
+        self.state.try_transition(State::Stopped)
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn control(&self) -> &Control {
+        &self.control
+    }
+
+    pub fn add_phase(&mut self, phase: Phase) -> &mut Self {
+        self.schedule.add_phase(phase);
+        self
+    }
+
+    /// Adds `system` to the named phase's parallel group, creating the phase (in schedule
+    /// order) the first time it's named. Lets a `Service` register into a phase — `"update"`,
+    /// `"render"` — without knowing or caring whether some other service already created it.
+    pub fn add_system<M>(&mut self, phase: &'static str, system: impl IntoSystem<M, System: System<Out = ()>>) -> SystemId {
+        if self.schedule.phase_mut(phase).is_none() {
+            self.schedule.add_phase(Phase::new(phase));
+        }
+        self.schedule.phase_mut(phase).expect("just inserted above").add_system(system)
+    }
+
+    /// Runs `service.start(self)`, letting a physics engine, renderer, or other subsystem
+    /// register its own systems (via `add_system`) and uniques (via
+    /// `world_mut().insert_unique`) instead of `Engine` needing to know its internals up
+    /// front. Turns a `Service` into a self-contained plugin.
+    pub fn add_service(&mut self, mut service: impl Service) -> &mut Self {
+        service.start(self);
+        self
+    }
+
+    /// Runs one frame's worth of work per the current `Control` state, returning whether the
+    /// loop should keep going (`false` once `quit()` has been requested).
+    pub fn tick(&mut self) -> bool {
+        match self.control.tick() {
+            Tick::Quit => false,
+            Tick::Full => {
+                self.schedule.run(&mut self.world);
+                true
+            }
+            Tick::RenderOnly => {
+                if let Some(phase) = self.schedule.phase_mut(RENDER_PHASE) {
+                    phase.run(&mut self.world);
+                }
+                true
+            }
+        }
+    }
+
+    /// Runs `tick` until it returns `false`, i.e. the frame loop the CLI's control handle
+    /// drives via `pause`/`step`/`resume`/`quit`.
+    pub fn looped(&mut self) {
+        while self.tick() {}
+    }
+
+    /// Runs `tick` up to `n` times, stopping early if `quit()` has been requested. Meant for
+    /// headless integration tests that want a deterministic number of frames without wiring
+    /// up a CLI's `Control` loop — a fresh `Control` defaults to running, so this behaves
+    /// like `looped()` bounded to `n` frames unless the test pauses or quits it itself.
+    ///
+    /// This engine has no delta-time clock resource yet (see `Context`'s doc comment), so
+    /// each frame here is just "run the schedule once" — there's no synthetic delta to feed
+    /// it; a system wanting per-frame movement drives its own fixed step from a `Unique` it
+    /// owns, the same way it would under `looped()`.
+    pub fn run_frames(&mut self, n: usize) {
+        for _ in 0..n {
+            if !self.tick() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+    use rusty_engine_macros::Component;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Velocity(f32, f32);
+
+    #[test]
+    fn paused_engine_only_advances_render_counter_until_stepped() {
+        let fixed = Arc::new(AtomicUsize::new(0));
+        let update = Arc::new(AtomicUsize::new(0));
+        let render = Arc::new(AtomicUsize::new(0));
+
+        let control = Control::new();
+        let mut engine = Engine::new(control.clone());
+
+        let fixed_counter = fixed.clone();
+        let mut fixed_phase = Phase::new("fixed");
+        fixed_phase.add_system(move |_: &mut World| {
+            fixed_counter.fetch_add(1, Ordering::SeqCst);
+        });
+        engine.add_phase(fixed_phase);
+
+        let update_counter = update.clone();
+        let mut update_phase = Phase::new("update");
+        update_phase.add_system(move |_: &mut World| {
+            update_counter.fetch_add(1, Ordering::SeqCst);
+        });
+        engine.add_phase(update_phase);
+
+        let render_counter = render.clone();
+        let mut render_phase = Phase::new(RENDER_PHASE);
+        render_phase.add_system(move |_: &mut World| {
+            render_counter.fetch_add(1, Ordering::SeqCst);
+        });
+        engine.add_phase(render_phase);
+
+        control.pause();
+        engine.tick();
+        assert_eq!(fixed.load(Ordering::SeqCst), 0);
+        assert_eq!(update.load(Ordering::SeqCst), 0);
+        assert_eq!(render.load(Ordering::SeqCst), 1);
+
+        control.step();
+        engine.tick();
+        assert_eq!(fixed.load(Ordering::SeqCst), 1);
+        assert_eq!(update.load(Ordering::SeqCst), 1);
+        assert_eq!(render.load(Ordering::SeqCst), 2);
+
+        engine.tick();
+        assert_eq!(fixed.load(Ordering::SeqCst), 1);
+        assert_eq!(update.load(Ordering::SeqCst), 1);
+        assert_eq!(render.load(Ordering::SeqCst), 3);
+
+        control.quit();
+        assert!(!engine.tick());
+    }
+
+    #[test]
+    fn run_frames_ticks_a_movement_system_a_fixed_number_of_times() {
+        let mut engine = Engine::new(Control::new());
+        let entity = engine.world_mut().spawn((Position(0.0, 0.0), Velocity(1.0, 2.0)));
+
+        let mut movement = Phase::new("update");
+        movement.add_system(|world: &mut World| {
+            let mut query: Query<(&mut Position, &Velocity)> = Query::new(world);
+            for (position, velocity) in query.iter() {
+                position.0 += velocity.0;
+                position.1 += velocity.1;
+            }
+        });
+        engine.add_phase(movement);
+
+        engine.run_frames(10);
+
+        let position = *engine.world().entity_ref(entity).unwrap().get::<Position>().unwrap();
+        assert_eq!(position, Position(10.0, 20.0));
+    }
+
+    #[test]
+    fn start_and_stop_advance_through_the_lifecycle_and_reject_skipping_starting() {
+        let mut engine = Engine::new(Control::new());
+        assert_eq!(engine.state(), State::Dead);
+
+        engine.start().unwrap();
+        assert_eq!(engine.state(), State::Running);
+
+        // Starting again without stopping first would skip back through `Starting`, which
+        // isn't a legal move from `Running`.
+        assert!(engine.start().is_err());
+
+        engine.stop().unwrap();
+        assert_eq!(engine.state(), State::Stopped);
+    }
+
+    #[test]
+    fn run_frames_stops_early_once_quit_is_requested() {
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let control = Control::new();
+        let mut engine = Engine::new(control.clone());
+
+        let counter = ticks.clone();
+        let mut phase = Phase::new("update");
+        phase.add_system(move |_: &mut World| {
+            let seen = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            if seen == 3 {
+                control.quit();
+            }
+        });
+        engine.add_phase(phase);
+
+        engine.run_frames(10);
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+    }
+}
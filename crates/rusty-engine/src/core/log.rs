@@ -0,0 +1,125 @@
+//! A minimal logging facade: `Level`-tagged, optionally structured `Record`s sent over a
+//! channel to whatever's consuming them (a CLI printer, a file sink, a test).
+//!
+//! This crate doesn't depend on the `log` crate yet, so `ChannelLogger` is a small facade of
+//! its own rather than an implementation of `log::Log` — close enough in shape (level, target,
+//! message, structured fields) to swap over later without changing callers much.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Log severity, most to least severe. Lower variants are considered "louder" — filtering
+/// keeps a record when its `Level` is at or above the configured threshold's severity, i.e.
+/// `record_level <= threshold`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// One emitted log line: its level, the module/system that logged it, a message, and any
+/// structured key/value fields attached for a future JSON sink to pick up without re-parsing
+/// `message`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub level: Level,
+    pub target: &'static str,
+    pub message: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Record {
+    /// True if `self` should still be shown under `threshold`, e.g. for a CLI consumer that
+    /// wants to additionally filter by level at display time rather than only at the source.
+    pub fn passes(&self, threshold: Level) -> bool {
+        self.level <= threshold
+    }
+}
+
+/// Sends every logged `Record` passing its level filter over an `mpsc` channel, for a CLI
+/// thread (or test) to drain independently of whatever's producing them.
+///
+/// Filtering happens in two layers: `max_level` is the default threshold for any target
+/// without its own entry in `target_levels`, which overrides it per-target (e.g. quieting a
+/// noisy subsystem without turning down everything else).
+pub struct ChannelLogger {
+    sender: Sender<Record>,
+    max_level: Level,
+    target_levels: std::collections::HashMap<&'static str, Level>,
+}
+
+impl ChannelLogger {
+    /// Builds a logger at `max_level` and the `Receiver` it sends every passing `Record` to.
+    pub fn with_receiver(max_level: Level) -> (Self, Receiver<Record>) {
+        let (sender, receiver) = mpsc::channel();
+        (
+            Self {
+                sender,
+                max_level,
+                target_levels: std::collections::HashMap::new(),
+            },
+            receiver,
+        )
+    }
+
+    /// Overrides the level threshold for `target` alone, independent of `max_level`.
+    pub fn set_target_level(&mut self, target: &'static str, level: Level) {
+        self.target_levels.insert(target, level);
+    }
+
+    fn threshold_for(&self, target: &str) -> Level {
+        self.target_levels.get(target).copied().unwrap_or(self.max_level)
+    }
+
+    /// Sends a `Record` if `level` passes `target`'s threshold. Silently drops it (no error;
+    /// a full receiver isn't the sender's problem) if it's filtered out or the channel's
+    /// receiver has already been dropped.
+    pub fn log(&self, level: Level, target: &'static str, message: impl Into<String>, fields: Vec<(&'static str, String)>) {
+        if level > self.threshold_for(target) {
+            return;
+        }
+        let _ = self.sender.send(Record {
+            level,
+            target,
+            message: message.into(),
+            fields,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_target_level_overrides_the_default_max_level() {
+        let (mut logger, receiver) = ChannelLogger::with_receiver(Level::Info);
+        logger.set_target_level("noisy", Level::Error);
+
+        logger.log(Level::Debug, "quiet", "ignored, above default threshold", vec![]);
+        logger.log(Level::Info, "quiet", "kept, at default threshold", vec![]);
+        logger.log(Level::Warn, "noisy", "ignored, above the per-target threshold", vec![]);
+        logger.log(Level::Error, "noisy", "kept, at the per-target threshold", vec![("code", "42".to_string())]);
+
+        let received: Vec<Record> = receiver.try_iter().collect();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].message, "kept, at default threshold");
+        assert_eq!(received[1].message, "kept, at the per-target threshold");
+        assert_eq!(received[1].fields, vec![("code", "42".to_string())]);
+    }
+
+    #[test]
+    fn record_passes_reflects_display_time_filtering() {
+        let record = Record {
+            level: Level::Debug,
+            target: "t",
+            message: "m".to_string(),
+            fields: vec![],
+        };
+        assert!(!record.passes(Level::Info));
+        assert!(record.passes(Level::Debug));
+        assert!(record.passes(Level::Trace));
+    }
+}
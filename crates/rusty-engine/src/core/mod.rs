@@ -0,0 +1,19 @@
+//! Engine-level scaffolding built on top of `ecs`: the frame loop and its runtime controls.
+
+pub mod context;
+pub mod control;
+pub mod engine;
+pub mod log;
+pub mod plugin;
+pub mod service;
+pub mod state;
+pub mod tasks;
+
+pub use context::{Context, Logic, LogicSystem};
+pub use control::Control;
+pub use engine::Engine;
+pub use log::{ChannelLogger, Level, Record};
+pub use plugin::{App, Plugin};
+pub use service::Service;
+pub use state::{State, StateMachine, TransitionError};
+pub use tasks::Executor;
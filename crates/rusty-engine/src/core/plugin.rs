@@ -0,0 +1,123 @@
+//! `Plugin`: a composable bundle of systems, uniques, and phases registered into an
+//! `Engine` through an `App` view, formalizing the ad-hoc pattern `Service` left to each
+//! caller to hand-roll.
+
+use crate::core::engine::Engine;
+use crate::ecs::component::{Component, ComponentId};
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::unique::Unique;
+use crate::ecs::{Phase, SystemId};
+
+/// A physics engine, renderer, audio subsystem, or any other plugin-shaped chunk of
+/// functionality that needs to add its own systems, uniques, and component registrations
+/// into the `Engine` it's running under, without `Engine` needing to know about it up
+/// front. Register one with `Engine::add_plugin`.
+pub trait Plugin: 'static {
+    /// Called once, before the frame loop starts. Register systems, uniques, and
+    /// components through `app` rather than reaching into the `Engine`/`World` directly.
+    fn build(&self, app: &mut App);
+}
+
+/// A narrow, plugin-facing view onto the `Engine` being assembled, handed to
+/// `Plugin::build` so a plugin can add systems, uniques, and components without holding
+/// (or needing) the rest of `Engine`'s API.
+pub struct App<'a> {
+    engine: &'a mut Engine,
+}
+
+impl<'a> App<'a> {
+    fn new(engine: &'a mut Engine) -> Self {
+        Self { engine }
+    }
+
+    /// Adds `phase` to the underlying schedule, same as `Engine::add_phase`.
+    pub fn add_phase(&mut self, phase: Phase) -> &mut Self {
+        self.engine.add_phase(phase);
+        self
+    }
+
+    /// Adds `system` to the named phase, creating it in schedule order if needed — see
+    /// `Engine::add_system`.
+    pub fn add_system<M>(&mut self, phase: &'static str, system: impl IntoSystem<M, System: System<Out = ()>>) -> SystemId {
+        self.engine.add_system(phase, system)
+    }
+
+    /// Inserts `value` as the world's singleton `U`, same as `World::insert_unique`.
+    pub fn add_unique<U: Unique>(&mut self, value: U) -> &mut Self {
+        self.engine.world_mut().insert_unique(value);
+        self
+    }
+
+    /// Registers `C` with the world's component registry, same as `Registry::register`.
+    pub fn register_component<C: Component>(&mut self) -> ComponentId {
+        self.engine.world_mut().registry_mut().register::<C>()
+    }
+
+    /// Escape hatch for a plugin that needs the full `Engine` API (services, control,
+    /// world access beyond registration).
+    pub fn engine_mut(&mut self) -> &mut Engine {
+        self.engine
+    }
+}
+
+impl Engine {
+    /// Runs `plugin.build(&mut app)`, the `Plugin` counterpart to `add_service` — the
+    /// difference is `Plugin` only sees the narrow `App` view instead of the whole
+    /// `Engine`, so composing several plugins can't accidentally depend on engine
+    /// internals beyond systems, uniques, and components.
+    pub fn add_plugin(&mut self, plugin: impl Plugin) -> &mut Self {
+        plugin.build(&mut App::new(self));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::control::Control;
+    use crate::ecs::World;
+    use rusty_engine_macros::Component;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct PhysicsConfig {
+        gravity: f32,
+    }
+
+    impl Unique for PhysicsConfig {}
+
+    #[derive(Component)]
+    struct Mass(#[allow(dead_code)] f32);
+
+    struct PhysicsPlugin {
+        ticks: Arc<AtomicUsize>,
+    }
+
+    impl Plugin for PhysicsPlugin {
+        fn build(&self, app: &mut App) {
+            app.register_component::<Mass>();
+            app.add_unique(PhysicsConfig { gravity: 9.8 });
+
+            let ticks = self.ticks.clone();
+            app.add_system("update", move |_: &mut World| {
+                ticks.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    #[test]
+    fn a_plugin_registers_its_component_unique_and_system_and_the_system_runs_on_a_later_frame() {
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let mut engine = Engine::new(Control::new());
+        engine.add_plugin(PhysicsPlugin { ticks: ticks.clone() });
+
+        assert_eq!(engine.world().unique::<PhysicsConfig>().unwrap().gravity, 9.8);
+        assert!(engine.world().registry().id_of_type(std::any::TypeId::of::<Mass>()).is_some());
+        assert_eq!(ticks.load(Ordering::SeqCst), 0);
+
+        engine.tick();
+        assert_eq!(ticks.load(Ordering::SeqCst), 1);
+        engine.tick();
+        assert_eq!(ticks.load(Ordering::SeqCst), 2);
+    }
+}
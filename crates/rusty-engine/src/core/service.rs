@@ -0,0 +1,63 @@
+//! `Service`: a self-contained bundle of systems and uniques that registers itself into an
+//! `Engine` at startup.
+
+use crate::core::engine::Engine;
+
+/// A physics engine, renderer, audio subsystem, or any other plugin-shaped chunk of
+/// functionality that needs to add its own systems (to whichever phases it cares about) and
+/// uniques (its config, its resource handles) into the `Engine` it's running under, without
+/// `Engine` needing to know about it up front.
+///
+/// Register one with `Engine::add_service`.
+pub trait Service: 'static {
+    /// Called once, before the frame loop starts. Register systems with
+    /// `engine.add_system(phase, system)` and uniques with
+    /// `engine.world_mut().insert_unique(value)`.
+    fn start(&mut self, engine: &mut Engine);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::control::Control;
+    use crate::ecs::unique::Unique;
+    use crate::ecs::World;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct PhysicsConfig {
+        gravity: f32,
+    }
+
+    impl Unique for PhysicsConfig {}
+
+    struct Physics {
+        ticks: Arc<AtomicUsize>,
+    }
+
+    impl Service for Physics {
+        fn start(&mut self, engine: &mut Engine) {
+            engine.world_mut().insert_unique(PhysicsConfig { gravity: 9.8 });
+
+            let ticks = self.ticks.clone();
+            engine.add_system("update", move |_: &mut World| {
+                ticks.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    #[test]
+    fn a_service_registers_its_system_and_unique_and_the_system_runs_on_a_later_frame() {
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let mut engine = Engine::new(Control::new());
+        engine.add_service(Physics { ticks: ticks.clone() });
+
+        assert_eq!(engine.world().unique::<PhysicsConfig>().unwrap().gravity, 9.8);
+        assert_eq!(ticks.load(Ordering::SeqCst), 0);
+
+        engine.tick();
+        assert_eq!(ticks.load(Ordering::SeqCst), 1);
+        engine.tick();
+        assert_eq!(ticks.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! `StateMachine`: validates the coarse lifecycle an `Engine` moves through, so a caller
+//! can't accidentally skip a step (e.g. going straight from `Dead` to `Running`).
+
+use std::fmt;
+
+/// The engine's lifecycle stage. `Stopped` can restart back to `Starting`; every other
+/// transition only moves forward one step at a time — see `StateMachine::try_transition`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Dead,
+    Starting,
+    Running,
+    Stopping,
+    Stopped,
+}
+
+/// An attempted transition that skips a required step or moves backward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionError {
+    pub from: State,
+    pub to: State,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "illegal state transition: {:?} -> {:?}", self.from, self.to)
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+/// Tracks the current `State` and rejects any transition that isn't one step along the
+/// lifecycle: `Dead -> Starting -> Running -> Stopping -> Stopped`, with `Stopped ->
+/// Starting` allowed so an `Engine` can be restarted without being rebuilt from scratch.
+#[derive(Debug, Clone, Copy)]
+pub struct StateMachine {
+    current: State,
+}
+
+impl Default for StateMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StateMachine {
+    /// Starts in `State::Dead`, matching an `Engine` that hasn't been started yet.
+    pub fn new() -> Self {
+        Self { current: State::Dead }
+    }
+
+    pub fn current(&self) -> State {
+        self.current
+    }
+
+    /// Moves to `to` if it's a legal next step from the current state, updating `current`.
+    /// Leaves the state untouched on an illegal transition.
+    pub fn try_transition(&mut self, to: State) -> std::result::Result<(), TransitionError> {
+        let legal = matches!(
+            (self.current, to),
+            (State::Dead, State::Starting)
+                | (State::Starting, State::Running)
+                | (State::Running, State::Stopping)
+                | (State::Stopping, State::Stopped)
+                | (State::Stopped, State::Starting)
+        );
+        if !legal {
+            return Err(TransitionError { from: self.current, to });
+        }
+        self.current = to;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legal_transitions_advance_through_the_full_lifecycle() {
+        let mut machine = StateMachine::new();
+        assert_eq!(machine.current(), State::Dead);
+
+        machine.try_transition(State::Starting).unwrap();
+        machine.try_transition(State::Running).unwrap();
+        machine.try_transition(State::Stopping).unwrap();
+        machine.try_transition(State::Stopped).unwrap();
+        assert_eq!(machine.current(), State::Stopped);
+
+        // Restarting from `Stopped` is the one allowed backward-looking move.
+        machine.try_transition(State::Starting).unwrap();
+        assert_eq!(machine.current(), State::Starting);
+    }
+
+    #[test]
+    fn skipping_starting_is_rejected() {
+        let mut machine = StateMachine::new();
+        let err = machine.try_transition(State::Running).unwrap_err();
+        assert_eq!(err, TransitionError { from: State::Dead, to: State::Running });
+        // A rejected transition doesn't move the state.
+        assert_eq!(machine.current(), State::Dead);
+    }
+
+    #[test]
+    fn stopped_cannot_jump_straight_back_to_running() {
+        let mut machine = StateMachine::new();
+        machine.try_transition(State::Starting).unwrap();
+        machine.try_transition(State::Running).unwrap();
+        machine.try_transition(State::Stopping).unwrap();
+        machine.try_transition(State::Stopped).unwrap();
+
+        assert!(machine.try_transition(State::Running).is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//! A fixed-ish worker pool for running jobs off the main loop thread, e.g. asset loading or
+//! background pathfinding that shouldn't block a frame.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, Once};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+enum Message {
+    Job(Job),
+    Shutdown,
+}
+
+/// A worker's panic, caught around its job via `catch_unwind` rather than left to unwind the
+/// thread (which would silently shrink the pool by one). Sent to `Executor::try_recv_panic`
+/// instead of only living as a swallowed default backtrace print, so a job that panics is
+/// something the main loop can log or report on rather than a job that just never finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    /// The worker thread's name, e.g. `"rusty-worker-2"`.
+    pub thread: String,
+    /// The panic message, downcast from the payload's `&str`/`String` if it was one (the
+    /// vast majority of panics, including every `panic!`/`assert!` macro) or a placeholder
+    /// otherwise.
+    pub payload: String,
+}
+
+fn panic_payload_to_string(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Wraps the process's default panic hook so a worker thread's panic (named
+/// `"rusty-worker-*"`, see `Executor::spawn_worker`) doesn't print its own backtrace —
+/// `catch_unwind` around each job already turns it into a `PanicReport` for
+/// `Executor::try_recv_panic`, so the default print would just be noise duplicating that.
+/// Every other thread's panics (the main thread, a caller's own threads) print exactly as
+/// before. Installed once per process, the first time any `Executor` is created.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let on_worker = thread::current().name().is_some_and(|name| name.starts_with("rusty-worker-"));
+            if !on_worker {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+/// A pool of worker threads pulling jobs off a shared queue.
+///
+/// `resize` and `shutdown` both work by sending one `Shutdown` message per thread to remove:
+/// since the queue is FIFO, a `Shutdown` sent after a batch of submitted jobs is only consumed
+/// once every job ahead of it has run, so shrinking the pool never drops in-flight work.
+pub struct Executor {
+    sender: Sender<Message>,
+    receiver: Arc<Mutex<mpsc::Receiver<Message>>>,
+    workers: Vec<JoinHandle<()>>,
+    /// Whether `par_for_each` lets an idle worker steal from a busy one's shard, rather than
+    /// sticking to its own static split. Exposed so callers can benchmark one against the
+    /// other on their own workloads.
+    stealing: bool,
+    /// Ever-increasing, never reused across a `resize`, so a worker spawned to replace one
+    /// that shrank away still gets a name distinct from every worker that ever ran before it.
+    next_worker_id: usize,
+    panic_sender: Sender<PanicReport>,
+    panics: Mutex<mpsc::Receiver<PanicReport>>,
+}
+
+/// One deque per worker for a `par_for_each` batch, seeded from the caller's static shards
+/// (e.g. one archetype's rows apiece — the "static row-chunking" every worker starts with).
+/// A worker drains its own shard from the front; when `stealing` is enabled, an idle worker
+/// also raids the *back* of another worker's shard once its own runs dry, so one shard far
+/// larger than its peers doesn't strand its own worker to grind through it alone while
+/// everyone else sits idle.
+struct Shards<T> {
+    deques: Vec<Mutex<VecDeque<T>>>,
+    stealing: bool,
+}
+
+impl<T> Shards<T> {
+    /// Assigns `shards` to `workers` deques round-robin (so up to `workers` shards land one
+    /// per worker, matching the pre-stealing static split exactly) and folds any excess
+    /// shards onto that same rotation.
+    fn new(shards: Vec<Vec<T>>, workers: usize, stealing: bool) -> Self {
+        let workers = workers.max(1);
+        let mut deques: Vec<VecDeque<T>> = (0..workers).map(|_| VecDeque::new()).collect();
+        for (index, shard) in shards.into_iter().enumerate() {
+            deques[index % workers].extend(shard);
+        }
+        Self {
+            deques: deques.into_iter().map(Mutex::new).collect(),
+            stealing,
+        }
+    }
+
+    /// Takes the next item for `worker`: its own shard first, then (if stealing is enabled)
+    /// the tail of another worker's shard.
+    fn next(&self, worker: usize) -> Option<T> {
+        if let Some(item) = self.deques[worker].lock().expect("shard mutex poisoned").pop_front() {
+            return Some(item);
+        }
+        if !self.stealing {
+            return None;
+        }
+        for other in 0..self.deques.len() {
+            if other == worker {
+                continue;
+            }
+            if let Some(item) = self.deques[other].lock().expect("shard mutex poisoned").pop_back() {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+/// A handle to a value a background job is computing, returned by `Executor::submit_task`.
+/// Meant to be `poll`ed from the main loop between frames (e.g. once per update) rather than
+/// blocked on, so a background load doesn't stall a frame waiting for it.
+pub struct TaskHandle<T> {
+    result: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Whether the job has finished and its result is ready to `try_take`.
+    pub fn poll(&self) -> bool {
+        self.result.lock().expect("task result mutex poisoned").is_some()
+    }
+
+    /// Takes the result if the job has finished. Returns `None` both before completion and on
+    /// a second call after a successful take — the result is only ever handed out once.
+    pub fn try_take(&self) -> Option<T> {
+        self.result.lock().expect("task result mutex poisoned").take()
+    }
+}
+
+impl Executor {
+    /// Spawns `size` worker threads, named `"rusty-worker-0"`, `"rusty-worker-1"`, etc.
+    pub fn new(size: usize) -> Self {
+        install_panic_hook();
+        let (sender, receiver) = mpsc::channel();
+        let (panic_sender, panic_receiver) = mpsc::channel();
+        let mut executor = Self {
+            sender,
+            receiver: Arc::new(Mutex::new(receiver)),
+            workers: Vec::with_capacity(size),
+            stealing: true,
+            next_worker_id: 0,
+            panic_sender,
+            panics: Mutex::new(panic_receiver),
+        };
+        for _ in 0..size {
+            let worker = executor.spawn_worker();
+            executor.workers.push(worker);
+        }
+        executor
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Takes the next worker panic reported since the last call, if any, oldest first. Meant
+    /// to be polled from the main loop (e.g. once per frame) the same way `TaskHandle::poll`
+    /// is, rather than blocked on.
+    pub fn try_recv_panic(&self) -> Option<PanicReport> {
+        self.panics.lock().expect("executor panic mutex poisoned").try_recv().ok()
+    }
+
+    /// Whether `par_for_each` currently allows work-stealing between shards.
+    pub fn stealing(&self) -> bool {
+        self.stealing
+    }
+
+    /// Enables or disables work-stealing for future `par_for_each` calls, e.g. to benchmark
+    /// against the plain static split.
+    pub fn set_stealing(&mut self, stealing: bool) {
+        self.stealing = stealing;
+    }
+
+    /// Runs `f` once per item across every shard in `shards` (e.g. one shard per archetype
+    /// table), spread across the pool. Each shard statically starts on one worker; if
+    /// `stealing` is enabled, a worker that drains its own shard steals from the tail of
+    /// another worker's shard instead of sitting idle, which matters when the shards are
+    /// wildly uneven (e.g. one archetype's table dwarfing the rest). Blocks until every item
+    /// has run.
+    pub fn par_for_each<T, F>(&self, shards: Vec<Vec<T>>, f: F)
+    where
+        T: Send + 'static,
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        let workers = self.worker_count().max(1);
+        let shards = Arc::new(Shards::new(shards, workers, self.stealing));
+        let f = Arc::new(f);
+        let (done, joined) = mpsc::channel::<()>();
+
+        for worker in 0..workers {
+            let shards = Arc::clone(&shards);
+            let f = Arc::clone(&f);
+            let done = done.clone();
+            self.submit(move || {
+                while let Some(item) = shards.next(worker) {
+                    f(item);
+                }
+                let _ = done.send(());
+            });
+        }
+        drop(done);
+        for _ in 0..workers {
+            let _ = joined.recv();
+        }
+    }
+
+    /// Queues `job` to run on the next free worker.
+    pub fn submit(&self, job: impl FnOnce() + Send + 'static) {
+        let _ = self.sender.send(Message::Job(Box::new(job)));
+    }
+
+    /// Queues `job` to run on the next free worker and returns a `TaskHandle` for its result,
+    /// e.g. kicking off an asset load from the main thread and polling for it between frames
+    /// instead of blocking on it. Unlike plain `submit`, which fires a job off with no way to
+    /// observe completion, this stashes the result somewhere `TaskHandle::poll` can see.
+    pub fn submit_task<T, F>(&self, job: F) -> TaskHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let result = Arc::new(Mutex::new(None));
+        let stored = Arc::clone(&result);
+        self.submit(move || {
+            *stored.lock().expect("task result mutex poisoned") = Some(job());
+        });
+        TaskHandle { result }
+    }
+
+    /// Grows or shrinks the pool to `size` workers. Shrinking joins the removed workers only
+    /// after they've drained every job queued ahead of their shutdown signal.
+    pub fn resize(&mut self, size: usize) {
+        let current = self.workers.len();
+        if size > current {
+            let mut new_workers = Vec::with_capacity(size - current);
+            for _ in current..size {
+                new_workers.push(self.spawn_worker());
+            }
+            self.workers.extend(new_workers);
+        } else if size < current {
+            let removing = current - size;
+            for _ in 0..removing {
+                let _ = self.sender.send(Message::Shutdown);
+            }
+            for worker in self.workers.drain(..removing) {
+                let _ = worker.join();
+            }
+        }
+    }
+
+    /// Signals every worker to stop once it's drained the queue up to its shutdown message,
+    /// then joins them. Safe to call more than once.
+    pub fn shutdown(&mut self) {
+        for _ in 0..self.workers.len() {
+            let _ = self.sender.send(Message::Shutdown);
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+
+    /// Spawns one worker named `"rusty-worker-{id}"`, `id` taken from (and incrementing)
+    /// `self.next_worker_id`. Each job runs inside `catch_unwind` so a panic reports through
+    /// `panic_sender` and moves on to the next job instead of killing the thread and quietly
+    /// shrinking the pool.
+    fn spawn_worker(&mut self) -> JoinHandle<()> {
+        let id = self.next_worker_id;
+        self.next_worker_id += 1;
+        let receiver = Arc::clone(&self.receiver);
+        let panic_sender = self.panic_sender.clone();
+        thread::Builder::new()
+            .name(format!("rusty-worker-{id}"))
+            .spawn(move || loop {
+                let message = receiver.lock().expect("executor worker mutex poisoned").recv();
+                match message {
+                    Ok(Message::Job(job)) => {
+                        if let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job)) {
+                            let thread = thread::current().name().unwrap_or("<unnamed>").to_string();
+                            let _ = panic_sender.send(PanicReport { thread, payload: panic_payload_to_string(&*payload) });
+                        }
+                    }
+                    Ok(Message::Shutdown) | Err(_) => break,
+                }
+            })
+            .expect("failed to spawn executor worker thread")
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn tasks_submitted_after_resize_still_run() {
+        let mut executor = Executor::new(2);
+        executor.resize(4);
+        assert_eq!(executor.worker_count(), 4);
+
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..8 {
+            let done = Arc::clone(&done);
+            executor.submit(move || {
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        for _ in 0..100 {
+            if done.load(Ordering::SeqCst) == 8 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(done.load(Ordering::SeqCst), 8);
+
+        executor.resize(1);
+        assert_eq!(executor.worker_count(), 1);
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let signal = Arc::clone(&done);
+        executor.submit(move || {
+            signal.fetch_add(1, Ordering::SeqCst);
+        });
+        for _ in 0..100 {
+            if done.load(Ordering::SeqCst) == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn submit_task_result_appears_once_the_job_completes() {
+        let executor = Executor::new(2);
+        let handle = executor.submit_task(|| {
+            thread::sleep(Duration::from_millis(20));
+            21 * 2
+        });
+
+        assert!(!handle.poll());
+
+        let mut result = None;
+        for _ in 0..100 {
+            if handle.poll() {
+                result = handle.try_take();
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(result, Some(42));
+
+        // Once taken, the result is gone even though the job already ran.
+        assert!(!handle.poll());
+        assert_eq!(handle.try_take(), None);
+    }
+
+    #[test]
+    fn shutdown_joins_every_worker_without_deadlock() {
+        let mut executor = Executor::new(3);
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..5 {
+            let done = Arc::clone(&done);
+            executor.submit(move || {
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        executor.shutdown();
+        assert_eq!(executor.worker_count(), 0);
+        assert_eq!(done.load(Ordering::SeqCst), 5);
+
+        // Idempotent: shutting down an already-empty pool doesn't deadlock or panic.
+        executor.shutdown();
+    }
+
+    #[test]
+    fn a_panicking_task_reports_its_worker_thread_name_and_payload() {
+        let executor = Executor::new(1);
+        executor.submit(|| panic!("boom"));
+
+        let mut report = None;
+        for _ in 0..100 {
+            if let Some(found) = executor.try_recv_panic() {
+                report = Some(found);
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let report = report.expect("the panic should have been reported within 1s");
+        assert_eq!(report.thread, "rusty-worker-0");
+        assert_eq!(report.payload, "boom");
+
+        // The worker survives the panic and keeps processing later jobs rather than dying
+        // and quietly shrinking the pool.
+        let done = Arc::new(AtomicUsize::new(0));
+        let signal = Arc::clone(&done);
+        executor.submit(move || {
+            signal.fetch_add(1, Ordering::SeqCst);
+        });
+        for _ in 0..100 {
+            if done.load(Ordering::SeqCst) == 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+        assert_eq!(executor.worker_count(), 1);
+    }
+
+    /// One giant shard (items `0..100`, standing in for one huge archetype table) plus three
+    /// tiny ones (one item each, standing in for tiny archetypes): once a tiny shard's own
+    /// item is taken, stealing should pull from the *back* of the giant shard rather than
+    /// leaving that worker idle.
+    fn giant_and_tiny_shards() -> Vec<Vec<usize>> {
+        vec![(0..100).collect(), vec![100], vec![101], vec![102]]
+    }
+
+    #[test]
+    fn shards_steal_from_the_giant_shard_when_enabled() {
+        let shards = Shards::new(giant_and_tiny_shards(), 4, true);
+
+        assert_eq!(shards.next(1), Some(100));
+        assert_eq!(shards.next(2), Some(101));
+        assert_eq!(shards.next(3), Some(102));
+
+        // Each tiny worker's own shard is now empty, so `next` steals from shard 0's tail.
+        assert_eq!(shards.next(1), Some(99));
+        assert_eq!(shards.next(2), Some(98));
+        assert_eq!(shards.next(3), Some(97));
+    }
+
+    #[test]
+    fn shards_never_steal_when_disabled() {
+        let shards = Shards::new(giant_and_tiny_shards(), 4, false);
+
+        assert_eq!(shards.next(1), Some(100));
+        // Shard 1 had exactly one item and stealing is off, so it's dry now, even though
+        // shard 0 still holds 99 items.
+        assert_eq!(shards.next(1), None);
+    }
+
+    /// A giant table (100 rows) alongside many tiny ones, run through the real pool: every
+    /// row must still be visited exactly once, whether or not stealing is enabled.
+    #[test]
+    fn par_for_each_visits_every_item_across_uneven_shards() {
+        let expected: Vec<usize> = (0..103).collect();
+
+        for stealing in [false, true] {
+            let mut executor = Executor::new(4);
+            executor.set_stealing(stealing);
+
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let log = Arc::clone(&seen);
+            executor.par_for_each(giant_and_tiny_shards(), move |item| {
+                log.lock().unwrap().push(item);
+            });
+
+            let mut seen = seen.lock().unwrap().clone();
+            seen.sort_unstable();
+            assert_eq!(seen, expected);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! `CommandBuffer`: a queue of entity operations recorded independent of a live `World`,
+//! applied later in one shot via `World::apply_commands`. Meant for code that builds up a batch
+//! of spawns/despawns/inserts/removes without `&mut World` in hand — e.g. a loader assembling a
+//! scene off to the side — decoupling command application from `Phase::run`.
+
+use crate::ecs::component::{Component, Set};
+use crate::ecs::entity::Entity;
+use crate::ecs::world::World;
+
+/// A placeholder for an entity `CommandBuffer::spawn` hasn't created yet, usable by later
+/// `insert`/`remove`/`despawn` calls in the same buffer. Resolved to a real `Entity` only once
+/// `World::apply_commands` actually runs the spawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reserved(usize);
+
+/// An entity a `CommandBuffer` operation targets: either one that already exists, or one
+/// `spawn`ed earlier in the same buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Live(Entity),
+    Reserved(Reserved),
+}
+
+impl From<Entity> for Target {
+    fn from(entity: Entity) -> Self {
+        Target::Live(entity)
+    }
+}
+
+impl From<Reserved> for Target {
+    fn from(reserved: Reserved) -> Self {
+        Target::Reserved(reserved)
+    }
+}
+
+type SpawnFn = Box<dyn FnOnce(&mut World) -> Entity>;
+type MutateFn = Box<dyn FnOnce(&mut World, Entity)>;
+
+enum Command {
+    Spawn(SpawnFn),
+    Despawn(Target),
+    Insert(Target, MutateFn),
+    Remove(Target, MutateFn),
+}
+
+/// A queue of spawn/despawn/insert/remove operations, applied in order by
+/// `World::apply_commands`.
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+    reserved_count: usize,
+}
+
+impl CommandBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a spawn of `set`, returning a `Reserved` placeholder that later `insert`/
+    /// `remove`/`despawn` calls in this same buffer can target before the entity actually
+    /// exists.
+    pub fn spawn<S: Set>(&mut self, set: S) -> Reserved {
+        let reserved = Reserved(self.reserved_count);
+        self.reserved_count += 1;
+        self.commands.push(Command::Spawn(Box::new(move |world| world.spawn(set))));
+        reserved
+    }
+
+    /// Queues a despawn of `target`. A no-op at apply time if `target` is already dead.
+    pub fn despawn(&mut self, target: impl Into<Target>) {
+        self.commands.push(Command::Despawn(target.into()));
+    }
+
+    /// Queues adding `value` to `target`.
+    pub fn insert<C: Component>(&mut self, target: impl Into<Target>, value: C) {
+        self.commands.push(Command::Insert(target.into(), Box::new(move |world, entity| { world.add_component(entity, value); })));
+    }
+
+    /// Queues removing `target`'s `C`.
+    pub fn remove<C: Component>(&mut self, target: impl Into<Target>) {
+        self.commands.push(Command::Remove(target.into(), Box::new(move |world, entity| { world.remove_component::<C>(entity); })));
+    }
+
+    /// Drains and runs every queued command against `world`, in order, resolving `Reserved`
+    /// targets to the `Entity` their `spawn` produced. `spawn` always queues its command before
+    /// any command that could reference its `Reserved`, so by the time a later command resolves
+    /// one it's always already been assigned.
+    pub(crate) fn apply(self, world: &mut World) {
+        let mut resolved: Vec<Option<Entity>> = vec![None; self.reserved_count];
+        let resolve = |target: Target, resolved: &[Option<Entity>]| match target {
+            Target::Live(entity) => Some(entity),
+            Target::Reserved(Reserved(index)) => resolved[index],
+        };
+
+        let mut next_reserved = 0;
+        for command in self.commands {
+            match command {
+                Command::Spawn(spawn) => {
+                    let entity = spawn(world);
+                    resolved[next_reserved] = Some(entity);
+                    next_reserved += 1;
+                }
+                Command::Despawn(target) => {
+                    if let Some(entity) = resolve(target, &resolved) {
+                        world.despawn(entity);
+                    }
+                }
+                Command::Insert(target, insert) => {
+                    if let Some(entity) = resolve(target, &resolved) {
+                        insert(world, entity);
+                    }
+                }
+                Command::Remove(target, remove) => {
+                    if let Some(entity) = resolve(target, &resolved) {
+                        remove(world, entity);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(f32, f32);
+    #[derive(Component, Debug, PartialEq)]
+    struct Velocity(f32, f32);
+
+    #[test]
+    fn applying_a_buffer_of_mixed_operations_produces_the_expected_world_state() {
+        let mut world = World::new();
+        let stays = world.spawn(Position(0.0, 0.0));
+        let despawned = world.spawn(Position(1.0, 1.0));
+
+        let mut buffer = CommandBuffer::new();
+        let spawned = buffer.spawn(Position(2.0, 2.0));
+        buffer.insert(spawned, Velocity(0.5, 0.5));
+        buffer.insert(stays, Velocity(1.0, 1.0));
+        buffer.remove::<Position>(stays);
+        buffer.despawn(despawned);
+
+        world.apply_commands(buffer);
+
+        assert!(world.entity_ref(despawned).is_none());
+        assert_eq!(world.entity_ref(stays).unwrap().get::<Position>(), None);
+        assert_eq!(world.entity_ref(stays).unwrap().get::<Velocity>(), Some(&Velocity(1.0, 1.0)));
+
+        let mut query: crate::ecs::query::Query<(&Position, &Velocity)> = crate::ecs::query::Query::new(&mut world);
+        let rows: Vec<_> = query.iter().collect();
+        assert_eq!(rows, vec![(&Position(2.0, 2.0), &Velocity(0.5, 0.5))]);
+    }
+
+    #[test]
+    fn despawning_an_already_dead_entity_is_a_no_op() {
+        let mut world = World::new();
+        let e = world.spawn(Position(0.0, 0.0));
+        world.despawn(e);
+
+        let mut buffer = CommandBuffer::new();
+        buffer.despawn(e);
+        world.apply_commands(buffer); // must not panic
+    }
+}
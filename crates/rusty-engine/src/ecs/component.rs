@@ -0,0 +1,703 @@
+//! Component registration and archetype signatures.
+
+use std::alloc::Layout;
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+
+/// Marker trait for types that can be attached to entities.
+///
+/// Implemented via `#[derive(Component)]`; there is no blanket implementation so that
+/// future per-type configuration (storage strategy, mutability) has somewhere to live.
+pub trait Component: Send + Sync + 'static {
+    /// Set by deriving with `#[component(immutable)]`. A `Query` for `&mut` this component
+    /// panics as soon as it's built, catching an accidental mutable borrow of a component
+    /// that's meant to be fixed for the entity's whole lifetime (e.g. a stable `EntityKind`
+    /// id) at system-construction time instead of leaving it to be found by inspection.
+    const IMMUTABLE: bool = false;
+}
+
+/// Identifies a registered component type within a `World`. Stable for the lifetime
+/// of the `World` that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ComponentId(pub(crate) usize);
+
+impl ComponentId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Everything the storage layer needs to know about a component type without being
+/// generic over it: how big it is, how to drop it, and what it's called.
+#[derive(Debug, Clone)]
+pub struct Info {
+    id: ComponentId,
+    type_id: TypeId,
+    name: &'static str,
+    layout: Layout,
+    drop_fn: Option<unsafe fn(*mut u8)>,
+    clone_fn: Option<unsafe fn(*const u8, *mut u8)>,
+    debug_fn: Option<unsafe fn(*const u8) -> String>,
+    immutable: bool,
+}
+
+impl Info {
+    fn new<C: Component>(id: ComponentId) -> Self {
+        unsafe fn drop_in_place<C>(ptr: *mut u8) {
+            std::ptr::drop_in_place(ptr.cast::<C>());
+        }
+
+        Self {
+            id,
+            type_id: TypeId::of::<C>(),
+            name: type_name::<C>(),
+            layout: Layout::new::<C>(),
+            drop_fn: if std::mem::needs_drop::<C>() {
+                Some(drop_in_place::<C>)
+            } else {
+                None
+            },
+            clone_fn: None,
+            debug_fn: None,
+            immutable: C::IMMUTABLE,
+        }
+    }
+
+    /// Clones the value at `src` into uninitialized memory at `dst`, via `C::clone`.
+    unsafe fn clone_in_place<C: Clone>(src: *const u8, dst: *mut u8) {
+        let value = (*src.cast::<C>()).clone();
+        dst.cast::<C>().write(value);
+    }
+
+    /// Formats the value at `ptr` via `C::fmt`, for `Info::debug_fn`.
+    unsafe fn debug_in_place<C: std::fmt::Debug>(ptr: *const u8) -> String {
+        format!("{:?}", &*ptr.cast::<C>())
+    }
+
+    pub fn id(&self) -> ComponentId {
+        self.id
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub(crate) fn drop_fn(&self) -> Option<unsafe fn(*mut u8)> {
+        self.drop_fn
+    }
+
+    /// How to deep-copy one value of this component type, or `None` if it was only ever
+    /// registered via `Registry::register` (not `register_cloneable`).
+    pub(crate) fn clone_fn(&self) -> Option<unsafe fn(*const u8, *mut u8)> {
+        self.clone_fn
+    }
+
+    /// How to format one value of this component type via `Debug`, or `None` if it was
+    /// never registered via `Registry::register_debuggable`.
+    pub(crate) fn debug_fn(&self) -> Option<unsafe fn(*const u8) -> String> {
+        self.debug_fn
+    }
+
+    /// Whether this component was derived with `#[component(immutable)]` — see
+    /// `Component::IMMUTABLE`.
+    pub fn immutable(&self) -> bool {
+        self.immutable
+    }
+}
+
+/// Maps component `TypeId`s to stable `ComponentId`s and stores their layout metadata.
+#[derive(Default, Clone)]
+pub struct Registry {
+    infos: Vec<Info>,
+    indices: HashMap<TypeId, ComponentId>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `C` if it hasn't been seen before, returning its `ComponentId` either way.
+    pub fn register<C: Component>(&mut self) -> ComponentId {
+        if let Some(id) = self.indices.get(&TypeId::of::<C>()) {
+            return *id;
+        }
+        let id = ComponentId(self.infos.len());
+        self.infos.push(Info::new::<C>(id));
+        self.indices.insert(TypeId::of::<C>(), id);
+        id
+    }
+
+    /// Registers `C` like `register`, but additionally records how to deep-copy its column
+    /// data (via `C::clone`) for `Column::try_clone`/`Table::try_clone`/`Archetypes::try_clone`.
+    ///
+    /// This is opt-in rather than automatic for every `Clone` component: nothing about
+    /// `#[derive(Component)]` can see a sibling `#[derive(Clone)]` without either depending
+    /// on the unstable `specialization` feature or having the derive macro sniff the other
+    /// attribute textually, and this crate does neither. Calling this instead of `register`
+    /// for the types you want snapshot/rollback support for is a small, explicit price for
+    /// avoiding both.
+    pub fn register_cloneable<C: Component + Clone>(&mut self) -> ComponentId {
+        let id = self.register::<C>();
+        let info = &mut self.infos[id.0];
+        if info.clone_fn.is_none() {
+            info.clone_fn = Some(Info::clone_in_place::<C>);
+        }
+        id
+    }
+
+    /// Registers `C` like `register`, but additionally records how to format its column
+    /// data via `C::fmt` for `Ref::debug_dump`.
+    ///
+    /// Opt-in for the same reason `register_cloneable` is: the derive macro can't see
+    /// whether a sibling `#[derive(Debug)]` is present without unstable `specialization` or
+    /// sniffing the attribute textually, so it can't populate this on its own.
+    pub fn register_debuggable<C: Component + std::fmt::Debug>(&mut self) -> ComponentId {
+        let id = self.register::<C>();
+        let info = &mut self.infos[id.0];
+        if info.debug_fn.is_none() {
+            info.debug_fn = Some(Info::debug_in_place::<C>);
+        }
+        id
+    }
+
+    pub fn id_of<C: Component>(&self) -> Option<ComponentId> {
+        self.indices.get(&TypeId::of::<C>()).copied()
+    }
+
+    pub fn id_of_type(&self, type_id: TypeId) -> Option<ComponentId> {
+        self.indices.get(&type_id).copied()
+    }
+
+    pub fn info(&self, id: ComponentId) -> &Info {
+        &self.infos[id.0]
+    }
+
+    /// Whether `id` was actually handed out by this registry, e.g. one it registered
+    /// itself as opposed to one a scripting layer read off some other `World`'s snapshot.
+    pub fn contains(&self, id: ComponentId) -> bool {
+        id.0 < self.infos.len()
+    }
+
+    fn id_of_name(&self, name: &str) -> Option<ComponentId> {
+        self.infos.iter().find(|info| info.name() == name).map(|info| info.id())
+    }
+
+    /// Captures every registered component's stable name (`Info::name`, i.e. `type_name`)
+    /// alongside its current id, for later reconciliation via `validate_against` after a
+    /// save/load round-trip where registration order isn't guaranteed to match.
+    pub fn snapshot(&self) -> HashMap<&'static str, ComponentId> {
+        self.infos.iter().map(|info| (info.name(), info.id())).collect()
+    }
+
+    /// Reconciles a saved `snapshot` against this registry by component name rather than
+    /// id, since two processes (or two runs) can register the same types in different
+    /// orders and end up with different `ComponentId`s for them.
+    ///
+    /// Returns a map from each saved id to this registry's id for the same type, so a
+    /// deserializer can remap ids found in saved data. Fails if a saved component type
+    /// isn't registered here at all, listing every such name.
+    pub fn validate_against(&self, saved: &HashMap<&'static str, ComponentId>) -> Result<HashMap<ComponentId, ComponentId>, Vec<&'static str>> {
+        let mut mapping = HashMap::with_capacity(saved.len());
+        let mut missing = Vec::new();
+        for (&name, &old_id) in saved {
+            match self.id_of_name(name) {
+                Some(new_id) => {
+                    mapping.insert(old_id, new_id);
+                }
+                None => missing.push(name),
+            }
+        }
+        if missing.is_empty() {
+            Ok(mapping)
+        } else {
+            missing.sort_unstable();
+            Err(missing)
+        }
+    }
+
+    /// Registers a component type described by another registry's `Info`, e.g. when
+    /// merging one `World` into another and neither side has the concrete type in scope.
+    ///
+    /// Idempotent by `TypeId` just like `register`, so merging the same source registry
+    /// twice reuses the same ids.
+    pub(crate) fn register_info(&mut self, info: &Info) -> ComponentId {
+        if let Some(id) = self.indices.get(&info.type_id) {
+            return *id;
+        }
+        let id = ComponentId(self.infos.len());
+        self.infos.push(Info { id, ..info.clone() });
+        self.indices.insert(info.type_id, id);
+        id
+    }
+}
+
+/// An immutable, sorted set of `ComponentId`s describing the shape of an archetype.
+///
+/// Two specs with the same components (in any insertion order) compare equal and hash
+/// identically, since they're always normalized on construction. The hash is computed once
+/// up front and cached, so looking a `Spec` up in `Archetypes::by_spec` doesn't re-hash the
+/// whole id list on every lookup — only a collision falls back to the full `ids` comparison,
+/// same as any other `HashMap` key.
+#[derive(Debug, Clone)]
+pub struct Spec {
+    ids: Vec<ComponentId>,
+    hash: u64,
+}
+
+impl Spec {
+    pub fn new(mut ids: Vec<ComponentId>) -> Self {
+        ids.sort_unstable();
+        ids.dedup();
+        let hash = Self::hash_ids(&ids);
+        Self { ids, hash }
+    }
+
+    fn hash_ids(ids: &[ComponentId]) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        ids.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, id: ComponentId) -> bool {
+        self.ids.binary_search(&id).is_ok()
+    }
+
+    pub fn ids(&self) -> &[ComponentId] {
+        &self.ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns a new spec with `id` added, or `self` unchanged (cloned) if already present.
+    pub fn with(&self, id: ComponentId) -> Self {
+        if self.contains(id) {
+            return self.clone();
+        }
+        let mut ids = self.ids.clone();
+        ids.push(id);
+        Self::new(ids)
+    }
+
+    /// Returns a new spec with `id` removed.
+    pub fn without(&self, id: ComponentId) -> Self {
+        let ids = self.ids.iter().copied().filter(|&i| i != id).collect();
+        Self::new(ids)
+    }
+
+    /// The components gained and lost moving from `self` to `to`, e.g. for emitting
+    /// "component added"/"component removed" events around an archetype migration.
+    pub fn diff(&self, to: &Spec) -> SpecDiff {
+        SpecDiff {
+            added: to.ids.iter().copied().filter(|id| !self.contains(*id)).collect(),
+            removed: self.ids.iter().copied().filter(|id| !to.contains(*id)).collect(),
+        }
+    }
+}
+
+/// The `ComponentId`s gained and lost going from one `Spec` to another, as returned by
+/// `Spec::diff`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpecDiff {
+    pub added: Vec<ComponentId>,
+    pub removed: Vec<ComponentId>,
+}
+
+/// Incrementally builds a `Spec` from `ComponentId`s known only at runtime — e.g. a
+/// scripting layer building an entity's component set from a list of ids it read off some
+/// external description rather than a static `Set` type. Validates each id against its
+/// `Registry` as it's added instead of letting a stale or foreign id reach `Archetypes::
+/// get_or_create`, deep inside archetype lookup, before failing.
+pub struct SpecBuilder<'r> {
+    registry: &'r Registry,
+    ids: Vec<ComponentId>,
+}
+
+impl<'r> SpecBuilder<'r> {
+    pub fn new(registry: &'r Registry) -> Self {
+        Self { registry, ids: Vec::new() }
+    }
+
+    /// Adds `id` to the set under construction.
+    ///
+    /// # Panics
+    /// Panics if `id` isn't registered in this builder's `Registry`.
+    pub fn push(mut self, id: ComponentId) -> Self {
+        assert!(self.registry.contains(id), "SpecBuilder::push: {id:?} isn't registered in this registry");
+        self.ids.push(id);
+        self
+    }
+
+    /// Adds every id in `ids`, in order — see `push`'s panic condition.
+    pub fn extend(mut self, ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        for id in ids {
+            self = self.push(id);
+        }
+        self
+    }
+
+    pub fn build(self) -> Spec {
+        Spec::new(self.ids)
+    }
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl PartialEq for Spec {
+    fn eq(&self, other: &Self) -> bool {
+        self.ids == other.ids
+    }
+}
+
+impl Eq for Spec {}
+
+impl std::hash::Hash for Spec {
+    /// Writes the precomputed hash rather than re-hashing `ids`, since `Spec` never mutates
+    /// after construction.
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// A bitset over `ComponentId`s, for matching a query's component set against a table's
+/// columns in one bitmask AND instead of one `HashMap` lookup per id.
+///
+/// `ComponentId`s are dense, monotonically-assigned indices (see `Registry::register`), so
+/// bit `i` of word `i / 64` answers "does this component set contain the id with index `i`"
+/// directly, with no auxiliary lookup table. Grows to fit the largest id it's ever seen;
+/// `contains_all` treats any bit beyond the shorter mask's length as unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentMask {
+    words: Vec<u64>,
+}
+
+impl ComponentMask {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ids(ids: impl IntoIterator<Item = ComponentId>) -> Self {
+        let mut mask = Self::new();
+        for id in ids {
+            mask.insert(id);
+        }
+        mask
+    }
+
+    pub fn insert(&mut self, id: ComponentId) {
+        let (word, bit) = (id.index() / 64, id.index() % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn contains(&self, id: ComponentId) -> bool {
+        let (word, bit) = (id.index() / 64, id.index() % 64);
+        self.words.get(word).is_some_and(|w| w & (1 << bit) != 0)
+    }
+
+    /// Whether every id set in `other` is also set in `self` — the "does this table have
+    /// every component this query asks for" check, as one word-at-a-time AND per word of
+    /// `other` rather than one hash lookup per id.
+    pub fn contains_all(&self, other: &ComponentMask) -> bool {
+        other.words.iter().enumerate().all(|(i, &word)| self.words.get(i).copied().unwrap_or(0) & word == word)
+    }
+}
+
+/// A set of components that can be spawned onto an entity together, e.g. `(Position, Velocity)`.
+///
+/// Implemented for every `Component` and for tuples of them up to arity 8; see the
+/// `impl_set_for_tuple!` invocations below.
+pub trait Set: 'static {
+    /// Registers every component in the set and returns their ids, in the same order the
+    /// set will yield values from `take`.
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId>;
+
+    /// The `TypeId` and name of every component in the set, in the same order
+    /// `component_ids` produces ids for, without registering anything. Lets a caller (e.g.
+    /// `World::try_spawn`'s strict mode) check registration first and fail instead.
+    fn type_ids() -> Vec<(TypeId, &'static str)>;
+
+    /// Hands each component's id (in the same order `component_ids` produced them) and a
+    /// pointer to its value to `f`, which takes ownership.
+    ///
+    /// # Safety
+    /// `ids` must be exactly what `Self::component_ids` returned for the `Registry` `f` is
+    /// writing into. `f` must fully consume each value it is given exactly once.
+    unsafe fn take(self, ids: &[ComponentId], f: &mut dyn FnMut(ComponentId, *const u8));
+}
+
+impl<C: Component> Set for C {
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        vec![registry.register::<C>()]
+    }
+
+    fn type_ids() -> Vec<(TypeId, &'static str)> {
+        vec![(TypeId::of::<C>(), type_name::<C>())]
+    }
+
+    unsafe fn take(self, ids: &[ComponentId], f: &mut dyn FnMut(ComponentId, *const u8)) {
+        f(ids[0], (&self as *const C).cast());
+        std::mem::forget(self);
+    }
+}
+
+macro_rules! impl_set_for_tuple {
+    ($($name:ident : $index:tt),+) => {
+        impl<$($name: Component),+> Set for ($($name,)+) {
+            fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+                vec![$(registry.register::<$name>()),+]
+            }
+
+            fn type_ids() -> Vec<(TypeId, &'static str)> {
+                let mut ids = Vec::new();
+                $(ids.extend($name::type_ids());)+
+                ids
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn take(self, ids: &[ComponentId], f: &mut dyn FnMut(ComponentId, *const u8)) {
+                let ($($name,)+) = self;
+                $(
+                    f(ids[$index], (&$name as *const $name).cast());
+                    std::mem::forget($name);
+                )+
+            }
+        }
+    };
+}
+
+impl_set_for_tuple!(A:0);
+impl_set_for_tuple!(A:0, B:1);
+impl_set_for_tuple!(A:0, B:1, C:2);
+impl_set_for_tuple!(A:0, B:1, C:2, D:3);
+impl_set_for_tuple!(A:0, B:1, C:2, D:3, E:4);
+impl_set_for_tuple!(A:0, B:1, C:2, D:3, E:4, F:5);
+impl_set_for_tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6);
+impl_set_for_tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component)]
+    struct A;
+    #[derive(Component)]
+    struct B;
+
+    #[test]
+    fn spec_normalizes_order() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+
+        let spec1 = Spec::new(vec![a, b]);
+        let spec2 = Spec::new(vec![b, a]);
+        assert_eq!(spec1, spec2);
+    }
+
+    #[test]
+    fn spec_builder_validates_ids_and_spawns_into_the_resulting_archetype() {
+        use crate::ecs::entity::Entities;
+        use crate::ecs::storage::archetype::Archetypes;
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Pos(f32, f32);
+        #[derive(Component, Debug, PartialEq)]
+        struct Vel(f32, f32);
+
+        let mut registry = Registry::new();
+        let pos = registry.register::<Pos>();
+        let vel = registry.register::<Vel>();
+
+        let spec = SpecBuilder::new(&registry).push(pos).push(vel).build();
+        assert_eq!(spec, Spec::new(vec![pos, vel]));
+
+        let mut archetypes = Archetypes::new();
+        let archetype = archetypes.get_or_create(&registry, spec.clone());
+        assert_eq!(archetypes.spec(archetype), &spec);
+
+        let mut entities = Entities::new();
+        let entity = entities.alloc();
+        let table = archetypes.table_mut(archetype);
+        let (p, v) = (Pos(1.0, 2.0), Vel(3.0, 4.0));
+        unsafe {
+            table.write_component(pos, (&p as *const Pos).cast(), 0);
+            table.write_component(vel, (&v as *const Vel).cast(), 0);
+        }
+        let row = table.finish_push(entity);
+
+        assert_eq!(unsafe { &*(table.column(pos).unwrap().get(row).unwrap() as *const Pos) }, &Pos(1.0, 2.0));
+        assert_eq!(unsafe { &*(table.column(vel).unwrap().get(row).unwrap() as *const Vel) }, &Vel(3.0, 4.0));
+    }
+
+    #[test]
+    fn spec_builder_panics_on_an_id_from_a_different_registry() {
+        let mut registry = Registry::new();
+        registry.register::<A>();
+
+        let mut other = Registry::new();
+        other.register::<A>();
+        let foreign = other.register::<B>();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| SpecBuilder::new(&registry).push(foreign)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn specs_built_in_different_orders_hash_equal() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+
+        let spec1 = Spec::new(vec![a, b]);
+        let spec2 = Spec::new(vec![b, a]);
+
+        let hash_of = |spec: &Spec| {
+            let mut hasher = DefaultHasher::new();
+            spec.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_of(&spec1), hash_of(&spec2));
+    }
+
+    #[test]
+    fn with_and_without_recompute_the_hash() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+
+        let spec = Spec::empty().with(a).with(b);
+        assert_eq!(spec, Spec::new(vec![a, b]));
+
+        let spec = spec.without(a);
+        assert_eq!(spec, Spec::new(vec![b]));
+    }
+
+    #[test]
+    fn diff_reports_components_gained_and_lost_for_a_combined_add_and_remove() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+        #[derive(Component)]
+        struct C;
+        let c = registry.register::<C>();
+
+        // a -> b: drop `a`, gain `b` and `c`.
+        let from = Spec::new(vec![a]);
+        let to = Spec::new(vec![b, c]);
+
+        let diff = from.diff(&to);
+        assert_eq!(diff.added, vec![b, c]);
+        assert_eq!(diff.removed, vec![a]);
+    }
+
+    #[test]
+    fn diff_between_identical_specs_is_empty() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let spec = Spec::new(vec![a]);
+
+        assert_eq!(spec.diff(&spec), SpecDiff::default());
+    }
+
+    #[test]
+    fn mask_contains_all_only_when_every_bit_of_the_smaller_mask_is_set() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+        #[derive(Component)]
+        struct C;
+        let c = registry.register::<C>();
+
+        let table = ComponentMask::from_ids([a, b, c]);
+        assert!(table.contains_all(&ComponentMask::from_ids([a, b])));
+        assert!(table.contains_all(&ComponentMask::from_ids([])));
+        assert!(!table.contains_all(&ComponentMask::from_ids([a, ComponentId(c.index() + 1)])));
+    }
+
+    #[test]
+    fn mask_across_a_word_boundary_still_matches_correctly() {
+        // `ComponentMask` packs 64 ids per word; id 65 exercises the second word without
+        // needing to register 65 real component types.
+        let near = ComponentId(3);
+        let far = ComponentId(65);
+
+        let mut mask = ComponentMask::new();
+        mask.insert(far);
+
+        assert!(mask.contains(far));
+        assert!(!mask.contains(near));
+        assert!(mask.contains_all(&ComponentMask::from_ids([far])));
+        assert!(!mask.contains_all(&ComponentMask::from_ids([near])));
+    }
+
+    #[test]
+    fn register_is_idempotent() {
+        let mut registry = Registry::new();
+        let a1 = registry.register::<A>();
+        let a2 = registry.register::<A>();
+        assert_eq!(a1, a2);
+    }
+
+    #[test]
+    fn validate_against_remaps_ids_registered_in_a_different_order() {
+        let mut saved_registry = Registry::new();
+        let a_old = saved_registry.register::<A>();
+        let b_old = saved_registry.register::<B>();
+        let snapshot = saved_registry.snapshot();
+
+        let mut loaded_registry = Registry::new();
+        let b_new = loaded_registry.register::<B>();
+        let a_new = loaded_registry.register::<A>();
+
+        let mapping = loaded_registry.validate_against(&snapshot).unwrap();
+        assert_eq!(mapping[&a_old], a_new);
+        assert_eq!(mapping[&b_old], b_new);
+    }
+
+    #[test]
+    fn validate_against_reports_missing_saved_components() {
+        #[derive(Component)]
+        struct Missing;
+
+        let mut saved_registry = Registry::new();
+        saved_registry.register::<A>();
+        saved_registry.register::<Missing>();
+        let snapshot = saved_registry.snapshot();
+
+        let mut loaded_registry = Registry::new();
+        loaded_registry.register::<A>();
+
+        let missing = loaded_registry.validate_against(&snapshot).unwrap_err();
+        assert_eq!(missing, vec![type_name::<Missing>()]);
+    }
+}
@@ -0,0 +1,435 @@
+//! Entity identifiers, allocation, and component-access views.
+
+mod ref_;
+
+pub use ref_::{Ref, RefMut, View, ViewMut};
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::num::NonZeroU64;
+
+/// A lightweight handle to a row of component data living somewhere in a `World`.
+///
+/// Entities are opaque outside of the crate; identity and liveness are tracked by
+/// pairing an `index` with a `generation` so that a stale handle to a despawned
+/// entity can never alias a freshly spawned one.
+///
+/// Packed into a single `NonZeroU64` (index in the high 32 bits, offset by one so an
+/// all-zero bit pattern is never valid; generation in the low 32 bits) so `Option<Entity>`
+/// gets a niche and costs no more than a bare `Entity` — handy for `Option<Entity>`-heavy
+/// data like a `Children: Vec<Option<Entity>>` slot list. `index + 1` only overflows a u32
+/// once the allocator has handed out `u32::MAX` live+freed slots, which would already need
+/// far more memory than `Entities::meta` could hold; that's the one edge this packing
+/// doesn't cover.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity(NonZeroU64);
+
+impl Entity {
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        let packed = ((index as u64 + 1) << 32) | generation as u64;
+        Self(NonZeroU64::new(packed).expect("index + 1 is never zero"))
+    }
+
+    /// The slot this entity occupies in the allocator. Not stable identity on its own;
+    /// combine with `generation` for that.
+    pub fn index(self) -> u32 {
+        ((self.0.get() >> 32) - 1) as u32
+    }
+
+    /// The generation the slot was on when this handle was created.
+    pub fn generation(self) -> u32 {
+        self.0.get() as u32
+    }
+}
+
+impl fmt::Debug for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entity({}v{})", self.index(), self.generation())
+    }
+}
+
+/// Where an entity's component data currently lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub archetype: crate::ecs::storage::archetype::ArchetypeId,
+    pub row: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Meta {
+    generation: u32,
+    location: Option<Location>,
+}
+
+/// The freed-index pool backing `Entities`.
+///
+/// `Stack` is a plain LIFO free list: cheapest to push/pop, but which index comes back
+/// next depends on free/alloc interleaving rather than the index's value. `Sorted` always
+/// hands back the lowest freed index first, which costs a heap push/pop instead of a
+/// vector push/pop but makes reuse order reproducible run-to-run.
+#[derive(Clone)]
+enum FreePool {
+    Stack(Vec<u32>),
+    Sorted(BinaryHeap<Reverse<u32>>),
+}
+
+impl FreePool {
+    fn push(&mut self, index: u32) {
+        match self {
+            Self::Stack(free) => free.push(index),
+            Self::Sorted(free) => free.push(Reverse(index)),
+        }
+    }
+
+    fn pop(&mut self) -> Option<u32> {
+        match self {
+            Self::Stack(free) => free.pop(),
+            Self::Sorted(free) => free.pop().map(|Reverse(index)| index),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Stack(free) => free.len(),
+            Self::Sorted(free) => free.len(),
+        }
+    }
+
+    /// Removes `index` from the free pool if it's there, returning whether it was found.
+    /// Only used by `Entities::alloc_at`, which claims an exact index outside the usual
+    /// pop-from-the-front allocation path, so this can afford to be a linear scan rather than
+    /// something the hot `alloc`/`free` path needs to stay cheap.
+    fn remove(&mut self, index: u32) -> bool {
+        match self {
+            Self::Stack(free) => {
+                if let Some(pos) = free.iter().position(|&i| i == index) {
+                    free.remove(pos);
+                    true
+                } else {
+                    false
+                }
+            }
+            Self::Sorted(free) => {
+                if !free.iter().any(|&Reverse(i)| i == index) {
+                    return false;
+                }
+                *free = free.drain().filter(|&Reverse(i)| i != index).collect();
+                true
+            }
+        }
+    }
+}
+
+impl Default for FreePool {
+    fn default() -> Self {
+        Self::Stack(Vec::new())
+    }
+}
+
+/// The most entity slots (live + freed) an `Entities` will ever hand out — see `Entity`'s
+/// doc comment for why `index` tops out one below `u32::MAX`. `try_alloc` returns
+/// `EntitiesExhausted` rather than silently wrapping `index` back to zero (and colliding
+/// with slot 0) once this many slots have ever been allocated.
+pub const MAX_ENTITIES: u32 = u32::MAX - 1;
+
+/// Allocates and recycles `Entity` ids, tracking each live entity's current location.
+#[derive(Clone)]
+pub struct Entities {
+    meta: Vec<Meta>,
+    free: FreePool,
+    cap: u32,
+}
+
+impl Default for Entities {
+    fn default() -> Self {
+        Self {
+            meta: Vec::new(),
+            free: FreePool::default(),
+            cap: MAX_ENTITIES,
+        }
+    }
+}
+
+impl Entities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like `new`, but freed indices are always reused lowest-first instead of in
+    /// free/alloc order.
+    ///
+    /// Costs a small ordering overhead per free/alloc; use it for lockstep networking or
+    /// snapshot tests where entity ids must come back the same way on every run, and stick
+    /// with `new` elsewhere.
+    pub fn deterministic() -> Self {
+        Self {
+            meta: Vec::new(),
+            free: FreePool::Sorted(BinaryHeap::new()),
+            cap: MAX_ENTITIES,
+        }
+    }
+
+    /// Like `new`, but exhausted once `cap` slots (live + freed) have ever been handed out,
+    /// instead of `MAX_ENTITIES`. Only meant for exercising exhaustion handling in a test
+    /// without actually allocating billions of entities.
+    #[cfg(test)]
+    fn with_cap(cap: u32) -> Self {
+        Self { cap, ..Self::default() }
+    }
+
+    /// Allocates a fresh entity id, reusing a freed slot (with bumped generation) when
+    /// possible, or returning `EntitiesExhausted` instead of wrapping `index` back to an
+    /// already-live value once every slot up to `MAX_ENTITIES` is spoken for.
+    pub fn try_alloc(&mut self) -> Result<Entity, EntitiesExhausted> {
+        if let Some(index) = self.free.pop() {
+            let meta = &mut self.meta[index as usize];
+            meta.location = None;
+            return Ok(Entity::new(index, meta.generation));
+        }
+        if self.meta.len() >= self.cap as usize {
+            return Err(EntitiesExhausted { cap: self.cap });
+        }
+        let index = self.meta.len() as u32;
+        self.meta.push(Meta {
+            generation: 0,
+            location: None,
+        });
+        Ok(Entity::new(index, 0))
+    }
+
+    /// Allocates a fresh entity id, reusing a freed slot (with bumped generation) when
+    /// possible.
+    ///
+    /// Panics on exhaustion rather than wrapping `index` — see `try_alloc` for a
+    /// non-panicking alternative. In practice this means a long-running world has spawned
+    /// and despawned `MAX_ENTITIES` entities without ever fully idling; that's a design
+    /// problem worth panicking loudly over, not silently colliding ids for.
+    pub fn alloc(&mut self) -> Entity {
+        self.try_alloc().expect("Entities exhausted")
+    }
+
+    /// Claims `entity`'s exact index and generation instead of letting the allocator assign
+    /// one — e.g. replicating an entity a networked server already assigned an id to, which
+    /// must land at that specific slot rather than whatever the local allocator would
+    /// otherwise hand out next.
+    ///
+    /// Grows the allocator (marking any newly created intermediate indices free, so the local
+    /// allocator can still hand them out later) if `entity`'s index hasn't been seen yet.
+    /// Otherwise claims the slot outright, regardless of what was there before — reusing it if
+    /// it was free, or overwriting it (and returning its previous `Location`, so the caller can
+    /// clean up the old row) if it was already live under any generation.
+    pub fn alloc_at(&mut self, entity: Entity) -> Option<Location> {
+        let index = entity.index() as usize;
+        if index >= self.meta.len() {
+            for i in self.meta.len()..index {
+                self.meta.push(Meta {
+                    generation: 0,
+                    location: None,
+                });
+                self.free.push(i as u32);
+            }
+            self.meta.push(Meta {
+                generation: entity.generation(),
+                location: None,
+            });
+            return None;
+        }
+
+        let was_free = self.free.remove(index as u32);
+        let previous_location = self.meta[index].location;
+        self.meta[index] = Meta {
+            generation: entity.generation(),
+            location: None,
+        };
+        if was_free {
+            None
+        } else {
+            previous_location
+        }
+    }
+
+    /// Frees `entity`'s slot for reuse, bumping its generation so old handles are invalidated.
+    /// Returns `false` if the entity was already dead.
+    pub fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let meta = &mut self.meta[entity.index() as usize];
+        meta.generation = meta.generation.wrapping_add(1);
+        meta.location = None;
+        self.free.push(entity.index());
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.meta
+            .get(entity.index() as usize)
+            .is_some_and(|meta| meta.generation == entity.generation())
+    }
+
+    pub fn location(&self, entity: Entity) -> Option<Location> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.meta[entity.index() as usize].location
+    }
+
+    pub fn set_location(&mut self, entity: Entity, location: Location) {
+        debug_assert!(self.is_alive(entity));
+        self.meta[entity.index() as usize].location = Some(location);
+    }
+
+    pub fn len(&self) -> usize {
+        self.meta.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returned by `Entities::try_alloc` once every slot up to `cap` (live + freed) has already
+/// been handed out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntitiesExhausted {
+    pub cap: u32,
+}
+
+impl fmt::Display for EntitiesExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "entity allocator exhausted: {} slots already allocated", self.cap)
+    }
+}
+
+impl std::error::Error for EntitiesExhausted {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alloc_and_free_recycles_index_with_new_generation() {
+        let mut entities = Entities::new();
+        let a = entities.alloc();
+        assert!(entities.is_alive(a));
+        assert!(entities.free(a));
+        assert!(!entities.is_alive(a));
+
+        let b = entities.alloc();
+        assert_eq!(a.index(), b.index());
+        assert_ne!(a.generation(), b.generation());
+    }
+
+    #[test]
+    fn deterministic_reuses_lowest_freed_index_first() {
+        let mut entities = Entities::deterministic();
+        let a = entities.alloc();
+        let b = entities.alloc();
+        let c = entities.alloc();
+
+        entities.free(c);
+        entities.free(a);
+        entities.free(b);
+
+        let reused = entities.alloc();
+        assert_eq!(reused.index(), a.index());
+        let reused = entities.alloc();
+        assert_eq!(reused.index(), b.index());
+        let reused = entities.alloc();
+        assert_eq!(reused.index(), c.index());
+    }
+
+    #[test]
+    fn double_free_returns_false() {
+        let mut entities = Entities::new();
+        let a = entities.alloc();
+        assert!(entities.free(a));
+        assert!(!entities.free(a));
+    }
+
+    #[test]
+    fn alloc_at_beyond_the_current_end_reserves_the_gap_as_free() {
+        let mut entities = Entities::new();
+        let far = Entity::new(5, 3);
+        assert_eq!(entities.alloc_at(far), None);
+        assert!(entities.is_alive(far));
+
+        // The 5 indices skipped over land in the free pool, so 5 ordinary `alloc`s reuse them
+        // (in whatever order the pool hands them back) instead of jumping straight past them.
+        let reused: Vec<u32> = (0..5).map(|_| entities.alloc().index()).collect();
+        let mut sorted = reused.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn alloc_at_on_an_already_free_slot_claims_it_without_reporting_a_previous_location() {
+        let mut entities = Entities::new();
+        let a = entities.alloc();
+        entities.free(a);
+
+        let replayed = Entity::new(a.index(), a.generation() + 41);
+        assert_eq!(entities.alloc_at(replayed), None);
+        assert!(entities.is_alive(replayed));
+        assert!(!entities.is_alive(a));
+    }
+
+    #[test]
+    fn alloc_at_on_a_live_slot_overwrites_it_and_reports_its_previous_location() {
+        let mut entities = Entities::new();
+        let a = entities.alloc();
+        entities.set_location(a, Location { archetype: crate::ecs::storage::archetype::ArchetypeId(0), row: 3 });
+
+        let replacement = Entity::new(a.index(), a.generation() + 1);
+        let previous = entities.alloc_at(replacement);
+        assert_eq!(previous, Some(Location { archetype: crate::ecs::storage::archetype::ArchetypeId(0), row: 3 }));
+        assert!(entities.is_alive(replacement));
+        assert!(!entities.is_alive(a));
+        assert_eq!(entities.location(replacement), None);
+    }
+
+    #[test]
+    fn option_entity_is_niche_optimized_to_the_same_size_as_entity() {
+        assert_eq!(std::mem::size_of::<Option<Entity>>(), std::mem::size_of::<Entity>());
+    }
+
+    #[test]
+    fn index_and_generation_round_trip_through_the_packed_form() {
+        let entity = Entity::new(42, 7);
+        assert_eq!(entity.index(), 42);
+        assert_eq!(entity.generation(), 7);
+
+        let zero = Entity::new(0, 0);
+        assert_eq!(zero.index(), 0);
+        assert_eq!(zero.generation(), 0);
+
+        let max = Entity::new(u32::MAX - 1, u32::MAX);
+        assert_eq!(max.index(), u32::MAX - 1);
+        assert_eq!(max.generation(), u32::MAX);
+    }
+
+    #[test]
+    fn try_alloc_reports_exhaustion_instead_of_wrapping_the_index() {
+        let mut entities = Entities::with_cap(2);
+        let a = entities.try_alloc().unwrap();
+        let b = entities.try_alloc().unwrap();
+        assert_ne!(a.index(), b.index());
+
+        assert_eq!(entities.try_alloc(), Err(EntitiesExhausted { cap: 2 }));
+
+        // Freeing a slot makes room again — exhaustion isn't permanent, it's "every slot up
+        // to `cap` is currently spoken for".
+        entities.free(a);
+        let reused = entities.try_alloc().unwrap();
+        assert_eq!(reused.index(), a.index());
+    }
+
+    #[test]
+    #[should_panic(expected = "Entities exhausted")]
+    fn alloc_panics_on_exhaustion_rather_than_colliding_ids() {
+        let mut entities = Entities::with_cap(1);
+        entities.alloc();
+        entities.alloc();
+    }
+}
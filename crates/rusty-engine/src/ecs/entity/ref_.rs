@@ -0,0 +1,271 @@
+//! Borrowed views onto a single entity's component data.
+
+use crate::ecs::component::{Component, Registry};
+use crate::ecs::entity::Entity;
+use crate::ecs::storage::Table;
+use std::marker::PhantomData;
+
+/// A read-only view onto one entity's row of component data.
+pub struct Ref<'w> {
+    entity: Entity,
+    table: *const Table,
+    row: usize,
+    registry: &'w Registry,
+    _marker: PhantomData<&'w Table>,
+}
+
+impl<'w> Ref<'w> {
+    /// # Safety
+    /// `table` must outlive `'w` and `row` must be a valid, currently-occupied row in it.
+    pub(crate) unsafe fn new(entity: Entity, table: *const Table, row: usize, registry: &'w Registry) -> Self {
+        Self {
+            entity,
+            table,
+            row,
+            registry,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&'w C> {
+        let id = self.registry.id_of::<C>()?;
+        let table = unsafe { &*self.table };
+        let column = table.column(id)?;
+        let ptr = column.get(self.row)?;
+        Some(unsafe { &*(ptr as *const C) })
+    }
+
+    pub fn contains<C: Component>(&self) -> bool {
+        self.registry
+            .id_of::<C>()
+            .is_some_and(|id| unsafe { &*self.table }.has_column(id))
+    }
+
+    /// Fetches several components in one call, e.g. `view::<(&Position, &Velocity)>()`, so
+    /// an inspector panel doesn't repeat `get::<C>()` (and its column lookup) per field.
+    /// `Entity` can appear in the tuple too — `view::<(Entity, &Position)>()` — to pull the
+    /// handle out alongside its components instead of a separate `Ref::entity()` call.
+    /// `None` if any of `V`'s components is missing on this entity.
+    pub fn view<V: View<'w>>(&self) -> Option<V> {
+        V::fetch(unsafe { &*self.table }, self.row, self.registry)
+    }
+
+    /// Formats every component on this entity as `Name(value)`, one per line, for logging
+    /// and inspector panels. A component only renders its value if it was registered via
+    /// `Registry::register_debuggable`; otherwise it renders as `Name(<opaque>)`.
+    pub fn debug_dump(&self) -> String {
+        let table = unsafe { &*self.table };
+        let mut out = String::new();
+        for id in table.column_ids() {
+            let info = self.registry.info(id);
+            let value = match info.debug_fn() {
+                Some(debug_fn) => {
+                    let ptr = table.column(id).and_then(|column| column.get(self.row)).expect("column present on this row");
+                    unsafe { debug_fn(ptr) }
+                }
+                None => "<opaque>".to_string(),
+            };
+            out.push_str(info.name());
+            out.push('(');
+            out.push_str(&value);
+            out.push_str(")\n");
+        }
+        out
+    }
+}
+
+/// What `Ref::view` fetches: one component, `Entity`, or a tuple of up to four.
+///
+/// Implemented for `&'w C` for any `Component`, for `Entity`, and for tuples of `View` up to
+/// arity 4 — mirrors `query::QueryData`'s shape, but returns `Option` per-component (an
+/// entity ref has no archetype match to guarantee every column exists) instead of assuming a
+/// match.
+pub trait View<'w>: Sized {
+    fn fetch(table: &'w Table, row: usize, registry: &Registry) -> Option<Self>;
+}
+
+impl<'w, C: Component> View<'w> for &'w C {
+    fn fetch(table: &'w Table, row: usize, registry: &Registry) -> Option<Self> {
+        let id = registry.id_of::<C>()?;
+        let ptr = table.column(id)?.get(row)?;
+        Some(unsafe { &*(ptr as *const C) })
+    }
+}
+
+/// Fetches the row's own `Entity` — e.g. `view::<(Entity, &Position)>()` returns the handle
+/// alongside the component, so a caller building a batch of `(Entity, &Position)` pairs
+/// doesn't need a separate `Ref::entity()` call. Always succeeds: every occupied row has an
+/// entity.
+impl<'w> View<'w> for Entity {
+    fn fetch(table: &'w Table, row: usize, _registry: &Registry) -> Option<Self> {
+        Some(table.entity(row))
+    }
+}
+
+macro_rules! impl_view_for_tuple {
+    ($($name:ident),+) => {
+        impl<'w, $($name: View<'w>),+> View<'w> for ($($name,)+) {
+            fn fetch(table: &'w Table, row: usize, registry: &Registry) -> Option<Self> {
+                Some(($($name::fetch(table, row, registry)?,)+))
+            }
+        }
+    };
+}
+
+impl_view_for_tuple!(A, B);
+impl_view_for_tuple!(A, B, C);
+impl_view_for_tuple!(A, B, C, D);
+
+/// A mutable view onto one entity's row of component data.
+///
+/// Two `RefMut`s produced by `World::get_many_mut` are guaranteed to point at distinct
+/// entities, and therefore distinct rows, even when those rows live in the same `Table` —
+/// see `World::get_many_mut` for the safety argument.
+pub struct RefMut<'w> {
+    entity: Entity,
+    table: *mut Table,
+    row: usize,
+    registry: &'w Registry,
+    _marker: PhantomData<&'w mut Table>,
+}
+
+impl<'w> RefMut<'w> {
+    /// # Safety
+    /// `table` must outlive `'w`, `row` must be a valid, currently-occupied row in it, and
+    /// no other live `Ref`/`RefMut` may alias the same `(table, row)` pair for `'w`.
+    pub(crate) unsafe fn new(entity: Entity, table: *mut Table, row: usize, registry: &'w Registry) -> Self {
+        Self {
+            entity,
+            table,
+            row,
+            registry,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        let id = self.registry.id_of::<C>()?;
+        let table = unsafe { &*self.table };
+        let column = table.column(id)?;
+        let ptr = column.get(self.row)?;
+        Some(unsafe { &*(ptr as *const C) })
+    }
+
+    pub fn get_mut<C: Component>(&mut self) -> Option<&mut C> {
+        let id = self.registry.id_of::<C>()?;
+        let table = unsafe { &mut *self.table };
+        let column = table.column_mut(id)?;
+        let ptr = column.get_mut(self.row)?;
+        Some(unsafe { &mut *(ptr as *mut C) })
+    }
+
+    pub fn contains<C: Component>(&self) -> bool {
+        self.registry
+            .id_of::<C>()
+            .is_some_and(|id| unsafe { &*self.table }.has_column(id))
+    }
+
+    /// Fetches several components mutably in one call, e.g.
+    /// `view_mut::<(&mut Position, &mut Velocity)>()`. `Entity` can appear in the tuple too,
+    /// the same as `Ref::view`. Borrowing `&mut self` for the returned view's lifetime is
+    /// what rules out overlapping `view_mut` calls; fetching two disjoint components within
+    /// *one* call is sound the same way `Query`'s tuple fetch is.
+    /// `None` if any of `V`'s components is missing on this entity.
+    pub fn view_mut<'a, V: ViewMut<'a>>(&'a mut self) -> Option<V> {
+        unsafe { V::fetch_mut(self.table, self.row, self.registry) }
+    }
+}
+
+/// What `RefMut::view_mut` fetches: one component, or a tuple of up to four.
+pub trait ViewMut<'w>: Sized {
+    /// # Safety
+    /// `table` must be valid for `'w`, `row` a currently-occupied row in it, and no other
+    /// live reference may target any column this view fetches for `'w`. Implementations for
+    /// a tuple naming the same component type twice would alias — same caveat as
+    /// `query::QueryData`'s tuple impls.
+    unsafe fn fetch_mut(table: *mut Table, row: usize, registry: &Registry) -> Option<Self>;
+}
+
+impl<'w, C: Component> ViewMut<'w> for &'w mut C {
+    unsafe fn fetch_mut(table: *mut Table, row: usize, registry: &Registry) -> Option<Self> {
+        let id = registry.id_of::<C>()?;
+        let table = &mut *table;
+        let ptr = table.column_mut(id)?.get_mut(row)?;
+        Some(&mut *(ptr as *mut C))
+    }
+}
+
+/// Fetches the row's own `Entity` — e.g. `view_mut::<(Entity, &mut Position)>()`. Only reads
+/// `table`, so it never conflicts with the mutable components fetched alongside it in the
+/// same tuple.
+impl<'w> ViewMut<'w> for Entity {
+    unsafe fn fetch_mut(table: *mut Table, row: usize, _registry: &Registry) -> Option<Self> {
+        Some((*table).entity(row))
+    }
+}
+
+macro_rules! impl_view_mut_for_tuple {
+    ($($name:ident),+) => {
+        impl<'w, $($name: ViewMut<'w>),+> ViewMut<'w> for ($($name,)+) {
+            unsafe fn fetch_mut(table: *mut Table, row: usize, registry: &Registry) -> Option<Self> {
+                Some(($($name::fetch_mut(table, row, registry)?,)+))
+            }
+        }
+    };
+}
+
+impl_view_mut_for_tuple!(A, B);
+impl_view_mut_for_tuple!(A, B, C);
+impl_view_mut_for_tuple!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::World;
+
+    #[derive(Debug)]
+    struct Position(f32, f32);
+    impl Component for Position {}
+
+    struct Opaque;
+    impl Component for Opaque {}
+
+    #[test]
+    fn debug_dump_renders_debuggable_components_and_opaque_for_the_rest() {
+        let mut world = World::new();
+        world.registry_mut().register_debuggable::<Position>();
+        world.registry_mut().register::<Opaque>();
+        let handle = world.spawn((Position(1.0, 2.0), Opaque));
+
+        let entity_ref = world.entity_ref(handle).unwrap();
+        let dump = entity_ref.debug_dump();
+
+        assert!(dump.contains("Position(Position(1.0, 2.0))"), "{dump}");
+        assert!(dump.contains("Opaque(<opaque>)"), "{dump}");
+    }
+
+    #[test]
+    fn view_with_entity_matches_the_spawned_handle() {
+        let mut world = World::new();
+        let handle = world.spawn(Position(1.0, 2.0));
+
+        let entity_ref = world.entity_ref(handle).unwrap();
+        let (entity, position) = entity_ref.view::<(Entity, &Position)>().unwrap();
+        assert_eq!(entity, handle);
+        assert_eq!((position.0, position.1), (1.0, 2.0));
+
+        let mut entity_mut = world.entity_mut(handle).unwrap();
+        let (entity, position) = entity_mut.view_mut::<(Entity, &mut Position)>().unwrap();
+        assert_eq!(entity, handle);
+        position.0 = 5.0;
+        assert_eq!(entity_mut.get::<Position>().unwrap().0, 5.0);
+    }
+}
@@ -0,0 +1,275 @@
+//! Event streams: a bounded queue of values a system can `send`, drained independently by any
+//! number of `EventReader`s at their own pace via a monotonic cursor.
+
+use crate::ecs::unique::Unique;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+
+/// A queue of `E` values, written by `send` and drained independently by any number of
+/// `EventReader`s. Stored in a `World` as a `Unique` (`world.insert_unique(Stream::<E>::new())`),
+/// so any system can reach it.
+///
+/// `retain` bounds a ring buffer of the most recently sent events: any event pushed off the
+/// back of it is gone for good, so a reader that falls more than `retain` sends behind (or is
+/// created without opting into replay) misses it. `Stream::new()` sets `retain` to zero, i.e.
+/// no memory is spent retaining history at all — the default is a fire-and-forget stream where
+/// only a reader that's already caught up as of `send` sees the event. `with_retained` trades
+/// that memory for a replay window: an `EventReader` created later can opt into starting from
+/// the oldest still-retained event (via `EventReader::with_replay`) instead of only from "now"
+/// — useful for UI that needs to reconstruct recent state after being spawned mid-game.
+pub struct Stream<E> {
+    retain: usize,
+    buffer: VecDeque<E>,
+    /// The sequence number of `buffer`'s first element, advanced every time a retained event
+    /// ages out past `retain`.
+    start: u64,
+    /// The sequence number the next `send`ed event will get; also the cursor value meaning
+    /// "caught up to everything sent so far".
+    next: u64,
+}
+
+impl<E> Stream<E> {
+    /// No retained history: a reader only ever sees events sent after it was created.
+    pub fn new() -> Self {
+        Self::with_retained(0)
+    }
+
+    /// Keeps the last `retain` events around indefinitely (a bounded ring buffer) so a reader
+    /// created later can still replay them via `EventReader::with_replay`.
+    pub fn with_retained(retain: usize) -> Self {
+        Self {
+            retain,
+            buffer: VecDeque::new(),
+            start: 0,
+            next: 0,
+        }
+    }
+
+    pub fn send(&mut self, event: E) {
+        self.buffer.push_back(event);
+        self.next += 1;
+        while self.buffer.len() > self.retain {
+            self.buffer.pop_front();
+            self.start += 1;
+        }
+    }
+
+    /// Every retained event sent at or after `cursor`, oldest first. If `cursor` predates the
+    /// oldest retained event, replay just starts from there instead of erroring — those events
+    /// have already aged out of the window and are really gone.
+    pub fn since(&self, cursor: u64) -> impl Iterator<Item = &E> {
+        let skip = cursor.saturating_sub(self.start).min(self.buffer.len() as u64) as usize;
+        self.buffer.iter().skip(skip)
+    }
+
+    /// The cursor value meaning "caught up to every event sent so far".
+    pub fn latest_cursor(&self) -> u64 {
+        self.next
+    }
+
+    /// The oldest cursor value `since` can still fully honor; anything older has already aged
+    /// out of the retained window.
+    pub fn oldest_cursor(&self) -> u64 {
+        self.start
+    }
+}
+
+impl<E> Default for Stream<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Send + Sync + 'static> Unique for Stream<E> {}
+
+/// Marker trait for types that can be routed through a `Broker`. Implemented via
+/// `#[derive(Event)]`, which also generates an inherent `register` helper (see the macro's
+/// doc comment) so a caller never has to spell out `Broker::register::<E>()` itself.
+pub trait Event: Clone + Send + Sync + std::fmt::Debug + 'static {}
+
+/// Identifies an event type registered with a particular `Broker`. Stable for the lifetime of
+/// the `Broker` that produced it, and cheap to hold onto (e.g. as a system-local field) so a
+/// hot `send` site never has to look one up again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventTypeId(usize);
+
+/// Routes `Event`s to per-type `Stream`s by a stable `EventTypeId` assigned once at
+/// registration (see `Broker::register`), rather than hashing every `send` by `TypeId` the
+/// way ad hoc `World::unique::<Stream<E>>()` access would (`Uniques` keys its map by `TypeId`
+/// precisely because unique lookups aren't the hot path event sends are meant to be).
+///
+/// Mirrors `component::Registry`'s own "hash once at registration, index by `usize`
+/// afterward" shape.
+#[derive(Default)]
+pub struct Broker {
+    streams: Vec<Box<dyn Any + Send + Sync>>,
+    indices: HashMap<TypeId, EventTypeId>,
+}
+
+impl Broker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `E` if it hasn't been seen before, returning its `EventTypeId` either way.
+    /// The backing stream retains no history — see `Stream::new` — so only a reader created
+    /// (or read) before a `send` observes it; use `register_retained` for replay.
+    pub fn register<E: Event>(&mut self) -> EventTypeId {
+        self.register_with(Stream::<E>::new())
+    }
+
+    /// Like `register`, but backs `E` with `Stream::with_retained(retain)` instead of a
+    /// no-history stream, so a reader created later can still replay what's still buffered.
+    pub fn register_retained<E: Event>(&mut self, retain: usize) -> EventTypeId {
+        self.register_with(Stream::<E>::with_retained(retain))
+    }
+
+    fn register_with<E: Event>(&mut self, stream: Stream<E>) -> EventTypeId {
+        if let Some(&id) = self.indices.get(&TypeId::of::<E>()) {
+            return id;
+        }
+        let id = EventTypeId(self.streams.len());
+        self.streams.push(Box::new(stream));
+        self.indices.insert(TypeId::of::<E>(), id);
+        id
+    }
+
+    /// Sends `event` through the stream `id` names.
+    ///
+    /// # Panics
+    /// Panics if `id` wasn't returned by registering `E` with this same `Broker`.
+    pub fn send<E: Event>(&mut self, id: EventTypeId, event: E) {
+        self.stream_mut::<E>(id).send(event);
+    }
+
+    /// The `Stream<E>` `id` names, for constructing an `EventReader` or reading directly.
+    ///
+    /// # Panics
+    /// Panics if `id` wasn't returned by registering `E` with this same `Broker`.
+    pub fn stream<E: Event>(&self, id: EventTypeId) -> &Stream<E> {
+        self.streams[id.0].downcast_ref().expect("EventTypeId used with a Broker or event type it wasn't registered against")
+    }
+
+    fn stream_mut<E: Event>(&mut self, id: EventTypeId) -> &mut Stream<E> {
+        self.streams[id.0].downcast_mut().expect("EventTypeId used with a Broker or event type it wasn't registered against")
+    }
+}
+
+/// A cursor into a `Stream<E>`, letting one system read every event sent since it last did
+/// without stepping on any other reader's progress.
+pub struct EventReader<E> {
+    cursor: u64,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> EventReader<E> {
+    /// Starts caught up to `stream` — only events sent after this call are read.
+    pub fn new(stream: &Stream<E>) -> Self {
+        Self {
+            cursor: stream.latest_cursor(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Starts as far behind as `stream`'s retained window allows, so the first `read` replays
+    /// everything still retained instead of only events sent from this point on.
+    pub fn with_replay(stream: &Stream<E>) -> Self {
+        Self {
+            cursor: stream.oldest_cursor(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Every event sent since this reader last read, oldest first. Advances the cursor to
+    /// `stream`'s latest, so the next call only sees events sent after this one.
+    pub fn read<'s>(&mut self, stream: &'s Stream<E>) -> impl Iterator<Item = &'s E> {
+        let events = stream.since(self.cursor);
+        self.cursor = stream.latest_cursor();
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Damage(u32);
+
+    #[test]
+    fn reader_only_sees_events_sent_after_it_was_created() {
+        let mut stream = Stream::with_retained(4);
+        stream.send(Damage(1));
+
+        let mut reader = EventReader::new(&stream);
+        assert_eq!(reader.read(&stream).collect::<Vec<_>>(), Vec::<&Damage>::new());
+
+        stream.send(Damage(2));
+        assert_eq!(reader.read(&stream).collect::<Vec<_>>(), vec![&Damage(2)]);
+        assert_eq!(reader.read(&stream).collect::<Vec<_>>(), Vec::<&Damage>::new());
+    }
+
+    #[test]
+    fn default_stream_retains_nothing_so_reads_never_see_past_sends() {
+        let mut stream = Stream::new();
+        stream.send(Damage(1));
+        assert_eq!(stream.since(0).count(), 0);
+    }
+
+    #[test]
+    fn late_reader_replays_exactly_the_retained_window() {
+        let mut stream = Stream::with_retained(3);
+        for i in 0..5 {
+            stream.send(Damage(i));
+        }
+
+        // A reader created only now must still see the last 3 sends — 0, 1 already aged out.
+        let mut reader = EventReader::with_replay(&stream);
+        assert_eq!(reader.read(&stream).collect::<Vec<_>>(), vec![&Damage(2), &Damage(3), &Damage(4)]);
+        assert_eq!(reader.read(&stream).collect::<Vec<_>>(), Vec::<&Damage>::new());
+    }
+
+    #[test]
+    fn since_clamps_a_cursor_older_than_the_retained_window() {
+        let mut stream = Stream::with_retained(2);
+        for i in 0..10 {
+            stream.send(Damage(i));
+        }
+
+        // Cursor 0 asks for everything, but only the last 2 are still retained.
+        assert_eq!(stream.since(0).collect::<Vec<_>>(), vec![&Damage(8), &Damage(9)]);
+    }
+
+    #[derive(rusty_engine_macros::Event, Debug, PartialEq, Clone)]
+    struct Explosion(u32);
+
+    #[test]
+    fn derived_event_registers_and_returns_a_stable_id() {
+        let mut broker = Broker::new();
+        let first = Explosion::register(&mut broker);
+        let second = Explosion::register(&mut broker);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn sending_and_receiving_a_derived_event_through_a_broker() {
+        let mut broker = Broker::new();
+        let id = broker.register_retained::<Explosion>(4);
+
+        let mut reader = EventReader::new(broker.stream::<Explosion>(id));
+        broker.send(id, Explosion(9));
+
+        assert_eq!(reader.read(broker.stream(id)).collect::<Vec<_>>(), vec![&Explosion(9)]);
+        assert_eq!(reader.read(broker.stream(id)).count(), 0);
+    }
+
+    #[test]
+    fn no_retention_by_default_means_the_buffer_never_grows() {
+        let mut stream = Stream::new();
+        for i in 0..100 {
+            stream.send(Damage(i));
+        }
+        assert_eq!(stream.since(0).count(), 0);
+    }
+}
@@ -0,0 +1,26 @@
+//! The entity-component-system core of the engine.
+
+pub mod command;
+pub mod component;
+pub mod entity;
+pub mod event;
+pub mod query;
+pub mod rollback;
+pub mod schedule;
+pub mod shard;
+pub mod storage;
+pub mod system;
+pub mod unique;
+pub mod world;
+
+pub use command::{CommandBuffer, Reserved, Target};
+pub use component::Component;
+pub use entity::Entity;
+pub use event::{Broker, Event, EventReader, EventTypeId, Stream};
+pub use query::Query;
+pub use rollback::RollbackBuffer;
+pub use schedule::{Phase, Schedule, SystemId};
+pub use shard::{GrantTracker, RawShard, Shard};
+pub use system::{IntoSystem, System};
+pub use unique::{NonSend, NonSendMut, NonSendUnique, Uniq, UniqConflict, UniqMut, UniqSet, Unique};
+pub use world::{UnregisteredComponent, World};
@@ -0,0 +1,197 @@
+//! `Ref<C>`/`Mut<C>`: query elements that expose a component's change-detection ticks
+//! alongside its value, rather than requiring a separate filter.
+//!
+//! Not to be confused with `entity::Ref`/`entity::RefMut`, which are untyped, whole-row views
+//! returned by `World::entity`/`entity_mut` — these are typed `QueryData` elements, one per
+//! component, fetched the same way `&C`/`&mut C` are.
+
+use crate::ecs::component::{Component, ComponentId, Registry};
+use crate::ecs::query::data::{FetchContext, QueryData};
+use crate::ecs::storage::column::Column;
+use crate::ecs::world::World;
+use std::marker::PhantomData;
+
+/// A shared reference to a component plus the ticks it was added and last changed at.
+///
+/// `is_added`/`is_changed` compare those ticks against the world's tick *at the moment this
+/// `Ref` was fetched* — true only if the component was added/changed during that same tick.
+pub struct Ref<'w, C: Component> {
+    value: &'w C,
+    column: *const Column,
+    row: usize,
+    tick: u64,
+    _marker: PhantomData<&'w C>,
+}
+
+impl<'w, C: Component> Ref<'w, C> {
+    /// True if this component was pushed (spawned or inserted) during the tick it was
+    /// fetched at.
+    pub fn is_added(&self) -> bool {
+        unsafe { (*self.column).added_tick(self.row) == self.tick }
+    }
+
+    /// True if this component was pushed or mutated through a `Mut<C>` during the tick it
+    /// was fetched at.
+    pub fn is_changed(&self) -> bool {
+        unsafe { (*self.column).changed_tick(self.row) == self.tick }
+    }
+}
+
+impl<'w, C: Component> std::ops::Deref for Ref<'w, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.value
+    }
+}
+
+impl<C: Component> QueryData for Ref<'_, C> {
+    type Item<'w> = Ref<'w, C>;
+    const IDS_LEN: usize = 1;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        vec![registry.register::<C>()]
+    }
+
+    unsafe fn fetch<'w>(_world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Ref<'w, C> {
+        let column = ctx.columns[*cursor];
+        *cursor += 1;
+        assert!(!column.is_null(), "query matched a table missing its component");
+        let ptr = (*column).get(row).expect("query row out of bounds");
+        Ref {
+            value: &*(ptr as *const C),
+            column,
+            row,
+            tick: ctx.tick,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An exclusive reference to a component plus its change-detection ticks.
+///
+/// Reading through `Deref` doesn't mark the component changed; only actually going through
+/// `DerefMut` does, matching the crate's existing "mutable access marks the row dirty only
+/// when it's really taken" intent (mirrored by `Mut::is_changed` staying accurate even if a
+/// system holds a `Mut<C>` without ever writing through it).
+pub struct Mut<'w, C: Component> {
+    value: &'w mut C,
+    column: *mut Column,
+    row: usize,
+    tick: u64,
+    _marker: PhantomData<&'w mut C>,
+}
+
+impl<'w, C: Component> Mut<'w, C> {
+    pub fn is_added(&self) -> bool {
+        unsafe { (*self.column).added_tick(self.row) == self.tick }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        unsafe { (*self.column).changed_tick(self.row) == self.tick }
+    }
+}
+
+impl<'w, C: Component> std::ops::Deref for Mut<'w, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.value
+    }
+}
+
+impl<'w, C: Component> std::ops::DerefMut for Mut<'w, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        unsafe { (*self.column).mark_changed(self.row, self.tick) };
+        self.value
+    }
+}
+
+impl<C: Component> QueryData for Mut<'_, C> {
+    type Item<'w> = Mut<'w, C>;
+    const IDS_LEN: usize = 1;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        let id = registry.register::<C>();
+        assert!(
+            !registry.info(id).immutable(),
+            "component {} is #[component(immutable)] and can't be queried with Mut<_>",
+            registry.info(id).name(),
+        );
+        vec![id]
+    }
+
+    unsafe fn fetch<'w>(_world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Mut<'w, C> {
+        let column = ctx.columns[*cursor];
+        *cursor += 1;
+        assert!(!column.is_null(), "query matched a table missing its component");
+        let ptr = (*column).get_mut(row).expect("query row out of bounds");
+        Mut {
+            value: &mut *(ptr as *mut C),
+            column,
+            row,
+            tick: ctx.tick,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+    use crate::ecs::world::World;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[test]
+    fn ref_reports_added_on_the_tick_it_was_spawned_and_fetched() {
+        let mut world = World::new();
+        world.spawn(Health(10));
+
+        let mut query: Query<Ref<Health>> = Query::new(&mut world);
+        let health = query.iter().next().unwrap();
+        assert!(health.is_added());
+        assert!(health.is_changed());
+        assert_eq!(health.0, 10);
+    }
+
+    #[test]
+    fn mut_deref_mut_marks_changed_on_a_later_tick() {
+        let mut world = World::new();
+        world.spawn(Health(10));
+        world.advance_tick();
+
+        {
+            let mut query: Query<Mut<Health>> = Query::new(&mut world);
+            let mut health = query.iter().next().unwrap();
+            assert!(!health.is_added());
+            assert!(!health.is_changed());
+            health.0 += 1;
+        }
+
+        let mut query: Query<Ref<Health>> = Query::new(&mut world);
+        let health = query.iter().next().unwrap();
+        assert!(!health.is_added());
+        assert!(health.is_changed());
+        assert_eq!(health.0, 11);
+    }
+
+    #[test]
+    fn mut_without_deref_mut_does_not_mark_changed() {
+        let mut world = World::new();
+        world.spawn(Health(10));
+        world.advance_tick();
+
+        {
+            let mut query: Query<Mut<Health>> = Query::new(&mut world);
+            let _health = query.iter().next().unwrap();
+        }
+
+        let mut query: Query<Ref<Health>> = Query::new(&mut world);
+        let health = query.iter().next().unwrap();
+        assert!(!health.is_changed());
+    }
+}
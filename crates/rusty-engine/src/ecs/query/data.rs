@@ -0,0 +1,399 @@
+//! `QueryData`: what a `Query` fetches per matching entity.
+
+use crate::ecs::component::{Component, ComponentId, Registry};
+use crate::ecs::entity::Entity;
+use crate::ecs::storage::archetype::ArchetypeId;
+use crate::ecs::storage::column::Column;
+use crate::ecs::storage::table::Table;
+use crate::ecs::world::World;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Identifies which table a query row came from. Tables and archetypes are 1:1 in this crate,
+/// so a `TableId` is just an `ArchetypeId` under a name that reads naturally next to `Entity`
+/// and `Row` in `EntityLocation`'s item.
+pub type TableId = ArchetypeId;
+
+/// A row index within a `TableId`'s table.
+pub type Row = usize;
+
+/// Everything `QueryData::fetch` needs about the table a row lives in, besides the row index
+/// itself and its own cursor position — grouped so `fetch` doesn't take a fistful of separate
+/// parameters. One `FetchContext` covers every row `Query::iter` visits in a given table: the
+/// caller builds it once per table (see `query::Result`'s column cache) and shares it across
+/// however many rows that table has.
+pub struct FetchContext<'t> {
+    pub table: *mut Table,
+    pub archetype: ArchetypeId,
+    /// The flattened component ids `Query::iter` computed for the whole `Q`, in the order
+    /// `fetch` expects to consume them.
+    pub ids: &'t [ComponentId],
+    /// `ids`' columns in this context's `table`, resolved once per table instead of hashed
+    /// fresh by every `fetch` call — positionally aligned with `ids`, with a null entry where
+    /// `table` doesn't have that id's column at all.
+    pub columns: &'t [*mut Column],
+    pub tick: u64,
+}
+
+/// Describes one element (or tuple of elements) a `Query` yields per matching entity.
+///
+/// Implemented for `&C` and `&mut C` for any `Component`, for `EntityLocation`, and for
+/// tuples of `QueryData` up to arity 4. The `'w` lifetime on `Item` is the world borrow the
+/// query holds, not the lifetime of any particular `impl` — it's supplied fresh by each
+/// `fetch` call.
+pub trait QueryData {
+    type Item<'w>;
+
+    /// How many ids this element consumes from the flattened `ids` slice per `fetch` call —
+    /// fixed by the type, not the registry. Lets `Option<Q>` skip over exactly `Q`'s ids
+    /// (without calling back into a `&mut Registry` it doesn't have) when the current table
+    /// doesn't have them.
+    const IDS_LEN: usize;
+
+    /// Registers (or looks up) the component ids this data needs, in the order `fetch`
+    /// expects to consume them from the flattened `ids` slice.
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId>;
+
+    /// The ids a table must have for this element to match at all — used by `Query::iter`'s
+    /// archetype filter. Defaults to `component_ids`; `Option<Q>` overrides this to report
+    /// none, since it matches an archetype whether or not `Q`'s components are present.
+    fn required_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        Self::component_ids(registry)
+    }
+
+    /// Reads this data out of `ctx.table` at `row`, consuming ids (and their pre-resolved
+    /// columns) from `ctx.ids`/`ctx.columns` starting at `*cursor`, and advancing `cursor`
+    /// past what it used. `world` is the query's whole world, needed only by elements that
+    /// hop to another entity's row, like `Relation`; `ctx.archetype` identifies `ctx.table`
+    /// itself, needed only by `EntityLocation`; `ctx.tick` is the world's current tick, needed
+    /// only by change-detection elements like `query::Ref`/`query::Mut`. Plain `&C`/`&mut C`
+    /// fetch ignore `world` and everything in `ctx` except `ctx.columns`.
+    ///
+    /// # Safety
+    /// `ctx.table` must be valid for `'w` and have a column for every id this impl consumes;
+    /// `ctx.columns` must be positionally aligned with `ctx.ids` and resolved against that
+    /// same table; `row` must be an occupied row in it. Mutable fetches additionally require
+    /// that no other live reference targets the same `(table, row, component)` for `'w`.
+    unsafe fn fetch<'w>(world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Self::Item<'w>;
+}
+
+impl<C: Component> QueryData for &C {
+    type Item<'w> = &'w C;
+    const IDS_LEN: usize = 1;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        vec![registry.register::<C>()]
+    }
+
+    unsafe fn fetch<'w>(_world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> &'w C {
+        let column = ctx.columns[*cursor];
+        *cursor += 1;
+        assert!(!column.is_null(), "query matched a table missing its component");
+        let ptr = (*column).get(row).expect("query row out of bounds");
+        &*(ptr as *const C)
+    }
+}
+
+impl<C: Component> QueryData for &mut C {
+    type Item<'w> = &'w mut C;
+    const IDS_LEN: usize = 1;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        let id = registry.register::<C>();
+        assert!(
+            !registry.info(id).immutable(),
+            "component {} is #[component(immutable)] and can't be queried with &mut",
+            registry.info(id).name(),
+        );
+        vec![id]
+    }
+
+    unsafe fn fetch<'w>(_world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> &'w mut C {
+        let column = ctx.columns[*cursor];
+        *cursor += 1;
+        assert!(!column.is_null(), "query matched a table missing its component");
+        let ptr = (*column).get_mut(row).expect("query row out of bounds");
+        &mut *(ptr as *mut C)
+    }
+}
+
+/// A `QueryData` whose matched rows live in contiguous per-component storage, so a run of
+/// them can be handed out as a slice instead of fetched one row at a time. Backs
+/// `query::Result::for_each_chunk`.
+///
+/// Implemented for `&C`/`&mut C` and tuples of `Sliceable`; not for `EntityLocation` (its
+/// `(Entity, TableId, Row)` isn't component bytes at all) or `Relation` (each row's value
+/// lives in a different, unrelated entity's row, so there's no contiguous run to slice).
+pub trait Sliceable: QueryData {
+    /// The view `Result::for_each_chunk` hands its callback for one chunk — `&[C]` for a
+    /// single component, or a tuple of parallel same-length slices for a tuple of components.
+    type Slice<'w>;
+
+    /// Reads `len` rows starting at `start` out of `table`, consuming ids from
+    /// `ids[*cursor..]` the same way `QueryData::fetch` does.
+    ///
+    /// # Safety
+    /// Same requirements as `QueryData::fetch`, extended to the whole `[start, start + len)`
+    /// run of rows rather than a single one.
+    unsafe fn slice<'w>(table: *mut Table, ids: &[ComponentId], cursor: &mut usize, start: usize, len: usize) -> Self::Slice<'w>;
+}
+
+impl<C: Component> Sliceable for &C {
+    type Slice<'w> = &'w [C];
+
+    unsafe fn slice<'w>(table: *mut Table, ids: &[ComponentId], cursor: &mut usize, start: usize, len: usize) -> &'w [C] {
+        let id = ids[*cursor];
+        *cursor += 1;
+        (*table).column(id).expect("query matched a table missing its component").slice(start, len)
+    }
+}
+
+impl<C: Component> Sliceable for &mut C {
+    type Slice<'w> = &'w mut [C];
+
+    unsafe fn slice<'w>(table: *mut Table, ids: &[ComponentId], cursor: &mut usize, start: usize, len: usize) -> &'w mut [C] {
+        let id = ids[*cursor];
+        *cursor += 1;
+        (*table).column_mut(id).expect("query matched a table missing its component").slice_mut(start, len)
+    }
+}
+
+/// A relationship query element: given `T`, a component that dereferences to the `Entity` it
+/// points at (e.g. `Target(Entity)`), follows that link and fetches `D` off the *target*
+/// entity rather than the current one — for queries like "this entity's `Target`'s
+/// `Position`". Requires a second table lookup per row, since the target generally lives in
+/// a different table (possibly a different archetype) than the entity holding `T`.
+///
+/// Yields `None` rather than panicking when the link is dangling (the target was despawned)
+/// or the target simply doesn't have a `D` — both are ordinary states for a relationship to
+/// be in, not query bugs.
+pub struct Relation<T, D>(PhantomData<(T, D)>);
+
+impl<T, D> QueryData for Relation<T, D>
+where
+    T: Component + Deref<Target = Entity>,
+    D: Component,
+{
+    type Item<'w> = Option<&'w D>;
+    const IDS_LEN: usize = 1;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        vec![registry.register::<T>()]
+    }
+
+    unsafe fn fetch<'w>(world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Self::Item<'w> {
+        let column = ctx.columns[*cursor];
+        *cursor += 1;
+        assert!(!column.is_null(), "query matched a table missing its component");
+        let ptr = (*column).get(row).expect("query row out of bounds");
+        let relation = &*(ptr as *const T);
+        let target: Entity = **relation;
+
+        let location = world.location(target)?;
+        let target_id = world.registry().id_of::<D>()?;
+        let target_table = world.archetypes().table(location.archetype);
+        let ptr = target_table.column(target_id)?.get(location.row)?;
+        Some(&*(ptr as *const D))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Position(f32, f32);
+
+    #[derive(Component)]
+    struct Target(Entity);
+
+    impl Deref for Target {
+        type Target = Entity;
+
+        fn deref(&self) -> &Entity {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn relation_reads_the_target_entitys_component() {
+        let mut world = World::new();
+        let b = world.spawn(Position(1.0, 2.0));
+        world.spawn(Target(b));
+
+        let mut query: Query<Relation<Target, Position>> = Query::new(&mut world);
+        assert_eq!(query.iter().next(), Some(Some(&Position(1.0, 2.0))));
+    }
+
+    #[test]
+    fn relation_yields_none_for_a_dangling_target() {
+        let mut world = World::new();
+        let b = world.spawn(Position(1.0, 2.0));
+        world.spawn(Target(b));
+        world.despawn(b);
+
+        let mut query: Query<Relation<Target, Position>> = Query::new(&mut world);
+        assert_eq!(query.iter().next(), Some(None));
+    }
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Velocity(f32, f32);
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Drag(f32);
+
+    #[test]
+    fn option_tuple_matches_entities_with_the_whole_group_or_none_of_it() {
+        let mut world = World::new();
+        let with_both = world.spawn((Position(0.0, 0.0), Velocity(1.0, 0.0), Drag(0.5)));
+        let with_neither = world.spawn(Position(1.0, 0.0));
+
+        let mut query: Query<(&Position, Option<(&Velocity, &Drag)>)> = Query::new(&mut world);
+        let mut results: Vec<_> = query.iter().with_entities().collect();
+        results.sort_by_key(|(entity, _)| entity.index());
+
+        assert_eq!(results, vec![
+            (with_both, (&Position(0.0, 0.0), Some((&Velocity(1.0, 0.0), &Drag(0.5))))),
+            (with_neither, (&Position(1.0, 0.0), None)),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "only some of Q's components present")]
+    fn option_tuple_panics_when_only_part_of_the_group_is_present() {
+        let mut world = World::new();
+        world.spawn((Position(0.0, 0.0), Velocity(1.0, 0.0)));
+
+        let mut query: Query<Option<(&Velocity, &Drag)>> = Query::new(&mut world);
+        query.iter().next();
+    }
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    #[component(immutable)]
+    struct EntityKind(u32);
+
+    #[test]
+    fn immutable_component_queries_fine_by_shared_reference() {
+        let mut world = World::new();
+        world.spawn(EntityKind(1));
+
+        let mut query: Query<&EntityKind> = Query::new(&mut world);
+        assert_eq!(query.iter().next(), Some(&EntityKind(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "is #[component(immutable)] and can't be queried with &mut")]
+    fn immutable_component_panics_on_mutable_query() {
+        let mut world = World::new();
+        world.spawn(EntityKind(1));
+
+        let _query: Query<&mut EntityKind> = Query::new(&mut world);
+    }
+}
+
+/// A query element yielding `(Entity, TableId, Row)` for the current item — the raw storage
+/// coordinates behind it, for low-level operations that need to index a parallel buffer by
+/// row (e.g. a GPU-side transform buffer kept in step with a `Table`'s layout) without a
+/// separate `World::location` lookup per entity.
+///
+/// The yielded `Row` is only valid for the duration of the iteration: any swap-remove
+/// (despawn, `remove_component`, `add_component` on another entity in the same table) can
+/// move a different entity into that row afterward.
+pub struct EntityLocation;
+
+impl QueryData for EntityLocation {
+    type Item<'w> = (Entity, TableId, Row);
+    const IDS_LEN: usize = 0;
+
+    fn component_ids(_registry: &mut Registry) -> Vec<ComponentId> {
+        Vec::new()
+    }
+
+    unsafe fn fetch<'w>(_world: &'w World, ctx: &FetchContext<'_>, row: usize, _cursor: &mut usize) -> Self::Item<'w> {
+        let entity = (*ctx.table).entity(row);
+        (entity, ctx.archetype, row)
+    }
+}
+
+/// Matches every entity regardless of whether it has `Q`'s components, yielding `Some(item)`
+/// when it does and `None` when it doesn't — e.g. `Option<(&Velocity, &Drag)>` to treat two
+/// components that are always added and removed together as one logical group, matching
+/// entities with both or neither.
+///
+/// A table with only *some* of `Q`'s components (e.g. `Velocity` without `Drag`) means `Q`'s
+/// components aren't actually travelling together the way `Option<Q>` assumes, which is a
+/// modeling error rather than a state this element is meant to represent — `fetch` panics
+/// rather than silently picking `Some` or `None` for it.
+impl<Q: QueryData> QueryData for Option<Q> {
+    type Item<'w> = Option<Q::Item<'w>>;
+    const IDS_LEN: usize = Q::IDS_LEN;
+
+    fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+        Q::component_ids(registry)
+    }
+
+    fn required_ids(_registry: &mut Registry) -> Vec<ComponentId> {
+        Vec::new()
+    }
+
+    unsafe fn fetch<'w>(world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Self::Item<'w> {
+        let needed = &ctx.columns[*cursor..*cursor + Q::IDS_LEN];
+        let present = needed.iter().filter(|column| !column.is_null()).count();
+        if present == 0 {
+            *cursor += Q::IDS_LEN;
+            return None;
+        }
+        assert_eq!(
+            present,
+            needed.len(),
+            "Option<Q> matched a table with only some of Q's components present — Q's components must always be added and removed as a group"
+        );
+        Some(Q::fetch(world, ctx, row, cursor))
+    }
+}
+
+macro_rules! impl_query_data_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: QueryData),+> QueryData for ($($name,)+) {
+            type Item<'w> = ($($name::Item<'w>,)+);
+            const IDS_LEN: usize = 0 $(+ $name::IDS_LEN)+;
+
+            fn component_ids(registry: &mut Registry) -> Vec<ComponentId> {
+                let mut ids = Vec::new();
+                $(ids.extend($name::component_ids(registry));)+
+                ids
+            }
+
+            fn required_ids(registry: &mut Registry) -> Vec<ComponentId> {
+                let mut ids = Vec::new();
+                $(ids.extend($name::required_ids(registry));)+
+                ids
+            }
+
+            unsafe fn fetch<'w>(world: &'w World, ctx: &FetchContext<'_>, row: usize, cursor: &mut usize) -> Self::Item<'w> {
+                ($($name::fetch(world, ctx, row, cursor),)+)
+            }
+        }
+    };
+}
+
+impl_query_data_for_tuple!(A, B);
+impl_query_data_for_tuple!(A, B, C);
+impl_query_data_for_tuple!(A, B, C, D);
+
+macro_rules! impl_sliceable_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Sliceable),+> Sliceable for ($($name,)+) {
+            type Slice<'w> = ($($name::Slice<'w>,)+);
+
+            unsafe fn slice<'w>(table: *mut Table, ids: &[ComponentId], cursor: &mut usize, start: usize, len: usize) -> Self::Slice<'w> {
+                ($($name::slice(table, ids, cursor, start, len),)+)
+            }
+        }
+    };
+}
+
+impl_sliceable_for_tuple!(A, B);
+impl_sliceable_for_tuple!(A, B, C);
+impl_sliceable_for_tuple!(A, B, C, D);
@@ -0,0 +1,349 @@
+//! Queries: iterate every entity that has a given set of components.
+
+mod change;
+mod data;
+#[cfg(feature = "rayon")]
+mod par;
+mod result;
+
+pub use change::{Mut, Ref};
+pub use data::{EntityLocation, QueryData, Relation, Row, Sliceable, TableId};
+#[cfg(feature = "rayon")]
+pub use par::ParIter;
+pub use result::{FilterView, Result, WithEntities, WithRead, ZipSlice};
+
+use crate::ecs::component::{Component, ComponentId, ComponentMask, Registry};
+use crate::ecs::storage::ArchetypeId;
+use crate::ecs::world::World;
+use std::marker::PhantomData;
+
+/// A request for every entity with components matching `Q` (e.g. `Query::<(&A, &mut B)>`).
+///
+/// Borrows the whole `World` mutably for its lifetime — like `World::entity_mut`, this keeps
+/// aliasing trivially sound at the cost of one query at a time; per-access conflict tracking
+/// (so independent queries can run concurrently) is a `Schedule`-level concern.
+pub struct Query<'w, Q: QueryData> {
+    world: &'w mut World,
+    ids: Vec<ComponentId>,
+    required: Vec<ComponentId>,
+    extra_reads: Vec<ComponentId>,
+    enabled: bool,
+    allowlist: Option<Vec<ArchetypeId>>,
+    _marker: PhantomData<Q>,
+}
+
+impl<'w, Q: QueryData> Query<'w, Q> {
+    pub fn new(world: &'w mut World) -> Self {
+        let ids = Q::component_ids(world.registry_mut());
+        let required = Q::required_ids(world.registry_mut());
+        Self {
+            world,
+            ids,
+            required,
+            extra_reads: Vec::new(),
+            enabled: true,
+            allowlist: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Every component id this query touches, in the order `Q` declares them — including ones
+    /// wrapped in `Option`, which don't have to be present for a match. See `required_ids` for
+    /// just the ones a table must have.
+    pub fn ids(&self) -> &[ComponentId] {
+        &self.ids
+    }
+
+    pub fn registry(&self) -> &Registry {
+        self.world.registry()
+    }
+
+    /// Gates the query on `cond`, checked once, right now, against the world as it stands.
+    /// If it's false, `iter()` (and anything built from it) behaves as though the query
+    /// matched no entities — cheaply: `iter()` skips the archetype scan entirely rather than
+    /// building the match list and then discarding it. Meant for disabling a whole system's
+    /// work behind a rarely-true unique/resource flag without a separate run condition.
+    pub fn when(mut self, cond: impl Fn(&World) -> bool) -> Self {
+        if !cond(self.world) {
+            self.enabled = false;
+        }
+        self
+    }
+
+    /// Narrows `iter()` to only the archetypes named in `archetype_ids`, intersected with
+    /// whatever `Q` already matches — e.g. manually sharding a query across threads by
+    /// archetype for a parallel phase, where each shard only wants the tables it was assigned.
+    /// An archetype absent from `archetype_ids` is skipped even if it matches `Q`.
+    pub fn restrict_to(mut self, archetype_ids: &[ArchetypeId]) -> Self {
+        self.allowlist = Some(archetype_ids.to_vec());
+        self
+    }
+
+    /// Declares `C` as an extra, read-only component this query's iteration can access via
+    /// `Result::with_read` without ending the query to look it up separately — e.g. reading
+    /// `&Mass` alongside a `&mut Velocity` query. Checked once, right here, rather than
+    /// deferred to iteration: `C` must be disjoint from `Q`'s own components, since aliasing
+    /// one this query might already be handing out mutably would be unsound.
+    ///
+    /// # Panics
+    /// Panics if `C` is already one of `Q`'s own components.
+    pub fn also_read<C: Component>(mut self) -> Self {
+        let id = self.world.registry_mut().register::<C>();
+        assert!(!self.ids.contains(&id), "Query::also_read::<{}>: already part of this query's own components", std::any::type_name::<C>());
+        self.extra_reads.push(id);
+        self
+    }
+
+    /// Iterates every matching entity's `Q::Item`.
+    pub fn iter(&mut self) -> Result<'_, Q> {
+        if !self.enabled {
+            return Result::new(self.world, Vec::new(), Vec::new()).with_extra_reads(self.extra_reads.clone());
+        }
+        let mask = ComponentMask::from_ids(self.required.iter().copied());
+        let archetypes = self
+            .world
+            .archetypes()
+            .iter()
+            .filter(|(_, table)| table.matches(&mask))
+            .map(|(id, _)| id)
+            .filter(|id| self.allowlist.as_ref().is_none_or(|allowlist| allowlist.contains(id)))
+            .collect();
+        Result::new(self.world, self.ids.clone(), archetypes).with_extra_reads(self.extra_reads.clone())
+    }
+
+    /// Same matches as `iter()`, sorted by `Entity` (index then generation) instead of table
+    /// iteration order, which depends on table creation order and shifts under swap-remove
+    /// as entities despawn. Use this for reproducible tests and deterministic simulation
+    /// steps; it collects every match into a `Vec` and sorts before yielding the first item,
+    /// so it costs more than `iter()` and shouldn't be the default on a hot path.
+    pub fn ordered(&mut self) -> std::vec::IntoIter<Q::Item<'_>> {
+        let mut items: Vec<_> = self.iter().with_entities().collect();
+        items.sort_by_key(|(entity, _)| (entity.index(), entity.generation()));
+        items.into_iter().map(|(_, item)| item).collect::<Vec<_>>().into_iter()
+    }
+}
+
+/// A `Query<'_, Q>`'s reusable, world-independent setup: which components `Q` matches on.
+///
+/// `Query::new` recomputes `Q::component_ids` (which registers `Q`'s components with the
+/// `Registry` the first time they're seen) on every call, which is wasted work for a system
+/// closure that calls it once per `run`. Building a `QueryState` once — e.g. right before
+/// moving it into the closure passed to `Phase::add_system` — and reusing it via
+/// `QueryState::query` on every later `run` does that lookup exactly once for the system's
+/// whole lifetime instead of once per frame.
+///
+/// This crate's systems are plain `FnMut(&mut World)` closures rather than injected
+/// parameters (there's no `Parameter`/system-registry machinery here), so "build once" means
+/// "the caller constructs a `QueryState` once and moves it into the closure's capture" — the
+/// closure itself, reused by `Phase` across every `run`, is what makes the state persist.
+pub struct QueryState<Q: QueryData> {
+    ids: Vec<ComponentId>,
+    required: Vec<ComponentId>,
+    _marker: PhantomData<Q>,
+}
+
+impl<Q: QueryData> QueryState<Q> {
+    /// Registers `Q`'s components (if they aren't already) and caches their ids. Call this
+    /// once per system, not on every `run`.
+    pub fn new(registry: &mut Registry) -> Self {
+        Self {
+            ids: Q::component_ids(registry),
+            required: Q::required_ids(registry),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The component ids this state matches on, in the order `Q` declares them.
+    pub fn ids(&self) -> &[ComponentId] {
+        &self.ids
+    }
+
+    /// The subset of `ids()` a table must have to match — excludes any wrapped in `Option`.
+    /// What `World::add_component_to_all`/`remove_component_from_all` mask archetypes
+    /// against, since a `QueryState` (unlike a live `Query`) doesn't hold the `&mut World`
+    /// those bulk operations need for themselves.
+    pub fn required(&self) -> &[ComponentId] {
+        &self.required
+    }
+
+    /// Builds a `Query` against `world`, reusing this state's already-computed ids instead of
+    /// recomputing them the way `Query::new` would.
+    pub fn query<'w>(&self, world: &'w mut World) -> Query<'w, Q> {
+        Query {
+            world,
+            ids: self.ids.clone(),
+            required: self.required.clone(),
+            extra_reads: Vec::new(),
+            enabled: true,
+            allowlist: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    pub(crate) struct Position(pub f32, pub f32);
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    pub(crate) struct Velocity(pub f32, pub f32);
+
+    #[test]
+    fn iterates_only_matching_archetypes() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn((Position(2.0, 0.0), Velocity(1.0, 0.0)));
+        world.spawn(Velocity(3.0, 0.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let mut positions: Vec<_> = query.iter().collect();
+        positions.sort_by(|a: &&Position, b: &&Position| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(positions, vec![&Position(1.0, 0.0), &Position(2.0, 0.0)]);
+    }
+
+    #[test]
+    fn mutates_through_query() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        for position in query.iter() {
+            position.0 += 10.0;
+        }
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        assert_eq!(query.iter().next(), Some(&Position(11.0, 0.0)));
+    }
+
+    #[test]
+    fn ordered_iterates_the_same_sequence_regardless_of_despawn_history() {
+        let mut plain = World::new();
+        plain.spawn(Position(0.0, 0.0));
+        plain.spawn(Position(1.0, 0.0));
+        plain.spawn(Position(2.0, 0.0));
+
+        let mut plain_query: Query<&Position> = Query::new(&mut plain);
+        assert_eq!(plain_query.iter().copied().collect::<Vec<_>>(), vec![
+            Position(0.0, 0.0),
+            Position(1.0, 0.0),
+            Position(2.0, 0.0)
+        ]);
+
+        // `churned` spawns the same three positions in the same order, but with a filler
+        // entity despawned between each one. Each despawn swap-removes the table's last row
+        // into the freed slot, so the survivors end up in a different table row order than
+        // `plain`'s even though their entity ids still sort the same way (a despawned filler
+        // slot is never reused by a later survivor here, so no survivor's id collides or
+        // reorders relative to another's).
+        let mut churned = World::new();
+        churned.spawn(Position(0.0, 0.0));
+        let filler_a = churned.spawn(Position(-1.0, 0.0));
+        churned.spawn(Position(1.0, 0.0));
+        let filler_b = churned.spawn(Position(-2.0, 0.0));
+        churned.spawn(Position(2.0, 0.0));
+        churned.despawn(filler_a);
+        churned.despawn(filler_b);
+
+        let mut churned_query: Query<&Position> = Query::new(&mut churned);
+        let raw_order: Vec<_> = churned_query.iter().copied().collect();
+        assert_ne!(raw_order, vec![Position(0.0, 0.0), Position(1.0, 0.0), Position(2.0, 0.0)]);
+
+        let mut plain_query: Query<&Position> = Query::new(&mut plain);
+        let plain_ordered: Vec<_> = plain_query.ordered().copied().collect();
+
+        let mut churned_query: Query<&Position> = Query::new(&mut churned);
+        let churned_ordered: Vec<_> = churned_query.ordered().copied().collect();
+
+        assert_eq!(plain_ordered, churned_ordered);
+    }
+
+    #[test]
+    fn when_false_yields_an_empty_iterator_regardless_of_matching_entities() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world).when(|_| false);
+        assert_eq!(query.iter().count(), 0);
+    }
+
+    #[test]
+    fn when_true_matches_normally() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world).when(|_| true);
+        assert_eq!(query.iter().count(), 1);
+    }
+
+    #[test]
+    fn query_state_is_built_once_and_reused_across_many_system_runs() {
+        use crate::ecs::schedule::Phase;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+
+        let builds = Arc::new(AtomicUsize::new(0));
+        let build_counter = builds.clone();
+        // Stands in for "build_state at add_system time": constructed once, before the
+        // closure that becomes the system is ever handed to `Phase`.
+        build_counter.fetch_add(1, Ordering::SeqCst);
+        let state = QueryState::<&Position>::new(world.registry_mut());
+
+        let seen = Arc::new(Mutex::new(0));
+        let seen_counter = seen.clone();
+        let mut phase = Phase::new("update");
+        phase.add_system(move |world: &mut World| {
+            *seen_counter.lock().unwrap() = state.query(world).iter().count();
+        });
+
+        for _ in 0..5 {
+            phase.run(&mut world);
+        }
+
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(*seen.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn restrict_to_only_visits_entities_in_the_allowed_archetypes() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+
+        let allowed = world.location(a).unwrap().archetype;
+
+        let mut query: Query<(EntityLocation, &Position)> = Query::new(&mut world).restrict_to(&[allowed]);
+        let entities: Vec<_> = query.iter().map(|((entity, _, _), _)| entity).collect();
+        assert_eq!(entities, vec![a]);
+        assert_ne!(world.location(b).unwrap().archetype, allowed);
+    }
+
+    #[test]
+    fn entity_location_matches_world_location_for_every_entity() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+        let filler = world.spawn(Position(0.0, 0.0)); // churn a row before reading locations
+        world.despawn(filler);
+
+        let mut query: Query<(EntityLocation, &Position)> = Query::new(&mut world);
+        let fetched: Vec<_> = query.iter().map(|(location, _)| location).collect();
+        for (entity, table, row) in fetched {
+            let expected = world.location(entity).unwrap();
+            assert_eq!(table, expected.archetype);
+            assert_eq!(row, expected.row);
+        }
+
+        let mut query: Query<EntityLocation> = Query::new(&mut world);
+        let locations: std::collections::HashMap<_, _> = query.iter().map(|(entity, table, row)| (entity, (table, row))).collect();
+        assert_eq!(locations[&a], (world.location(a).unwrap().archetype, world.location(a).unwrap().row));
+        assert_eq!(locations[&b], (world.location(b).unwrap().archetype, world.location(b).unwrap().row));
+    }
+}
@@ -0,0 +1,228 @@
+//! `rayon` interop for `query::Result`, for callers already depending on rayon rather than
+//! this crate's own `core::tasks::Executor::par_for_each`.
+
+use crate::ecs::component::ComponentId;
+use crate::ecs::query::data::{FetchContext, QueryData};
+use crate::ecs::storage::archetype::ArchetypeId;
+use crate::ecs::storage::column::Column;
+use crate::ecs::storage::table::Table;
+use crate::ecs::world::World;
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// The `rayon::ParallelIterator` produced by `Result::into_par_iter`.
+pub struct ParIter<'w, Q: QueryData> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    rows: Vec<(ArchetypeId, *mut Table, usize)>,
+    _marker: PhantomData<Q>,
+}
+
+impl<'w, Q: QueryData> ParIter<'w, Q> {
+    pub(super) fn new(world: &'w World, ids: Vec<ComponentId>, rows: Vec<(ArchetypeId, *mut Table, usize)>) -> Self {
+        Self { world, ids, rows, _marker: PhantomData }
+    }
+}
+
+// SAFETY: every `Component` is `Send + Sync` (see `Component`'s supertraits), and rows are
+// only ever handed to one worker at a time (see `into_par_iter`'s doc comment), so sharing
+// or sending a `ParIter`/`RowProducer` across threads is exactly as sound as the sequential
+// `Result` reading the same rows from `&World` one at a time already is. This does rely on
+// `Column` itself never exposing a second kind of access to the same row across workers
+// outside of `Q::fetch` — its `#[cfg(feature = "stats")] reads`/`writes` counters are bumped
+// on every `get`/`get_mut` regardless of which worker's row triggered it, so they're
+// `AtomicU64` rather than `Cell<u64>`/`u64` specifically so this impl stays sound with
+// `--features rayon,stats` (see `Column`'s `reads` field doc comment).
+unsafe impl<'w, Q: QueryData> Send for ParIter<'w, Q> {}
+unsafe impl<'w, Q: QueryData> Sync for ParIter<'w, Q> {}
+
+impl<'w, Q: QueryData> ParallelIterator for ParIter<'w, Q>
+where
+    Q::Item<'w>: Send,
+{
+    type Item = Q::Item<'w>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.rows.len())
+    }
+}
+
+impl<'w, Q: QueryData> IndexedParallelIterator for ParIter<'w, Q>
+where
+    Q::Item<'w>: Send,
+{
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.callback(RowProducer::<Q> { world: self.world, ids: self.ids, rows: self.rows, _marker: PhantomData })
+    }
+}
+
+struct RowProducer<'w, Q: QueryData> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    rows: Vec<(ArchetypeId, *mut Table, usize)>,
+    _marker: PhantomData<Q>,
+}
+
+// SAFETY: see the safety comment on `ParIter`'s `Send`/`Sync` impls above.
+unsafe impl<'w, Q: QueryData> Send for RowProducer<'w, Q> {}
+unsafe impl<'w, Q: QueryData> Sync for RowProducer<'w, Q> {}
+
+impl<'w, Q: QueryData> Producer for RowProducer<'w, Q>
+where
+    Q::Item<'w>: Send,
+{
+    type Item = Q::Item<'w>;
+    type IntoIter = RowIter<'w, Q>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RowIter { world: self.world, ids: self.ids, rows: self.rows, columns: HashMap::new(), index: 0, _marker: PhantomData }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.rows.split_at(index);
+        (
+            RowProducer { world: self.world, ids: self.ids.clone(), rows: left.to_vec(), _marker: PhantomData },
+            RowProducer { world: self.world, ids: self.ids, rows: right.to_vec(), _marker: PhantomData },
+        )
+    }
+}
+
+/// The sequential iterator each rayon worker drives its share of rows through.
+struct RowIter<'w, Q: QueryData> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    rows: Vec<(ArchetypeId, *mut Table, usize)>,
+    /// `ids`' columns per table this worker's share has visited so far, resolved once per
+    /// distinct table rather than once per row — see `query::result::resolve_columns`.
+    columns: HashMap<ArchetypeId, Vec<*mut Column>>,
+    index: usize,
+    _marker: PhantomData<Q>,
+}
+
+impl<'w, Q: QueryData> RowIter<'w, Q> {
+    fn columns_for(&mut self, archetype: ArchetypeId, table: *mut Table) -> *const [*mut Column] {
+        self.columns.entry(archetype).or_insert_with(|| crate::ecs::query::result::resolve_columns(table, &self.ids)).as_slice()
+    }
+}
+
+impl<'w, Q: QueryData> Iterator for RowIter<'w, Q> {
+    type Item = Q::Item<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (archetype, table, row) = *self.rows.get(self.index)?;
+        self.index += 1;
+        // SAFETY: `columns_for` only ever inserts and never removes entries, so this pointer
+        // stays valid for the rest of `self`'s lifetime even once the borrow used to compute
+        // it ends.
+        let columns = unsafe { &*self.columns_for(archetype, table) };
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.ids, columns, tick: self.world.tick() };
+        // SAFETY: `table` is borrowed from `self.world` for `'w`, and `self.rows` holds each
+        // matched `(archetype, table, row)` exactly once — see `ParIter`'s safety comment.
+        Some(unsafe { Q::fetch(self.world, &ctx, row, &mut cursor) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.rows.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'w, Q: QueryData> ExactSizeIterator for RowIter<'w, Q> {}
+
+impl<'w, Q: QueryData> DoubleEndedIterator for RowIter<'w, Q> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.rows.len() {
+            return None;
+        }
+        let (archetype, table, row) = self.rows.pop()?;
+        // SAFETY: same as `next` above.
+        let columns = unsafe { &*self.columns_for(archetype, table) };
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.ids, columns, tick: self.world.tick() };
+        // SAFETY: same as `next` above.
+        Some(unsafe { Q::fetch(self.world, &ctx, row, &mut cursor) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::query::Query;
+    use crate::ecs::world::World;
+    use rayon::iter::ParallelIterator;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Health(u32);
+
+    #[test]
+    fn into_par_iter_sums_the_same_total_as_sequential_iteration() {
+        let mut world = World::new();
+        for i in 0..64 {
+            world.spawn(Health(i));
+        }
+
+        let mut query: Query<&Health> = Query::new(&mut world);
+        let expected: u32 = query.iter().map(|h| h.0).sum();
+
+        let mut query: Query<&Health> = Query::new(&mut world);
+        let total: u32 = query.iter().into_par_iter().map(|h| h.0).sum();
+
+        assert_eq!(total, expected);
+    }
+
+    #[test]
+    fn into_par_iter_visits_every_row_when_mutating() {
+        let mut world = World::new();
+        for i in 0..32 {
+            world.spawn(Health(i));
+        }
+
+        let mut query: Query<&mut Health> = Query::new(&mut world);
+        query.iter().into_par_iter().for_each(|h| h.0 += 1);
+
+        let mut query: Query<&Health> = Query::new(&mut world);
+        let total: u32 = query.iter().map(|h| h.0).sum();
+        assert_eq!(total, (0..32).sum::<u32>() + 32);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn into_par_iter_does_not_lose_stats_updates_to_concurrent_workers() {
+        let mut world = World::new();
+        let count = 200_000;
+        for i in 0..count {
+            world.spawn(Health(i));
+        }
+        let id = world.registry_mut().register::<Health>();
+
+        let mut query: Query<&Health> = Query::new(&mut world);
+        let total: u64 = query.iter().into_par_iter().map(|h| h.0 as u64).sum();
+
+        assert_eq!(total, (0..count as u64).sum::<u64>());
+        assert_eq!(world.access_stats()[&id].reads, count as u64);
+    }
+}
@@ -0,0 +1,902 @@
+//! The iterator produced by `Query::iter`.
+
+use crate::ecs::component::{Component, ComponentId};
+use crate::ecs::entity::Entity;
+use crate::ecs::query::data::{FetchContext, QueryData, Sliceable, TableId};
+use crate::ecs::storage::archetype::ArchetypeId;
+use crate::ecs::storage::column::Column;
+use crate::ecs::storage::table::Table;
+use crate::ecs::world::World;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Resolves each of `ids`' columns in `table` once, in the same order — the array `QueryData::
+/// fetch` indexes by cursor position instead of re-hashing `ComponentId -> Column` on every
+/// row. A null entry means `table` doesn't have that id's column at all (an `Option<Q>` element
+/// that doesn't match this table).
+pub(super) fn resolve_columns(table: *mut Table, ids: &[ComponentId]) -> Vec<*mut Column> {
+    // SAFETY: caller guarantees `table` stays valid and structurally unchanged for as long as
+    // the returned pointers are used.
+    ids.iter().map(|&id| unsafe { (*table).column_mut(id) }.map_or(std::ptr::null_mut(), |column| column as *mut Column)).collect()
+}
+
+/// An iterator over every entity matching a `Query`, yielding `Q::Item` per entity.
+///
+/// Named `Result` (the outcome of running the query) rather than `Iter`, matching how the
+/// rest of the crate names query-adjacent types after what they represent, not their shape.
+pub struct Result<'w, Q: QueryData> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    extra_reads: Vec<ComponentId>,
+    archetypes: Vec<ArchetypeId>,
+    archetype_index: usize,
+    row: usize,
+    /// `ids`' columns for the table at `archetype_index`, resolved once via `resolve_columns`
+    /// when it's first entered rather than every row `Q::fetch` visits in it.
+    columns: Vec<*mut Column>,
+    columns_for: Option<ArchetypeId>,
+    _marker: PhantomData<Q>,
+}
+
+impl<'w, Q: QueryData> Result<'w, Q> {
+    pub(crate) fn new(world: &'w World, ids: Vec<ComponentId>, archetypes: Vec<ArchetypeId>) -> Self {
+        Self {
+            world,
+            ids,
+            extra_reads: Vec::new(),
+            archetypes,
+            archetype_index: 0,
+            row: 0,
+            columns: Vec::new(),
+            columns_for: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Attaches `Query::also_read`'s extra ids so `with_read` can later confirm the type it's
+    /// asked for was actually declared, instead of taking the caller's word for it.
+    pub(crate) fn with_extra_reads(mut self, extra_reads: Vec<ComponentId>) -> Self {
+        self.extra_reads = extra_reads;
+        self
+    }
+
+    fn current_table(&mut self) -> Option<(ArchetypeId, *mut Table)> {
+        let archetype = *self.archetypes.get(self.archetype_index)?;
+        let table = self.world.archetypes().table(archetype) as *const Table as *mut Table;
+        if self.columns_for != Some(archetype) {
+            self.columns = resolve_columns(table, &self.ids);
+            self.columns_for = Some(archetype);
+        }
+        Some((archetype, table))
+    }
+
+    /// Finds the next unyielded row, skipping past exhausted tables, any row where one of
+    /// `self.ids`' components has been `World::disable_component`d, and any row whose entity
+    /// is `World::queue_despawn`d but not yet actually removed, and advances past it. Shared
+    /// by `Iterator::next`, `WithEntities`, `pairs()`, and `into_par_iter` so all four fetch
+    /// (and skip) from the same cursor. `for_each_chunk` does not observe disabled or
+    /// pending-despawn rows — see its own doc comment.
+    fn advance(&mut self) -> Option<(ArchetypeId, *mut Table, usize)> {
+        loop {
+            let (archetype, table) = self.current_table()?;
+            // SAFETY: see the safety comment on `Iterator::next` below.
+            let len = unsafe { (*table).len() };
+            if self.row >= len {
+                self.archetype_index += 1;
+                self.row = 0;
+                continue;
+            }
+            let row = self.row;
+            self.row += 1;
+            // SAFETY: same as the `len` read above.
+            if self.ids.iter().any(|&id| unsafe { (*table).column(id) }.is_some_and(|column| column.is_disabled(row))) {
+                continue;
+            }
+            // SAFETY: same as the `len` read above.
+            if self.world.is_pending_despawn(unsafe { (*table).entity(row) }) {
+                continue;
+            }
+            return Some((archetype, table, row));
+        }
+    }
+
+    /// Pairs each item with the `Entity` it came from, e.g. for building an `Entity`-keyed
+    /// map from a query without a separate per-item lookup.
+    pub fn with_entities(self) -> WithEntities<'w, Q> {
+        WithEntities { inner: self }
+    }
+
+    /// Runs `f` over every match, taking `self` by value so the `World` borrow it holds ends
+    /// as soon as this call returns rather than lingering with whatever variable produced it.
+    /// The blanket `Iterator::for_each` already does this — `Result` is consumed either way —
+    /// but a named inherent method here makes the pattern discoverable at the call site
+    /// instead of relying on a reader already knowing `Iterator::for_each` takes `self`: code
+    /// right after this call is free to make structural changes (`World::spawn`, etc.) that a
+    /// held-open `Query` would otherwise conflict with.
+    pub fn for_each(self, f: impl FnMut(Q::Item<'w>)) {
+        Iterator::for_each(self, f);
+    }
+
+    /// Runs `f` over every match with its `Entity`, without adding `Entity`/`EntityLocation`
+    /// to `Q` itself — e.g. syncing an external `HashMap<Entity, Handle>` (a physics engine's
+    /// body handles, an audio system's voices) alongside an existing query's components.
+    /// Shorthand for `self.with_entities().for_each(|(entity, item)| f(entity, item))`.
+    pub fn for_each_with_entity(self, mut f: impl FnMut(Entity, Q::Item<'w>)) {
+        self.with_entities().for_each(|(entity, item)| f(entity, item));
+    }
+
+    /// Converts this into a `rayon::ParallelIterator` over the same matches `iter()` would
+    /// yield, splitting the matched rows across rayon's thread pool instead of this crate's
+    /// own `core::tasks::Executor::par_for_each`. Disjoint mutable access between workers is
+    /// guaranteed the same way `pairs()` guarantees it between the two sides of a pair: every
+    /// row is visited exactly once, and a `rayon::Producer` only ever splits its row range in
+    /// two, so no two workers ever hold a `Q::Item` for the same row at once.
+    #[cfg(feature = "rayon")]
+    pub fn into_par_iter(mut self) -> crate::ecs::query::par::ParIter<'w, Q> {
+        let mut rows = Vec::new();
+        while let Some(coord) = self.advance() {
+            rows.push(coord);
+        }
+        crate::ecs::query::par::ParIter::new(self.world, self.ids, rows)
+    }
+
+    /// Every unordered pair of distinct matching entities, each yielded exactly once — e.g.
+    /// for a collision/interaction system that needs to test every pair of nearby entities
+    /// against each other. `O(n²)` in the number of matches, since every pair is visited.
+    pub fn pairs(self) -> Pairs<'w, Q> {
+        let world = self.world;
+        let ids = self.ids.clone();
+        let mut coords = Vec::new();
+        let mut rows = self;
+        while let Some(coord) = rows.advance() {
+            coords.push(coord);
+        }
+        Pairs {
+            world,
+            ids,
+            coords,
+            columns: HashMap::new(),
+            i: 0,
+            j: 1,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pairs each row's item with the matching element of an externally computed slice,
+    /// e.g. zipping positions against a `Vec<Force>` computed elsewhere for this frame.
+    /// `per_table` is asked for its archetype's whole slice once per row — a query can span
+    /// several tables, and there's no way for `Result` to know how an external `Vec` lines
+    /// up with any of them except by asking the caller — so it should be cheap (a `HashMap`
+    /// lookup or, for a single-table query, just returning the same slice every time).
+    ///
+    /// Panics if `per_table`'s slice is shorter than the row it's asked for; a slice not
+    /// aligned to the matched archetype's rows is a caller bug, not a recoverable case.
+    pub fn zip_slice<F>(self, per_table: F) -> ZipSlice<'w, Q, F> {
+        ZipSlice { inner: self, per_table }
+    }
+
+    /// Pairs each row's item with a read-only reference to `C` on the same entity, for a
+    /// component that isn't part of `Q` itself — e.g. reading `&Mass` alongside a
+    /// `&mut Velocity` query without ending the query to look it up separately. `Query::
+    /// also_read::<C>()` must have been called first; this only confirms that declaration was
+    /// made for `C` specifically, since `Result` itself has no way to reconstruct
+    /// `Query::ids` at this point.
+    ///
+    /// The item is `None` for a row whose table doesn't have `C` at all — `also_read` doesn't
+    /// require every match to carry it, only that it's disjoint from `Q`.
+    ///
+    /// # Panics
+    /// Panics if `Query::also_read::<C>()` wasn't called before `iter()`.
+    pub fn with_read<C: Component>(self) -> WithRead<'w, Q, C> {
+        let id = self.world.registry().id_of::<C>();
+        assert!(
+            id.is_some_and(|id| self.extra_reads.contains(&id)),
+            "Result::with_read::<{}>: call Query::also_read::<{}>() before iter()",
+            std::any::type_name::<C>(),
+            std::any::type_name::<C>(),
+        );
+        WithRead { inner: self, _marker: PhantomData }
+    }
+}
+
+/// The iterator produced by `Result::with_read`.
+pub struct WithRead<'w, Q: QueryData, C> {
+    inner: Result<'w, Q>,
+    _marker: PhantomData<C>,
+}
+
+impl<'w, Q: QueryData, C: Component> Iterator for WithRead<'w, Q, C> {
+    type Item = (Q::Item<'w>, Option<&'w C>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (archetype, table, row) = self.inner.advance()?;
+        // SAFETY: same as `Result::next` for the `Q::fetch` half. `C` was checked disjoint
+        // from `self.inner.ids` when it was registered via `Query::also_read`, so reading it
+        // here never aliases whatever `Q::fetch` hands out for this row.
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.inner.ids, columns: &self.inner.columns, tick: self.inner.world.tick() };
+        let item = unsafe { Q::fetch(self.inner.world, &ctx, row, &mut cursor) };
+        let id = self.inner.world.registry().id_of::<C>().expect("checked in Result::with_read");
+        // SAFETY: `table` is borrowed from `self.inner.world` for `'w`, same as above.
+        let extra = unsafe { (*table).column(id) }.and_then(|column| column.get(row)).map(|ptr| unsafe { &*(ptr as *const C) });
+        Some((item, extra))
+    }
+}
+
+/// The iterator produced by `Result::zip_slice`.
+pub struct ZipSlice<'w, Q: QueryData, F> {
+    inner: Result<'w, Q>,
+    per_table: F,
+}
+
+impl<'w, 'a, Q: QueryData, T: 'a, F> Iterator for ZipSlice<'w, Q, F>
+where
+    F: Fn(TableId) -> &'a [T],
+{
+    type Item = (Q::Item<'w>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (archetype, table, row) = self.inner.advance()?;
+        // SAFETY: same as `Result::next` — `table` is borrowed from `self.inner.world` for
+        // `'w`, and each row is visited exactly once across the whole iteration.
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.inner.ids, columns: &self.inner.columns, tick: self.inner.world.tick() };
+        let item = unsafe { Q::fetch(self.inner.world, &ctx, row, &mut cursor) };
+        let slice = (self.per_table)(archetype);
+        let value = slice.get(row).unwrap_or_else(|| panic!("zip_slice: per_table slice has {} rows, row {row} is out of bounds", slice.len()));
+        Some((item, value))
+    }
+}
+
+/// The iterator produced by `Result::pairs`.
+pub struct Pairs<'w, Q: QueryData> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    coords: Vec<(ArchetypeId, *mut Table, usize)>,
+    /// `ids`' columns per table visited so far, resolved once per distinct table rather than
+    /// once per pair — a table can appear in many pairs as `i` sweeps across `coords`.
+    columns: HashMap<ArchetypeId, Vec<*mut Column>>,
+    i: usize,
+    j: usize,
+    _marker: PhantomData<Q>,
+}
+
+impl<'w, Q: QueryData> Pairs<'w, Q> {
+    fn columns_for(&mut self, archetype: ArchetypeId, table: *mut Table) -> *const [*mut Column] {
+        self.columns.entry(archetype).or_insert_with(|| resolve_columns(table, &self.ids)).as_slice()
+    }
+}
+
+impl<'w, Q: QueryData> Iterator for Pairs<'w, Q> {
+    type Item = (Q::Item<'w>, Q::Item<'w>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.i < self.coords.len() {
+            if self.j >= self.coords.len() {
+                self.i += 1;
+                self.j = self.i + 1;
+                continue;
+            }
+            let (a_archetype, a_table, a_row) = self.coords[self.i];
+            let (b_archetype, b_table, b_row) = self.coords[self.j];
+            self.j += 1;
+
+            // SAFETY: `columns_for` only ever inserts and never removes entries, so this
+            // pointer stays valid for the rest of `self`'s lifetime even once the borrow
+            // used to compute it ends.
+            let a_columns = unsafe { &*self.columns_for(a_archetype, a_table) };
+            let b_columns = unsafe { &*self.columns_for(b_archetype, b_table) };
+
+            let (mut a_cursor, mut b_cursor) = (0, 0);
+            let tick = self.world.tick();
+            let a_ctx = FetchContext { table: a_table, archetype: a_archetype, ids: &self.ids, columns: a_columns, tick };
+            let b_ctx = FetchContext { table: b_table, archetype: b_archetype, ids: &self.ids, columns: b_columns, tick };
+            // SAFETY: `coords` holds each matched `(archetype, table, row)` exactly once, so
+            // `i != j` here means `(a_table, a_row)` and `(b_table, b_row)` are always distinct
+            // rows — either different tables entirely, or the same table at different rows,
+            // which never overlap in memory. Handing out two live `Q::Item`s from disjoint rows
+            // at once is sound the same way `World::get_many_mut`'s disjointness check is.
+            let a = unsafe { Q::fetch(self.world, &a_ctx, a_row, &mut a_cursor) };
+            let b = unsafe { Q::fetch(self.world, &b_ctx, b_row, &mut b_cursor) };
+            return Some((a, b));
+        }
+        None
+    }
+}
+
+/// The iterator produced by `Result::with_entities`.
+pub struct WithEntities<'w, Q: QueryData> {
+    inner: Result<'w, Q>,
+}
+
+impl<'w, Q: QueryData> Iterator for WithEntities<'w, Q> {
+    type Item = (Entity, Q::Item<'w>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (archetype, table, row) = self.inner.advance()?;
+        // SAFETY: see the safety comment on `Result::next` below.
+        let entity = unsafe { (*table).entity(row) };
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.inner.ids, columns: &self.inner.columns, tick: self.inner.world.tick() };
+        let item = unsafe { Q::fetch(self.inner.world, &ctx, row, &mut cursor) };
+        Some((entity, item))
+    }
+}
+
+impl<'w, 'q, C: Component + Clone> Result<'w, &'q C> {
+    /// Collects every match into a `Vec` of owned copies rather than borrowed references.
+    ///
+    /// Handy for short-lived snapshots (e.g. handing data to a UI or network layer) where
+    /// holding the query's `World` borrow open for as long as the borrowed items live isn't
+    /// worth it.
+    pub fn collect_owned(self) -> Vec<C> {
+        self.cloned().collect()
+    }
+}
+
+impl<'w, C: Component> Result<'w, &C> {
+    /// Scans every match tracking whichever has the highest `key_fn(component)` seen so far,
+    /// without collecting into a `Vec` first — e.g. finding the entity with the most `Health`
+    /// across every archetype that has one. Ties keep whichever entity was seen first.
+    ///
+    /// Faster and clearer than `iter().max_by_key(...)` for this case: that would need `K` to
+    /// be an owned, comparable snapshot of each row (there's no way to keep a borrow of the
+    /// winning row alive across `Iterator::max_by_key`'s internal comparisons), while this
+    /// hands back the actual `&C` the winning row still owns.
+    pub fn max_by_component<K: Ord>(self, mut key_fn: impl FnMut(&C) -> K) -> Option<(Entity, &'w C)> {
+        let mut best: Option<(Entity, &'w C, K)> = None;
+        for (entity, component) in self.with_entities() {
+            let key = key_fn(component);
+            if best.as_ref().is_none_or(|(_, _, best_key)| key > *best_key) {
+                best = Some((entity, component, key));
+            }
+        }
+        best.map(|(entity, component, _)| (entity, component))
+    }
+
+    /// Buckets every matched entity by `key_fn(component)` — e.g. spatial hashing entities
+    /// into grid cells, or batching renderables by material. Unlike archetype grouping, the
+    /// key is a runtime value read out of the component rather than a static shape, so this
+    /// necessarily visits every row and collects into a `HashMap` rather than returning a
+    /// view over existing storage.
+    pub fn group_by<K: Hash + Eq>(self, mut key_fn: impl FnMut(&C) -> K) -> HashMap<K, Vec<Entity>> {
+        let mut groups: HashMap<K, Vec<Entity>> = HashMap::new();
+        for (entity, component) in self.with_entities() {
+            groups.entry(key_fn(component)).or_default().push(entity);
+        }
+        groups
+    }
+}
+
+impl<'w, C: Component> Result<'w, &mut C> {
+    /// Overwrites every matched row's `C` in row order via `Table::fill_column`, table by
+    /// table, instead of assigning through `iter_mut()` one row at a time — e.g. recomputing
+    /// a whole `Visible` column for the frame. Like `for_each_chunk`, this does not skip rows
+    /// disabled via `World::disable_component`.
+    ///
+    /// # Panics
+    /// Panics if `values` doesn't yield exactly as many items as this query matches.
+    pub fn overwrite_column(mut self, mut values: impl Iterator<Item = C>) {
+        let id = self.ids[0];
+        while let Some((_, table)) = self.current_table() {
+            // SAFETY: `table` is borrowed from `self.world` for `'w`, and `id` is this
+            // query's own component id for `C` (`Q = &mut C` is how it was resolved), so `C`
+            // matches the column's actual type.
+            let len = unsafe { (*table).len() };
+            if self.row >= len {
+                self.archetype_index += 1;
+                self.row = 0;
+                continue;
+            }
+            unsafe { (*table).fill_column::<C>(id, values.by_ref().take(len)) }.unwrap_or_else(|err| panic!("Result::overwrite_column: {err}"));
+            self.row = 0;
+            self.archetype_index += 1;
+        }
+        assert!(values.next().is_none(), "Result::overwrite_column: too many values for the matched rows");
+    }
+}
+
+impl<'w, Q: QueryData> Result<'w, Q> {
+    /// Skips rows failing `predicate` as they're produced, rather than collecting into a
+    /// `Vec` first and filtering that.
+    pub fn filter_view<F>(self, predicate: F) -> FilterView<'w, Q, F>
+    where
+        F: FnMut(&Q::Item<'w>) -> bool,
+    {
+        FilterView { inner: self, predicate }
+    }
+
+    /// Remaining rows in each matched table not yet yielded, from `self.archetype_index`
+    /// onward. Shared by `any`/`is_empty`/`count` so none of them need to visit a row.
+    fn remaining_table_lens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.archetypes.iter().enumerate().skip(self.archetype_index).map(|(i, &archetype)| {
+            let len = self.world.archetypes().table(archetype).len();
+            if i == self.archetype_index {
+                len.saturating_sub(self.row)
+            } else {
+                len
+            }
+        })
+    }
+
+    /// Whether the query matches at least one entity, without building the row iterator.
+    /// Short-circuits on the first non-empty matched table.
+    pub fn any(self) -> bool {
+        self.remaining_table_lens().any(|len| len > 0)
+    }
+
+    /// Whether the query matches no entities. Short-circuits on the first non-empty
+    /// matched table, same as `any`.
+    pub fn is_empty(self) -> bool {
+        !self.any()
+    }
+
+    /// Total number of matching rows, summed from table lengths in O(tables) rather than
+    /// iterating every row.
+    pub fn count(self) -> usize {
+        self.remaining_table_lens().sum()
+    }
+}
+
+impl<'w, Q: Sliceable> Result<'w, Q> {
+    /// Hands `f` contiguous chunks of up to `chunk_size` rows at a time — parallel slices for
+    /// a multi-component `Q` — instead of one item per call. Numeric-heavy systems that want
+    /// fixed-size blocks (good for prefetch and SIMD) rather than row-by-row iteration should
+    /// reach for this over `iter()`. A chunk never spans two tables, so a table whose length
+    /// isn't a multiple of `chunk_size` ends in one shorter chunk.
+    ///
+    /// Unlike `iter()`, this does not skip rows disabled via `World::disable_component`, or
+    /// rows for an entity `World::queue_despawn`d but not yet actually removed — either one in
+    /// the middle of a chunk would break the contiguous-slice contract every caller of this
+    /// method relies on. A system built on `for_each_chunk` that also uses disabling or
+    /// `queue_despawn` should check `Column::is_disabled` itself, or avoid combining the two.
+    pub fn for_each_chunk(mut self, chunk_size: usize, mut f: impl FnMut(&'w [Entity], Q::Slice<'w>)) {
+        assert!(chunk_size > 0, "chunk_size must be nonzero");
+        while let Some((_, table)) = self.current_table() {
+            // SAFETY: see the safety comment on `Iterator::next` below.
+            let len = unsafe { (*table).len() };
+            if self.row >= len {
+                self.archetype_index += 1;
+                self.row = 0;
+                continue;
+            }
+            let start = self.row;
+            let end = (start + chunk_size).min(len);
+            self.row = end;
+
+            // SAFETY: `table` is borrowed from `self.world` for `'w` (see `Iterator::next`),
+            // and `[start, end)` is a run of occupied rows this `Result` hasn't yielded yet —
+            // no earlier chunk from this call overlaps it, and chunks partition the table.
+            let entities: &'w [Entity] = unsafe { &(*table).entities()[start..end] };
+            let mut cursor = 0;
+            let slice = unsafe { Q::slice(table, &self.ids, &mut cursor, start, end - start) };
+            f(entities, slice);
+        }
+    }
+}
+
+/// The iterator produced by `Result::filter_view`.
+pub struct FilterView<'w, Q: QueryData, F> {
+    inner: Result<'w, Q>,
+    predicate: F,
+}
+
+impl<'w, Q: QueryData, F> Iterator for FilterView<'w, Q, F>
+where
+    F: FnMut(&Q::Item<'w>) -> bool,
+{
+    type Item = Q::Item<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.inner.next()?;
+            if (self.predicate)(&item) {
+                return Some(item);
+            }
+        }
+    }
+}
+
+impl<'w, Q: QueryData> Iterator for Result<'w, Q> {
+    type Item = Q::Item<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (archetype, table, row) = self.advance()?;
+        // SAFETY: `table` is borrowed from `self.world` for `'w`; `Query` holds `&'w mut
+        // World` for the query's whole lifetime, so no other access to it can occur
+        // concurrently, and each row is visited exactly once across the whole iteration.
+        let mut cursor = 0;
+        let ctx = FetchContext { table, archetype, ids: &self.ids, columns: &self.columns, tick: self.world.tick() };
+        Some(unsafe { Q::fetch(self.world, &ctx, row, &mut cursor) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::entity::Entity;
+    use crate::ecs::query::Query;
+    use crate::ecs::world::World;
+    use rusty_engine_macros::Component;
+    use std::collections::HashMap;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Position(f32, f32);
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[test]
+    fn collect_owned_copies_values_out() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 2.0));
+        world.spawn(Position(3.0, 4.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let mut owned: Vec<Position> = query.iter().collect_owned();
+        owned.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(owned, vec![Position(1.0, 2.0), Position(3.0, 4.0)]);
+    }
+
+    #[test]
+    fn filter_view_skips_rows_failing_predicate() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(5.0, 0.0));
+        world.spawn(Position(9.0, 0.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let count = query.iter().filter_view(|p: &&Position| p.0 > 3.0).count();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn count_matches_full_iteration_length() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+        world.spawn(Position(3.0, 0.0));
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let from_iteration = query.iter().collect::<Vec<_>>().len();
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        assert_eq!(query.iter().count(), from_iteration);
+    }
+
+    #[test]
+    fn any_and_is_empty_reflect_whether_the_query_matched() {
+        let mut world = World::new();
+
+        let mut empty_query: Query<&Position> = Query::new(&mut world);
+        assert!(!empty_query.iter().any());
+        let mut empty_query: Query<&Position> = Query::new(&mut world);
+        assert!(empty_query.iter().is_empty());
+
+        world.spawn(Position(1.0, 0.0));
+
+        let mut non_empty_query: Query<&Position> = Query::new(&mut world);
+        assert!(non_empty_query.iter().any());
+        let mut non_empty_query: Query<&Position> = Query::new(&mut world);
+        assert!(!non_empty_query.iter().is_empty());
+    }
+
+    #[test]
+    fn pairs_yields_every_unordered_pair_exactly_once_and_never_self_pairs() {
+        use crate::ecs::entity::Entity;
+        use std::collections::HashSet;
+
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+        world.spawn(Position(3.0, 0.0));
+        world.spawn(Position(4.0, 0.0));
+
+        let mut query: Query<crate::ecs::query::EntityLocation> = Query::new(&mut world);
+        let seen: Vec<(Entity, Entity)> = query.iter().pairs().map(|((a, _, _), (b, _, _))| (a, b)).collect();
+
+        assert_eq!(seen.len(), 6);
+        for &(a, b) in &seen {
+            assert_ne!(a, b);
+        }
+        let unordered: HashSet<(Entity, Entity)> = seen
+            .iter()
+            .map(|&(a, b)| if a < b { (a, b) } else { (b, a) })
+            .collect();
+        assert_eq!(unordered.len(), 6);
+    }
+
+    #[test]
+    fn pairs_of_mutable_items_can_mutate_both_sides_at_once() {
+        let mut world = World::new();
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        for (a, b) in query.iter().pairs() {
+            a.0 += 1.0;
+            b.0 += 1.0;
+        }
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let total: f32 = query.iter().map(|p| p.0).sum();
+        assert_eq!(total, 6.0); // 3 pairs, each side bumped once per pair it's in
+    }
+
+    #[test]
+    fn for_each_chunk_sums_to_the_same_total_as_per_row_iteration() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Velocity(f32);
+
+        let mut world = World::new();
+        for i in 0..10 {
+            world.spawn((Position(i as f32, 0.0), Velocity(1.0)));
+        }
+        // A table whose length (10) isn't a multiple of the chunk size (3), so the last
+        // chunk this test sees is shorter than the rest.
+        let mut query: Query<(&Position, &Velocity)> = Query::new(&mut world);
+        let from_iteration: f32 = query.iter().map(|(p, v)| p.0 * v.0).sum();
+
+        let mut query: Query<(&Position, &Velocity)> = Query::new(&mut world);
+        let mut from_chunks = 0.0;
+        let mut chunk_lens = Vec::new();
+        query.iter().for_each_chunk(3, |entities, (positions, velocities)| {
+            assert_eq!(entities.len(), positions.len());
+            assert_eq!(positions.len(), velocities.len());
+            chunk_lens.push(positions.len());
+            for (p, v) in positions.iter().zip(velocities) {
+                from_chunks += p.0 * v.0;
+            }
+        });
+
+        assert_eq!(from_chunks, from_iteration);
+        assert_eq!(chunk_lens, vec![3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn for_each_chunk_can_mutate_through_its_slice() {
+        let mut world = World::new();
+        for i in 0..5 {
+            world.spawn(Position(i as f32, 0.0));
+        }
+
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        query.iter().for_each_chunk(2, |_, positions| {
+            for p in positions {
+                p.0 *= 10.0;
+            }
+        });
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let mut values: Vec<f32> = query.iter().map(|p| p.0).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![0.0, 10.0, 20.0, 30.0, 40.0]);
+    }
+
+    #[test]
+    fn disabled_component_is_excluded_from_queries_until_re_enabled() {
+        let mut world = World::new();
+        let entity = world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+
+        world.disable_component::<Position>(entity);
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let mut remaining: Vec<Position> = query.iter().copied().collect();
+        assert_eq!(remaining, vec![Position(2.0, 0.0)]);
+
+        world.enable_component::<Position>(entity);
+        let mut query: Query<&Position> = Query::new(&mut world);
+        remaining = query.iter().copied().collect();
+        remaining.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(remaining, vec![Position(1.0, 0.0), Position(2.0, 0.0)]);
+    }
+
+    #[test]
+    fn for_each_drops_the_borrow_in_time_for_a_spawn_right_after() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+
+        let mut total = 0.0;
+        let mut query: Query<&Position> = Query::new(&mut world);
+        query.iter().for_each(|p| total += p.0);
+        // `query` isn't touched again, so this wouldn't need `for_each` specifically to
+        // compile — but it's exactly the pattern the method exists to make obviously legal.
+        world.spawn(Position(3.0, 0.0));
+
+        assert_eq!(total, 3.0);
+        assert_eq!(world.len(), 3);
+    }
+
+    #[test]
+    fn zip_slice_pairs_each_row_with_the_matching_external_slice_element() {
+        let mut world = World::new();
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+
+        // Every entity here lives in the same single-column archetype, so the same slice
+        // answers `per_table` regardless of which `TableId` it's asked about; a query
+        // spanning several tables would instead look its `TableId` up in a `HashMap`.
+        let forces = [1.0f32, 2.0, 3.0];
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        for (position, force) in query.iter().zip_slice(|_table| &forces[..]) {
+            position.0 += force;
+        }
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let mut values: Vec<f32> = query.iter().map(|p| p.0).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn also_read_lets_a_mut_query_read_a_disjoint_component_per_row() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Velocity(f32);
+
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Mass(f32);
+
+        let mut world = World::new();
+        world.spawn((Velocity(1.0), Mass(2.0)));
+        world.spawn((Velocity(3.0), Mass(4.0)));
+
+        let mut query: Query<&mut Velocity> = Query::new(&mut world).also_read::<Mass>();
+        for (velocity, mass) in query.iter().with_read::<Mass>() {
+            velocity.0 *= mass.unwrap().0;
+        }
+
+        let mut query: Query<&Velocity> = Query::new(&mut world);
+        let mut values: Vec<f32> = query.iter().map(|v| v.0).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![2.0, 12.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already part of this query's own components")]
+    fn also_read_panics_when_the_component_is_already_in_the_query() {
+        #[derive(Component)]
+        struct Velocity;
+
+        let mut world = World::new();
+        world.spawn(Velocity);
+        let _query: Query<&mut Velocity> = Query::new(&mut world).also_read::<Velocity>();
+    }
+
+    #[test]
+    fn max_by_component_finds_the_highest_across_archetypes() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Health(i32);
+
+        let mut world = World::new();
+        world.spawn(Health(10));
+        world.spawn((Health(50), Position(0.0, 0.0))); // different archetype, still matched
+        let expected = world.spawn(Health(30));
+        world.spawn(Health(20));
+
+        let mut query: Query<&Health> = Query::new(&mut world);
+        let (entity, health) = query.iter().max_by_component(|h| h.0).unwrap();
+
+        // The highest overall is 50 on the (Health, Position) archetype, not `expected`'s 30 —
+        // named to make sure this doesn't just happen to match by coincidence.
+        assert_eq!(health.0, 50);
+        assert_ne!(entity, expected);
+    }
+
+    #[test]
+    fn max_by_component_returns_none_for_an_empty_query() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Health(i32);
+
+        let mut world = World::new();
+        let mut query: Query<&Health> = Query::new(&mut world);
+        assert!(query.iter().max_by_component(|h| h.0).is_none());
+    }
+
+    #[test]
+    fn group_by_buckets_entities_by_a_component_derived_key_across_archetypes() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Cell(i32);
+
+        let mut world = World::new();
+        let a = world.spawn(Cell(0));
+        let b = world.spawn((Cell(0), Position(0.0, 0.0))); // different archetype, still matched
+        let c = world.spawn(Cell(1));
+        let d = world.spawn(Cell(2));
+
+        let mut query: Query<&Cell> = Query::new(&mut world);
+        let groups = query.iter().group_by(|cell| cell.0);
+
+        assert_eq!(groups.len(), 3);
+        let mut zero = groups[&0].clone();
+        zero.sort();
+        let mut expected_zero = [a, b];
+        expected_zero.sort();
+        assert_eq!(zero, expected_zero);
+        assert_eq!(groups[&1], vec![c]);
+        assert_eq!(groups[&2], vec![d]);
+    }
+
+    #[test]
+    fn for_each_with_entity_syncs_an_external_map_without_entity_in_the_query() {
+        #[derive(Component, Debug, PartialEq, Clone, Copy)]
+        struct Handle(u32);
+
+        let mut world = World::new();
+        let a = world.spawn(Handle(10));
+        let b = world.spawn((Handle(20), Position(0.0, 0.0))); // different archetype, still matched
+        let c = world.spawn(Handle(30));
+
+        let mut external: HashMap<Entity, u32> = HashMap::new();
+        let mut query: Query<&Handle> = Query::new(&mut world);
+        query.iter().for_each_with_entity(|entity, handle| {
+            external.insert(entity, handle.0);
+        });
+
+        assert_eq!(external.len(), 3);
+        assert_eq!(external[&a], 10);
+        assert_eq!(external[&b], 20);
+        assert_eq!(external[&c], 30);
+    }
+
+    #[test]
+    fn overwrite_column_replaces_every_matched_row_and_drops_old_values_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Component)]
+        struct Counted(u32);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut world = World::new();
+        world.spawn(Counted(1));
+        world.spawn((Counted(2), Position(0.0, 0.0))); // a second archetype
+        world.spawn(Counted(3));
+        DROPS.store(0, Ordering::SeqCst);
+
+        let mut query: Query<&mut Counted> = Query::new(&mut world);
+        let total = query.iter().count();
+        let mut query: Query<&mut Counted> = Query::new(&mut world);
+        query.iter().overwrite_column((0..total as u32).map(Counted));
+
+        assert_eq!(DROPS.load(Ordering::SeqCst), total);
+        let mut query: Query<&Counted> = Query::new(&mut world);
+        let mut values: Vec<u32> = query.iter().map(|c| c.0).collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..total as u32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "Result::overwrite_column")]
+    fn overwrite_column_panics_on_a_length_mismatch() {
+        let mut world = World::new();
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        query.iter().overwrite_column(std::iter::once(Position(1.0, 1.0)));
+    }
+
+    #[test]
+    fn zero_sized_tag_components_still_yield_correct_query_counts() {
+        let mut world = World::new();
+        for _ in 0..5 {
+            world.spawn((Position(0.0, 0.0), Enemy));
+        }
+        world.spawn(Position(0.0, 0.0));
+
+        let mut tagged: Query<(&Position, &Enemy)> = Query::new(&mut world);
+        assert_eq!(tagged.iter().count(), 5);
+
+        let mut all: Query<&Position> = Query::new(&mut world);
+        assert_eq!(all.iter().count(), 6);
+    }
+}
@@ -0,0 +1,133 @@
+//! `RollbackBuffer`: a ring of recent `World` snapshots for netcode-style rewind/replay.
+
+use crate::ecs::storage::column::NotCloneable;
+use crate::ecs::world::World;
+use std::collections::VecDeque;
+
+/// Holds the last `capacity` `World` snapshots, keyed by an opaque `frame` number the
+/// caller assigns (a tick, a network frame counter, whatever it already tracks).
+///
+/// Restoring a frame only reproduces the `World` state saved at `push` time — it doesn't
+/// re-run any systems. Fast-forwarding a restored frame back up to the present by
+/// re-simulating stored inputs is left to the caller: this crate has no input log or
+/// schedule-replay mechanism to draw from yet, so `RollbackBuffer` only does the "save
+/// and restore a snapshot" half of rollback netcode.
+pub struct RollbackBuffer {
+    capacity: usize,
+    snapshots: VecDeque<(u64, World)>,
+}
+
+impl RollbackBuffer {
+    /// Panics if `capacity` is zero — a buffer that can't hold anything can't roll back
+    /// to anything either.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "RollbackBuffer capacity must be at least 1");
+        Self { capacity, snapshots: VecDeque::with_capacity(capacity) }
+    }
+
+    /// How many snapshots this buffer can hold at once.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// How many snapshots are currently stored.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Deep-copies `world` via `World::try_clone` and records it under `frame`, evicting
+    /// the oldest snapshot first if the buffer is already at `capacity`. Fails (leaving
+    /// the buffer unchanged) if `world` has a component that isn't cloneable — see
+    /// `World::try_clone`.
+    pub fn push(&mut self, frame: u64, world: &World) -> Result<(), NotCloneable> {
+        let snapshot = world.try_clone()?;
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((frame, snapshot));
+        Ok(())
+    }
+
+    /// The snapshot saved for `frame`, if it's still in the buffer.
+    pub fn get(&self, frame: u64) -> Option<&World> {
+        self.snapshots.iter().find(|(saved, _)| *saved == frame).map(|(_, world)| world)
+    }
+
+    /// The most recently pushed `(frame, World)` pair, if any.
+    pub fn latest(&self) -> Option<(u64, &World)> {
+        self.snapshots.back().map(|(frame, world)| (*frame, world))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Health(i32);
+
+    #[test]
+    fn rolling_back_restores_a_saved_frames_state() {
+        let mut world = World::new();
+        world.registry_mut().register_cloneable::<Health>();
+        let entity = world.spawn(Health(100));
+
+        let mut buffer = RollbackBuffer::new(3);
+        buffer.push(0, &world).unwrap();
+
+        world.entity_mut(entity).unwrap().get_mut::<Health>().unwrap().0 = 50;
+        buffer.push(1, &world).unwrap();
+
+        world.entity_mut(entity).unwrap().get_mut::<Health>().unwrap().0 = 10;
+        buffer.push(2, &world).unwrap();
+
+        world.entity_mut(entity).unwrap().get_mut::<Health>().unwrap().0 = 0;
+
+        assert!(world.rollback_to(&buffer, 0));
+        assert_eq!(world.entity_ref(entity).unwrap().get::<Health>(), Some(&Health(100)));
+    }
+
+    #[test]
+    fn rolling_back_to_a_frame_that_aged_out_leaves_the_world_untouched() {
+        let mut world = World::new();
+        world.registry_mut().register_cloneable::<Health>();
+        let entity = world.spawn(Health(100));
+
+        let mut buffer = RollbackBuffer::new(1);
+        buffer.push(0, &world).unwrap();
+        buffer.push(1, &world).unwrap(); // evicts frame 0
+
+        world.entity_mut(entity).unwrap().get_mut::<Health>().unwrap().0 = 5;
+        assert!(!world.rollback_to(&buffer, 0));
+        assert_eq!(world.entity_ref(entity).unwrap().get::<Health>(), Some(&Health(5)));
+    }
+
+    #[test]
+    fn buffer_evicts_the_oldest_frame_once_full() {
+        let world = World::new();
+        let mut buffer = RollbackBuffer::new(2);
+        buffer.push(0, &world).unwrap();
+        buffer.push(1, &world).unwrap();
+        buffer.push(2, &world).unwrap();
+
+        assert!(buffer.get(0).is_none());
+        assert!(buffer.get(1).is_some());
+        assert!(buffer.get(2).is_some());
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn push_rejects_a_non_cloneable_component_and_leaves_the_buffer_unchanged() {
+        let mut world = World::new();
+        world.spawn(Health(1)); // registered plain, not via register_cloneable
+
+        let mut buffer = RollbackBuffer::new(2);
+        assert!(buffer.push(0, &world).is_err());
+        assert_eq!(buffer.len(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+//! `SequenceGraph`: phases declare dependencies on other phases by name instead of relying on
+//! `Schedule`'s strict list order, so two phases that don't depend on each other — and don't
+//! touch overlapping components/uniques — can run concurrently instead of one after another.
+
+use crate::ecs::schedule::phase::{scheduling_conflicts, Phase};
+use crate::ecs::world::World;
+
+struct Node {
+    phase: Phase,
+    depends_on: Vec<&'static str>,
+}
+
+/// A raw pointer safe to hand to `std::thread::scope`'s worker closures, exactly the same
+/// justification as `Phase::run_parallel_group`'s `SendPtr`: each thread here only ever
+/// dereferences the one `Node` its `index` names, and `phases_conflict` has already ruled out
+/// any pair sharing this wave from touching overlapping `World` state.
+struct SendPtr<T>(*mut T);
+
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SendPtr<T> {}
+
+unsafe impl<T> Send for SendPtr<T> {}
+
+/// Whether two phases, taken as wholes, could safely run at the same time — every system in
+/// `a` checked against every system in `b` with the same `scheduling_conflicts` rule
+/// `Phase::run_parallel_group` uses for systems within one phase.
+fn phases_conflict(a: &Phase, b: &Phase) -> bool {
+    let (a_accesses, b_accesses) = (a.accesses(), b.accesses());
+    a_accesses.iter().any(|x| b_accesses.iter().any(|y| scheduling_conflicts(x, y)))
+}
+
+/// A `Schedule` alternative for phases with dependencies instead of a fixed run order: a
+/// phase runs only once every phase it `depends_on` has finished, and phases that become
+/// ready at the same time run concurrently (one OS thread per phase) as long as none of them
+/// conflict on component or unique access — otherwise they fall back to running one after
+/// another, the same way `Phase::run_parallel_group` serializes conflicting systems.
+#[derive(Default)]
+pub struct SequenceGraph {
+    nodes: Vec<Node>,
+}
+
+impl SequenceGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `phase`, which won't start until every phase named in `depends_on` has finished.
+    /// `depends_on` may be empty for a phase with no prerequisites.
+    pub fn add_phase(&mut self, phase: Phase, depends_on: &[&'static str]) -> &mut Self {
+        self.nodes.push(Node {
+            phase,
+            depends_on: depends_on.to_vec(),
+        });
+        self
+    }
+
+    pub fn phase_mut(&mut self, name: &str) -> Option<&mut Phase> {
+        self.nodes.iter_mut().find(|node| node.phase.name() == name).map(|node| &mut node.phase)
+    }
+
+    /// Runs every phase against `world`, one dependency-satisfied wave at a time: each wave's
+    /// mutually non-conflicting phases are grouped and dispatched across real OS threads via
+    /// `std::thread::scope`, joining before the next wave starts.
+    ///
+    /// # Panics
+    /// If `depends_on` names a phase never added to this graph, or the dependency graph has a
+    /// cycle — both would otherwise leave phases permanently unready, so this panics instead
+    /// of looping forever.
+    pub fn run(&mut self, world: &mut World) {
+        let names: Vec<&'static str> = self.nodes.iter().map(|node| node.phase.name()).collect();
+        for node in &self.nodes {
+            for dep in &node.depends_on {
+                assert!(names.contains(dep), "SequenceGraph: phase {:?} depends on unregistered phase {:?}", node.phase.name(), dep);
+            }
+        }
+
+        let mut remaining: Vec<usize> = (0..self.nodes.len()).collect();
+        let mut done: Vec<&'static str> = Vec::with_capacity(self.nodes.len());
+
+        while !remaining.is_empty() {
+            let (ready, waiting): (Vec<usize>, Vec<usize>) =
+                remaining.iter().copied().partition(|&index| self.nodes[index].depends_on.iter().all(|dep| done.contains(dep)));
+            assert!(
+                !ready.is_empty(),
+                "SequenceGraph: dependency cycle among {:?}",
+                waiting.iter().map(|&index| self.nodes[index].phase.name()).collect::<Vec<_>>()
+            );
+
+            // Greedily group this wave's ready phases into subsets that are pairwise
+            // non-conflicting, the same way `Phase::build_waves` groups systems within one
+            // phase — everything in a group runs concurrently; groups themselves run one
+            // after another.
+            let mut groups: Vec<Vec<usize>> = Vec::new();
+            for &index in &ready {
+                let group = groups
+                    .iter_mut()
+                    .find(|group| group.iter().all(|&other| !phases_conflict(&self.nodes[index].phase, &self.nodes[other].phase)));
+                match group {
+                    Some(group) => group.push(index),
+                    None => groups.push(vec![index]),
+                }
+            }
+
+            for group in groups {
+                if group.len() == 1 {
+                    self.nodes[group[0]].phase.run(world);
+                    continue;
+                }
+                let world_ptr = SendPtr(world as *mut World);
+                let nodes_ptr = SendPtr(self.nodes.as_mut_ptr());
+                std::thread::scope(|scope| {
+                    for &index in &group {
+                        scope.spawn(move || {
+                            // SAFETY: `phases_conflict` found no shared component/unique access
+                            // between any pair in `group`, and distinct `index`es never alias
+                            // the same `nodes` slot, so each thread here reaches a disjoint
+                            // phase and (per its systems' declared `Access`) a disjoint slice
+                            // of `world` — as long as those declarations are honest.
+                            //
+                            // Bind the whole `SendPtr` first (rather than reaching straight for
+                            // `.0`) so 2021-edition disjoint capture moves the wrapper itself
+                            // into this closure instead of just its raw-pointer field, which
+                            // wouldn't be `Send` on its own.
+                            let world_ptr = world_ptr;
+                            let nodes_ptr = nodes_ptr;
+                            let node = unsafe { &mut *nodes_ptr.0.add(index) };
+                            let world = unsafe { &mut *world_ptr.0 };
+                            node.phase.run(world);
+                        });
+                    }
+                });
+            }
+
+            for &index in &ready {
+                done.push(self.nodes[index].phase.name());
+            }
+            remaining = waiting;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::ComponentId;
+    use crate::ecs::schedule::phase::{Access, Mutability};
+    use std::sync::{Arc, Barrier, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn independent_phases_overlap_and_a_dependent_phase_waits() {
+        let barrier = Arc::new(Barrier::new(2));
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut graph = SequenceGraph::new();
+
+        let mut phase_a = Phase::new("a");
+        let a_barrier = barrier.clone();
+        let a_log = log.clone();
+        phase_a.add_system_with_access(
+            move |_: &mut World| {
+                a_barrier.wait();
+                a_log.lock().unwrap().push("a");
+            },
+            Access::Components(vec![(ComponentId(0), Mutability::Write)]),
+        );
+
+        let mut phase_b = Phase::new("b");
+        let b_barrier = barrier.clone();
+        let b_log = log.clone();
+        phase_b.add_system_with_access(
+            move |_: &mut World| {
+                b_barrier.wait();
+                b_log.lock().unwrap().push("b");
+            },
+            Access::Components(vec![(ComponentId(1), Mutability::Write)]),
+        );
+
+        let mut phase_c = Phase::new("c");
+        let c_log = log.clone();
+        phase_c.add_system(move |_: &mut World| c_log.lock().unwrap().push("c"));
+
+        graph.add_phase(phase_a, &[]);
+        graph.add_phase(phase_b, &[]);
+        graph.add_phase(phase_c, &["a"]);
+
+        // `a` and `b` share no declared access and have no dependency edge between them, so
+        // they should land in the same wave and run concurrently — proven by the barrier: on
+        // a sequential fallback the first to run would block on `wait()` forever, since the
+        // second never starts until the first returns.
+        let handle = std::thread::spawn(move || {
+            let mut world = World::new();
+            graph.run(&mut world);
+        });
+
+        for _ in 0..500 {
+            if handle.is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(handle.is_finished(), "independent phases sharing no access should overlap via the barrier, not deadlock");
+        handle.join().unwrap();
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[2], "c", "a dependent phase must run after its dependency finishes, not concurrently with it");
+    }
+
+    #[test]
+    #[should_panic(expected = "depends on unregistered phase")]
+    fn unknown_dependency_panics_instead_of_hanging() {
+        let mut graph = SequenceGraph::new();
+        graph.add_phase(Phase::new("a"), &["missing"]);
+
+        let mut world = World::new();
+        graph.run(&mut world);
+    }
+}
@@ -0,0 +1,92 @@
+//! Schedules: an ordered sequence of `Phase`s run against a `World` once per tick.
+
+mod graph;
+mod phase;
+mod sequence;
+
+pub use graph::SequenceGraph;
+pub use phase::{Access, ConflictError, ExclusiveOrder, Mutability, Phase, SystemAccessSummary, SystemId};
+pub use sequence::Sequence;
+
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::world::World;
+
+/// An ordered list of `Phase`s, run in sequence.
+#[derive(Default)]
+pub struct Schedule {
+    phases: Vec<Phase>,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_phase(&mut self, phase: Phase) -> &mut Self {
+        self.phases.push(phase);
+        self
+    }
+
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// How many phases this schedule has, for tooling that wants a schedule-wide summary
+    /// alongside each `Phase::system_count`/`system_access_summary`.
+    pub fn phase_count(&self) -> usize {
+        self.phases.len()
+    }
+
+    pub fn phase_mut(&mut self, name: &str) -> Option<&mut Phase> {
+        self.phases.iter_mut().find(|phase| phase.name() == name)
+    }
+
+    /// Removes `id` from `phase`, wherever it lives within that phase. Returns `false` if
+    /// `phase` doesn't exist or doesn't own `id`. See `Phase::remove_system` for what
+    /// removal does (and doesn't yet) clean up.
+    pub fn remove_system(&mut self, phase: &str, id: SystemId) -> bool {
+        match self.phase_mut(phase) {
+            Some(phase) => phase.remove_system(id),
+            None => false,
+        }
+    }
+
+    /// Swaps the system running under `id` in `phase` for `new_system`, in place. Returns
+    /// `false` if `phase` doesn't exist or doesn't own `id`.
+    pub fn replace_system<M>(&mut self, phase: &str, id: SystemId, new_system: impl IntoSystem<M, System: System<Out = ()>>) -> bool {
+        match self.phase_mut(phase) {
+            Some(phase) => phase.replace_system(id, new_system),
+            None => false,
+        }
+    }
+
+    /// Sets how many worker threads `phase`'s parallel group should run across — see
+    /// `Phase::set_threads`. Returns `false` if `phase` doesn't exist.
+    pub fn set_phase_threads(&mut self, phase: &str, threads: usize) -> bool {
+        match self.phase_mut(phase) {
+            Some(phase) => {
+                phase.set_threads(threads);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Validates every phase's parallel group up front, so a misconfigured system is
+    /// caught when it's added rather than discovered mid-run.
+    pub fn build(&self) -> Result<(), Vec<ConflictError>> {
+        let errors: Vec<ConflictError> = self.phases.iter().filter_map(|phase| phase.validate().err()).flatten().collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Runs every phase, in order, against `world`.
+    pub fn run(&mut self, world: &mut World) {
+        for phase in &mut self.phases {
+            phase.run(world);
+        }
+    }
+}
@@ -0,0 +1,691 @@
+//! A `Phase` groups the systems that run together at one point in a `Schedule`.
+
+use crate::ecs::component::{ComponentId, Registry};
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::world::World;
+use std::any::TypeId;
+
+/// Whether a system's declared access to a component is read-only or read-write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutability {
+    Read,
+    Write,
+}
+
+/// What a system in a `Phase`'s parallel group touches, for `Phase::validate` to reason
+/// about whether the group could ever actually run concurrently.
+///
+/// Plain closures over `&mut World` (the common case, added via `add_system`) can reach
+/// anything, so they default to `Exclusive` — safe but pessimistic. Declare `Components`
+/// via `add_system_with_access` for a system that only touches specific component types,
+/// so it validates cleanly alongside others.
+#[derive(Debug, Clone, Default)]
+pub enum Access {
+    /// Limited to reading/writing these components. Two `Components` accesses never
+    /// conflict here, even write-write on the same component — worst case they just don't
+    /// get to run at the same time and the phase serializes them like it already does.
+    Components(Vec<(ComponentId, Mutability)>),
+    /// Limited to reading/writing these `Send` uniques (see `unique::Unique`), reported via
+    /// `unique::UniqElement::access`/`UniqSet::access`. Unlike `Components`, an overlapping
+    /// read/write or write/write pair here IS a real conflict: a unique is one shared value
+    /// (not a whole component column split across many entities), so two systems racing on
+    /// it can't be waved off as "worst case they serialize" — `Phase::validate` reports it.
+    Uniques(Vec<(TypeId, Mutability)>),
+    /// Reaches the `World` widely enough (structural changes, unknown components) that it
+    /// can't safely share the parallel group with any other system.
+    #[default]
+    Exclusive,
+    /// Touches a non-`Send` unique (see `unique::NonSendUnique`). The parallel group isn't
+    /// guaranteed to run on the thread that owns the value, so this is rejected outright
+    /// rather than only when paired with another system.
+    NonSend,
+}
+
+/// A problem `Phase::validate` found in a parallel group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictError {
+    /// Two parallel systems where at least one needs exclusive world access, so they can
+    /// never actually run concurrently.
+    Exclusive { phase: &'static str, first: usize, second: usize },
+    /// A system touching a non-`Send` unique was placed in the parallel group instead of
+    /// as an exclusive (main-thread) system.
+    NonSendInParallel { phase: &'static str, index: usize },
+    /// Two parallel systems both declare `Access::Uniques` over the same unique type, with
+    /// at least one of them writing it.
+    UniqueConflict { phase: &'static str, first: usize, second: usize },
+}
+
+impl std::fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exclusive { phase, first, second } => write!(
+                f,
+                "phase {phase:?}: parallel systems {first} and {second} can't be parallelized (one needs exclusive world access)"
+            ),
+            Self::NonSendInParallel { phase, index } => write!(
+                f,
+                "phase {phase:?}: parallel system {index} touches a non-Send unique and must be added as exclusive instead"
+            ),
+            Self::UniqueConflict { phase, first, second } => write!(
+                f,
+                "phase {phase:?}: parallel systems {first} and {second} can't be parallelized (they share a unique, at least one mutably)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+/// A raw pointer that's safe to hand to `std::thread::scope`'s worker closures, because
+/// `Phase::run_parallel_group` only ever dereferences one per thread into a piece of the
+/// world/system list that `build_waves` has already established no other live thread
+/// touches at the same time.
+struct SendPtr<T>(*mut T);
+
+// Written by hand rather than derived: `#[derive(Clone, Copy)]` on a generic struct adds a
+// `T: Clone`/`T: Copy` bound even though `*mut T` is always `Copy` regardless of `T`, which
+// would wrongly stop this from being `Copy` for e.g. `SendPtr<World>`.
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SendPtr<T> {}
+
+unsafe impl<T> Send for SendPtr<T> {}
+
+/// Whether two systems' declared accesses must not be scheduled into the same wave —
+/// stricter than `Phase::validate`'s notion of a conflict (which tolerates component
+/// write/write overlap since every phase used to run sequentially regardless): here, any
+/// shared component or unique id at all rules out running them at the same time, since real
+/// threads are now actually racing on the same `World`.
+pub(crate) fn scheduling_conflicts(a: &Access, b: &Access) -> bool {
+    match (a, b) {
+        (Access::Exclusive, _) | (_, Access::Exclusive) => true,
+        (Access::NonSend, _) | (_, Access::NonSend) => true,
+        (Access::Components(a_ids), Access::Components(b_ids)) => {
+            a_ids.iter().any(|(id, _)| b_ids.iter().any(|(other, _)| id == other))
+        }
+        (Access::Uniques(a_ids), Access::Uniques(b_ids)) => {
+            a_ids.iter().any(|(id, _)| b_ids.iter().any(|(other, _)| id == other))
+        }
+        (Access::Components(_), Access::Uniques(_)) | (Access::Uniques(_), Access::Components(_)) => false,
+    }
+}
+
+/// Where an exclusive system runs relative to a phase's parallel group.
+///
+/// Exclusive systems need `&mut World` for the whole phase, so they can never run
+/// concurrently with the parallel group — only before or after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExclusiveOrder {
+    /// Runs before the parallel group. This is the default, matching the historical
+    /// "exclusive systems run first" behavior.
+    #[default]
+    PreParallel,
+    /// Runs after the parallel group, e.g. to apply commands or do a cleanup pass.
+    PostParallel,
+}
+
+/// Identifies one system within a `Phase`, returned by `add_system` (and its siblings) so a
+/// caller can later `remove_system`/`replace_system` it — e.g. hot-reloading gameplay code
+/// without tearing down and rebuilding the whole `Schedule`. Stable across other systems
+/// being added or removed; never reused within the `Phase` that issued it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(u64);
+
+/// One system's declared access, as reported by `Phase::system_access_summary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemAccessSummary {
+    pub id: SystemId,
+    pub exclusive: bool,
+    pub reads: Vec<&'static str>,
+    pub writes: Vec<&'static str>,
+}
+
+/// An ordered stage of a `Schedule`: exclusive systems pinned before and/or after a group
+/// of systems that (conceptually) run in parallel over the `World`.
+pub struct Phase {
+    name: &'static str,
+    pre_exclusive: Vec<(SystemId, Box<dyn System<Out = ()>>)>,
+    parallel: Vec<(SystemId, Box<dyn System<Out = ()>>, Access)>,
+    post_exclusive: Vec<(SystemId, Box<dyn System<Out = ()>>)>,
+    next_id: u64,
+    /// How many worker threads `run` should spread the parallel group across. `1` (the
+    /// default) is exactly the historical behavior: every parallel system runs in
+    /// declaration order on the calling thread. A serial phase (render submission) should
+    /// leave this at `1`; a massively parallel one (physics) can raise it via
+    /// `Schedule::set_phase_threads`/`set_threads`.
+    threads: usize,
+}
+
+impl Phase {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            pre_exclusive: Vec::new(),
+            parallel: Vec::new(),
+            post_exclusive: Vec::new(),
+            next_id: 0,
+            threads: 1,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// How many worker threads `run` will spread this phase's parallel group across.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Sets how many worker threads `run` should spread the parallel group across, clamped
+    /// to at least `1`. See the `threads` field doc for what this changes.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    fn alloc_id(&mut self) -> SystemId {
+        let id = SystemId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Adds a system to the phase's parallel group with `Access::Exclusive`, the safe
+    /// default for a plain `&mut World` closure. Use `add_system_with_access` to declare
+    /// narrower access so `validate` can confirm it parallelizes cleanly.
+    pub fn add_system<M>(&mut self, system: impl IntoSystem<M, System: System<Out = ()>>) -> SystemId {
+        self.add_system_with_access(system, Access::default())
+    }
+
+    /// Adds a system to the phase's parallel group, declaring exactly what it accesses.
+    pub fn add_system_with_access<M>(&mut self, system: impl IntoSystem<M, System: System<Out = ()>>, access: Access) -> SystemId {
+        let id = self.alloc_id();
+        self.parallel.push((id, Box::new(system.into_system()), access));
+        id
+    }
+
+    /// Adds an exclusive system, running before the parallel group by default.
+    pub fn add_exclusive<M>(&mut self, system: impl IntoSystem<M, System: System<Out = ()>>) -> SystemId {
+        self.add_exclusive_ordered(system, ExclusiveOrder::default())
+    }
+
+    /// Adds an exclusive system, running before or after the parallel group per `order`.
+    pub fn add_exclusive_ordered<M>(&mut self, system: impl IntoSystem<M, System: System<Out = ()>>, order: ExclusiveOrder) -> SystemId {
+        let id = self.alloc_id();
+        let boxed: Box<dyn System<Out = ()>> = Box::new(system.into_system());
+        match order {
+            ExclusiveOrder::PreParallel => self.pre_exclusive.push((id, boxed)),
+            ExclusiveOrder::PostParallel => self.post_exclusive.push((id, boxed)),
+        }
+        id
+    }
+
+    /// Removes the system `id` names, wherever it lives (pre-exclusive, parallel, or
+    /// post-exclusive), and returns whether one was actually found.
+    ///
+    /// This crate doesn't have inter-system ordering constraints yet (only a system's
+    /// pre/post-exclusive-vs-parallel placement, which `id` alone identifies), so there are
+    /// no ordering edges to drop here — once ordering constraints exist, pruning any
+    /// referencing `id` belongs in this method. `validate`/`run` always read the phase's
+    /// current system lists, so removing an entry here is already a full re-plan; there's
+    /// no separate cached plan to invalidate.
+    pub fn remove_system(&mut self, id: SystemId) -> bool {
+        if let Some(pos) = self.pre_exclusive.iter().position(|(sid, _)| *sid == id) {
+            self.pre_exclusive.remove(pos);
+            return true;
+        }
+        if let Some(pos) = self.parallel.iter().position(|(sid, _, _)| *sid == id) {
+            self.parallel.remove(pos);
+            return true;
+        }
+        if let Some(pos) = self.post_exclusive.iter().position(|(sid, _)| *sid == id) {
+            self.post_exclusive.remove(pos);
+            return true;
+        }
+        false
+    }
+
+    /// Swaps the system running under `id` for `new_system`, keeping its position and (for
+    /// a parallel-group system) its declared `Access` unchanged. Returns whether `id` was
+    /// found. Use this over `remove_system` + `add_system` when hot-reloading a system that
+    /// should keep its place in the phase.
+    pub fn replace_system<M>(&mut self, id: SystemId, new_system: impl IntoSystem<M, System: System<Out = ()>>) -> bool {
+        let boxed: Box<dyn System<Out = ()>> = Box::new(new_system.into_system());
+        if let Some(entry) = self.pre_exclusive.iter_mut().find(|(sid, _)| *sid == id) {
+            entry.1 = boxed;
+            return true;
+        }
+        if let Some(entry) = self.parallel.iter_mut().find(|(sid, _, _)| *sid == id) {
+            entry.1 = boxed;
+            return true;
+        }
+        if let Some(entry) = self.post_exclusive.iter_mut().find(|(sid, _)| *sid == id) {
+            entry.1 = boxed;
+            return true;
+        }
+        false
+    }
+
+    pub fn system_count(&self) -> usize {
+        self.pre_exclusive.len() + self.parallel.len() + self.post_exclusive.len()
+    }
+
+    /// Every system's effective `Access`, in run order, for `SequenceGraph` to decide whether
+    /// this whole phase can run concurrently with another one. Pre/post-exclusive systems
+    /// always report `Access::Exclusive` — they need the full `&mut World`, the same as a
+    /// parallel-group system added via plain `add_system`.
+    pub(crate) fn accesses(&self) -> Vec<Access> {
+        let mut accesses = Vec::with_capacity(self.system_count());
+        accesses.extend(self.pre_exclusive.iter().map(|_| Access::Exclusive));
+        accesses.extend(self.parallel.iter().map(|(_, _, access)| access.clone()));
+        accesses.extend(self.post_exclusive.iter().map(|_| Access::Exclusive));
+        accesses
+    }
+
+    /// Per-system read/write component names and exclusivity, for a debug overlay or other
+    /// tooling that wants a full picture of what a schedule touches without running it.
+    ///
+    /// Listed in run order: pre-exclusive systems, then the parallel group, then
+    /// post-exclusive systems. Exclusive systems (pre/post, or parallel ones added via
+    /// `add_system`/`Access::Exclusive`/`Access::NonSend`) report no specific components,
+    /// since their whole point is that they can reach anything.
+    pub fn system_access_summary(&self, registry: &Registry) -> Vec<SystemAccessSummary> {
+        let mut summaries = Vec::with_capacity(self.system_count());
+        for (id, _) in &self.pre_exclusive {
+            summaries.push(SystemAccessSummary { id: *id, exclusive: true, reads: Vec::new(), writes: Vec::new() });
+        }
+        for (id, _, access) in &self.parallel {
+            summaries.push(match access {
+                Access::Components(components) => {
+                    let mut reads = Vec::new();
+                    let mut writes = Vec::new();
+                    for &(component, mutability) in components {
+                        let name = registry.info(component).name();
+                        match mutability {
+                            Mutability::Read => reads.push(name),
+                            Mutability::Write => writes.push(name),
+                        }
+                    }
+                    SystemAccessSummary { id: *id, exclusive: false, reads, writes }
+                }
+                // Uniques aren't named in the `Registry`, so there's no component name to
+                // report here; `Phase::validate` is what actually reasons about them.
+                Access::Uniques(_) | Access::Exclusive | Access::NonSend => {
+                    SystemAccessSummary { id: *id, exclusive: true, reads: Vec::new(), writes: Vec::new() }
+                }
+            });
+        }
+        for (id, _) in &self.post_exclusive {
+            summaries.push(SystemAccessSummary { id: *id, exclusive: true, reads: Vec::new(), writes: Vec::new() });
+        }
+        summaries
+    }
+
+    /// Runs pre-exclusive systems, then the parallel group, then post-exclusive systems.
+    pub fn run(&mut self, world: &mut World) {
+        for (_, system) in &mut self.pre_exclusive {
+            system.run(world);
+        }
+        self.run_parallel_group(world);
+        for (_, system) in &mut self.post_exclusive {
+            system.run(world);
+        }
+    }
+
+    /// Runs the parallel group, splitting it across up to `self.threads` real OS threads
+    /// when that's more than `1` and doing so looks safe; falls back to the historical
+    /// declaration-order, single-thread loop otherwise.
+    ///
+    /// Only `threads() > 1` on its own isn't enough to fan out: `validate()` must also pass
+    /// (an `Exclusive`/`NonSend`/unique-conflicted group can't be trusted to touch disjoint
+    /// memory), and even then, two systems sharing a *component* id only ever get grouped
+    /// into the same wave here if `scheduling_conflicts` says they don't overlap at all —
+    /// stricter than `validate()`'s own component check, which tolerates write/write
+    /// overlap because until now every phase ran strictly sequentially regardless.
+    fn run_parallel_group(&mut self, world: &mut World) {
+        if self.threads <= 1 || self.parallel.len() <= 1 || self.validate().is_err() {
+            for (_, system, _) in &mut self.parallel {
+                system.run(world);
+            }
+            return;
+        }
+
+        let waves = self.build_waves();
+        let world_ptr = SendPtr(world as *mut World);
+        let parallel_ptr = SendPtr(self.parallel.as_mut_ptr());
+        for wave in waves {
+            for chunk in wave.chunks(self.threads) {
+                std::thread::scope(|scope| {
+                    for &index in chunk {
+                        scope.spawn(move || {
+                            // SAFETY: `build_waves` only ever puts systems in the same wave
+                            // when `scheduling_conflicts` found no shared component/unique
+                            // id between any pair in it, and distinct `index`es never alias
+                            // the same `parallel` slot — so each spawned thread here reaches
+                            // a disjoint system and (per every system's declared `Access`) a
+                            // disjoint slice of `world`, as long as that declaration is
+                            // honest.
+                            //
+                            // Bind the whole `SendPtr` first (rather than reaching straight
+                            // for `.0`) so 2021-edition disjoint capture moves the wrapper
+                            // itself into this closure instead of just its raw-pointer
+                            // field, which wouldn't be `Send` on its own.
+                            let world_ptr = world_ptr;
+                            let parallel_ptr = parallel_ptr;
+                            let (_, system, _) = unsafe { &mut *parallel_ptr.0.add(index) };
+                            let world = unsafe { &mut *world_ptr.0 };
+                            system.run(world);
+                        });
+                    }
+                });
+            }
+        }
+    }
+
+    /// Greedily partitions the parallel group's indices into waves where no two systems in
+    /// the same wave conflict per `scheduling_conflicts` — everything in a wave can safely
+    /// run at once; waves themselves still run one after another.
+    fn build_waves(&self) -> Vec<Vec<usize>> {
+        let mut waves: Vec<Vec<usize>> = Vec::new();
+        for (index, (_, _, access)) in self.parallel.iter().enumerate() {
+            let wave = waves.iter_mut().find(|wave| {
+                wave.iter().all(|&other| !scheduling_conflicts(access, &self.parallel[other].2))
+            });
+            match wave {
+                Some(wave) => wave.push(index),
+                None => waves.push(vec![index]),
+            }
+        }
+        waves
+    }
+
+    /// Checks every pair of systems in the parallel group for access conflicts, without
+    /// running anything.
+    ///
+    /// Two `Access::Components` systems never conflict here, even when they both write the
+    /// same component — worst case is they serialize, which the phase already does. An
+    /// `Access::Exclusive` system sharing the group with anything else is a real
+    /// misconfiguration (it needs the whole world, so it can never actually be parallel)
+    /// and is reported as a `ConflictError`. `Access::Uniques` sits in between: unlike a
+    /// component (spread across many entities), a unique is one shared value, so an
+    /// overlapping read/write or write/write pair is reported too, the same way two
+    /// systems both taking `UniqMut<Config>` must not be considered parallelizable.
+    pub fn validate(&self) -> Result<(), Vec<ConflictError>> {
+        let mut errors = Vec::new();
+
+        for (index, (_, _, access)) in self.parallel.iter().enumerate() {
+            if matches!(access, Access::NonSend) {
+                errors.push(ConflictError::NonSendInParallel { phase: self.name, index });
+            }
+        }
+
+        for first in 0..self.parallel.len() {
+            for second in (first + 1)..self.parallel.len() {
+                let (_, _, first_access) = &self.parallel[first];
+                let (_, _, second_access) = &self.parallel[second];
+                let exclusive = matches!(first_access, Access::Exclusive) || matches!(second_access, Access::Exclusive);
+                if exclusive {
+                    errors.push(ConflictError::Exclusive {
+                        phase: self.name,
+                        first,
+                        second,
+                    });
+                }
+                if let (Access::Uniques(a), Access::Uniques(b)) = (first_access, second_access) {
+                    let conflicts = a.iter().any(|&(id, mutability)| {
+                        b.iter().any(|&(other_id, other_mutability)| {
+                            id == other_id && (mutability == Mutability::Write || other_mutability == Mutability::Write)
+                        })
+                    });
+                    if conflicts {
+                        errors.push(ConflictError::UniqueConflict {
+                            phase: self.name,
+                            first,
+                            second,
+                        });
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::ComponentId;
+    use crate::ecs::unique::{UniqElement, UniqMut, Unique};
+    use rusty_engine_macros::Component;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Component)]
+    struct Health;
+    #[derive(Component)]
+    struct Velocity;
+
+    struct Config;
+    impl Unique for Config {}
+    struct SoundVolume;
+    impl Unique for SoundVolume {}
+
+    #[test]
+    fn write_write_component_access_validates_cleanly() {
+        let mut phase = Phase::new("update");
+        let a = ComponentId(0);
+
+        phase.add_system_with_access(|_: &mut World| {}, Access::Components(vec![(a, Mutability::Write)]));
+        phase.add_system_with_access(|_: &mut World| {}, Access::Components(vec![(a, Mutability::Write)]));
+
+        assert!(phase.validate().is_ok());
+    }
+
+    #[test]
+    fn exclusive_system_sharing_the_group_is_a_conflict() {
+        let mut phase = Phase::new("update");
+        let a = ComponentId(0);
+
+        phase.add_system_with_access(|_: &mut World| {}, Access::Components(vec![(a, Mutability::Read)]));
+        phase.add_system(|_: &mut World| {});
+
+        let errors = phase.validate().unwrap_err();
+        assert_eq!(errors, vec![ConflictError::Exclusive { phase: "update", first: 0, second: 1 }]);
+    }
+
+    #[test]
+    fn two_uniq_mut_systems_over_the_same_unique_conflict_but_different_uniques_dont() {
+        let mut phase = Phase::new("update");
+        phase.add_system_with_access(|_: &mut World| {}, Access::Uniques(vec![UniqMut::<Config>::access()]));
+        phase.add_system_with_access(|_: &mut World| {}, Access::Uniques(vec![UniqMut::<Config>::access()]));
+
+        let errors = phase.validate().unwrap_err();
+        assert_eq!(errors, vec![ConflictError::UniqueConflict { phase: "update", first: 0, second: 1 }]);
+
+        let mut phase = Phase::new("update");
+        phase.add_system_with_access(|_: &mut World| {}, Access::Uniques(vec![UniqMut::<Config>::access()]));
+        phase.add_system_with_access(|_: &mut World| {}, Access::Uniques(vec![UniqMut::<SoundVolume>::access()]));
+
+        assert!(phase.validate().is_ok());
+    }
+
+    #[test]
+    fn non_send_system_in_parallel_group_fails_validation() {
+        let mut phase = Phase::new("render");
+        phase.add_system_with_access(|_: &mut World| {}, Access::NonSend);
+
+        let errors = phase.validate().unwrap_err();
+        assert_eq!(errors, vec![ConflictError::NonSendInParallel { phase: "render", index: 0 }]);
+    }
+
+    #[test]
+    fn exclusive_systems_run_before_and_after_parallel_group() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut phase = Phase::new("update");
+
+        let pre_log = log.clone();
+        phase.add_exclusive_ordered(
+            move |_: &mut World| pre_log.lock().unwrap().push("pre"),
+            ExclusiveOrder::PreParallel,
+        );
+
+        let parallel_log = log.clone();
+        phase.add_system(move |_: &mut World| parallel_log.lock().unwrap().push("parallel"));
+
+        let post_log = log.clone();
+        phase.add_exclusive_ordered(
+            move |_: &mut World| post_log.lock().unwrap().push("post"),
+            ExclusiveOrder::PostParallel,
+        );
+
+        let mut world = World::new();
+        phase.run(&mut world);
+
+        assert_eq!(*log.lock().unwrap(), vec!["pre", "parallel", "post"]);
+    }
+
+    #[test]
+    fn add_exclusive_defaults_to_pre_parallel() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut phase = Phase::new("update");
+
+        let parallel_log = log.clone();
+        phase.add_system(move |_: &mut World| parallel_log.lock().unwrap().push("parallel"));
+
+        let exclusive_log = log.clone();
+        phase.add_exclusive(move |_: &mut World| exclusive_log.lock().unwrap().push("exclusive"));
+
+        let mut world = World::new();
+        phase.run(&mut world);
+
+        assert_eq!(*log.lock().unwrap(), vec!["exclusive", "parallel"]);
+    }
+
+    #[test]
+    fn remove_system_drops_only_the_named_system() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut phase = Phase::new("update");
+
+        let kept_log = log.clone();
+        let kept = phase.add_system(move |_: &mut World| kept_log.lock().unwrap().push("kept"));
+
+        let removed_log = log.clone();
+        let removed = phase.add_system(move |_: &mut World| removed_log.lock().unwrap().push("removed"));
+
+        assert!(phase.remove_system(removed));
+        assert_eq!(phase.system_count(), 1);
+
+        let mut world = World::new();
+        phase.run(&mut world);
+
+        assert_eq!(*log.lock().unwrap(), vec!["kept"]);
+        assert!(phase.remove_system(kept));
+        assert_eq!(phase.system_count(), 0);
+    }
+
+    #[test]
+    fn system_access_summary_lists_read_write_and_exclusive_systems() {
+        let mut registry = Registry::new();
+        let health = registry.register::<Health>();
+        let velocity = registry.register::<Velocity>();
+
+        let mut phase = Phase::new("update");
+        let pre = phase.add_exclusive(|_: &mut World| {});
+        let reader = phase.add_system_with_access(|_: &mut World| {}, Access::Components(vec![(health, Mutability::Read)]));
+        let writer = phase.add_system_with_access(
+            |_: &mut World| {},
+            Access::Components(vec![(health, Mutability::Write), (velocity, Mutability::Read)]),
+        );
+        let exclusive = phase.add_system(|_: &mut World| {});
+
+        let summary = phase.system_access_summary(&registry);
+        assert_eq!(summary, vec![
+            SystemAccessSummary { id: pre, exclusive: true, reads: vec![], writes: vec![] },
+            SystemAccessSummary { id: reader, exclusive: false, reads: vec![registry.info(health).name()], writes: vec![] },
+            SystemAccessSummary {
+                id: writer,
+                exclusive: false,
+                reads: vec![registry.info(velocity).name()],
+                writes: vec![registry.info(health).name()],
+            },
+            SystemAccessSummary { id: exclusive, exclusive: true, reads: vec![], writes: vec![] },
+        ]);
+    }
+
+    #[test]
+    fn replace_system_swaps_the_running_system_in_place() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut phase = Phase::new("update");
+
+        let original_log = log.clone();
+        let id = phase.add_system(move |_: &mut World| original_log.lock().unwrap().push("original"));
+
+        let replacement_log = log.clone();
+        assert!(phase.replace_system(id, move |_: &mut World| replacement_log.lock().unwrap().push("replacement")));
+
+        let mut world = World::new();
+        phase.run(&mut world);
+
+        assert_eq!(*log.lock().unwrap(), vec!["replacement"]);
+    }
+
+    #[test]
+    fn one_thread_runs_parallel_systems_in_declaration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut phase = Phase::new("render");
+        let a = ComponentId(0);
+        let b = ComponentId(1);
+
+        let first_log = log.clone();
+        phase.add_system_with_access(move |_: &mut World| first_log.lock().unwrap().push(1), Access::Components(vec![(a, Mutability::Write)]));
+        let second_log = log.clone();
+        phase.add_system_with_access(move |_: &mut World| second_log.lock().unwrap().push(2), Access::Components(vec![(b, Mutability::Write)]));
+        assert_eq!(phase.threads(), 1);
+
+        let mut world = World::new();
+        phase.run(&mut world);
+
+        assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn enough_threads_run_disjoint_systems_concurrently() {
+        use std::sync::Barrier;
+        use std::time::Duration;
+
+        // Each system blocks on a two-party barrier; the only way both ever return is if
+        // they're actually running on separate threads at the same time. On the historical
+        // sequential loop (or a threads() of 1) the second system would never start until
+        // the first returns, so the first's `wait()` would block forever.
+        let barrier = Arc::new(Barrier::new(2));
+        let mut phase = Phase::new("physics");
+        let a = ComponentId(0);
+        let b = ComponentId(1);
+
+        let first_barrier = barrier.clone();
+        phase.add_system_with_access(move |_: &mut World| { first_barrier.wait(); }, Access::Components(vec![(a, Mutability::Write)]));
+        let second_barrier = barrier.clone();
+        phase.add_system_with_access(move |_: &mut World| { second_barrier.wait(); }, Access::Components(vec![(b, Mutability::Write)]));
+        phase.set_threads(2);
+
+        let handle = std::thread::spawn(move || {
+            let mut world = World::new();
+            phase.run(&mut world);
+        });
+
+        for _ in 0..500 {
+            if handle.is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(handle.is_finished(), "disjoint systems on a multi-threaded phase should run concurrently, not deadlock on the barrier");
+    }
+}
@@ -0,0 +1,127 @@
+//! `Sequence`: a composable alternative to `Schedule` for expressing "run this, then if
+//! some condition holds run that" rather than a flat, always-run phase list.
+//!
+//! Where `Schedule` is a flat `Vec<Phase>` meant to be validated and run once per tick,
+//! `Sequence` builds a tree of steps — plain phases, conditional branches gated on `&World`
+//! at run time, and embedded sub-sequences — for cases like "run FixedUpdate, then if not
+//! paused run Update and Render".
+
+use crate::ecs::schedule::Phase;
+use crate::ecs::world::World;
+
+enum Step {
+    Phase(Phase),
+    Conditional(Box<dyn Fn(&World) -> bool + Send>, Phase),
+    Sequence(Sequence),
+}
+
+/// An ordered list of steps — phases, conditional phases, and sub-sequences — run against a
+/// `World` in order.
+#[derive(Default)]
+pub struct Sequence {
+    steps: Vec<Step>,
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `phase`, always run.
+    pub fn then(&mut self, phase: Phase) -> &mut Self {
+        self.steps.push(Step::Phase(phase));
+        self
+    }
+
+    /// Appends `phase`, run only when `condition(world)` evaluates to `true` at run time.
+    pub fn then_if(&mut self, condition: impl Fn(&World) -> bool + Send + 'static, phase: Phase) -> &mut Self {
+        self.steps.push(Step::Conditional(Box::new(condition), phase));
+        self
+    }
+
+    /// Embeds `sequence` as a sub-sequence, run in place with its own steps evaluated in
+    /// order.
+    pub fn then_sequence(&mut self, sequence: Sequence) -> &mut Self {
+        self.steps.push(Step::Sequence(sequence));
+        self
+    }
+
+    /// Runs every step against `world` in order, skipping conditional branches whose
+    /// condition evaluates to `false`.
+    pub fn run(&mut self, world: &mut World) {
+        for step in &mut self.steps {
+            match step {
+                Step::Phase(phase) => phase.run(world),
+                Step::Conditional(condition, phase) => {
+                    if condition(world) {
+                        phase.run(world);
+                    }
+                }
+                Step::Sequence(sequence) => sequence.run(world),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::unique::Unique;
+
+    struct Paused(bool);
+    impl Unique for Paused {}
+
+    #[derive(Default)]
+    struct Counters {
+        fixed: u32,
+        update: u32,
+        render: u32,
+    }
+    impl Unique for Counters {}
+
+    fn phase_incrementing(name: &'static str, f: fn(&mut Counters)) -> Phase {
+        let mut phase = Phase::new(name);
+        phase.add_exclusive(move |world: &mut World| f(world.unique_mut::<Counters>().unwrap()));
+        phase
+    }
+
+    #[test]
+    fn conditional_branch_runs_only_while_unpaused() {
+        let mut world = World::new();
+        world.insert_unique(Paused(true));
+        world.insert_unique(Counters::default());
+
+        let mut sequence = Sequence::new();
+        sequence.then(phase_incrementing("fixed", |c| c.fixed += 1));
+        sequence.then_if(
+            |world| !world.unique::<Paused>().unwrap().0,
+            phase_incrementing("update", |c| c.update += 1),
+        );
+
+        sequence.run(&mut world);
+        assert_eq!(world.unique::<Counters>().unwrap().fixed, 1);
+        assert_eq!(world.unique::<Counters>().unwrap().update, 0);
+
+        world.unique_mut::<Paused>().unwrap().0 = false;
+        sequence.run(&mut world);
+        assert_eq!(world.unique::<Counters>().unwrap().fixed, 2);
+        assert_eq!(world.unique::<Counters>().unwrap().update, 1);
+    }
+
+    #[test]
+    fn sub_sequence_runs_its_own_steps_in_place() {
+        let mut world = World::new();
+        world.insert_unique(Counters::default());
+
+        let mut render_pass = Sequence::new();
+        render_pass.then(phase_incrementing("render", |c| c.render += 1));
+
+        let mut sequence = Sequence::new();
+        sequence.then(phase_incrementing("fixed", |c| c.fixed += 1));
+        sequence.then_sequence(render_pass);
+
+        sequence.run(&mut world);
+        assert_eq!(world.unique::<Counters>().unwrap().fixed, 1);
+        assert_eq!(world.unique::<Counters>().unwrap().render, 1);
+    }
+}
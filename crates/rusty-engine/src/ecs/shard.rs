@@ -0,0 +1,278 @@
+//! `Shard`: a scoped, access-tracked view into a `World` for fanning queries out beyond a
+//! single `&mut World` borrow.
+//!
+//! `Query` always holds `&mut World` (see its doc comment), which keeps aliasing trivially
+//! sound but limits callers to one query at a time. A `Shard` instead borrows `&World`
+//! immutably and validates its component access against a shared `GrantTracker`, so a
+//! caller that only needs to read can hand out several `Shard`s over the same components at
+//! once.
+
+use crate::ecs::component::ComponentId;
+use crate::ecs::query::{QueryData, Result};
+use crate::ecs::schedule::Mutability;
+use crate::ecs::world::World;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Tracks which components currently have an outstanding access grant, and at what
+/// mutability, so `Shard`s covering overlapping components can't be created unsoundly.
+///
+/// Concurrent `Read` grants for the same component never conflict with each other, so
+/// they're reference-counted rather than rejected; a `Write` grant is exclusive and
+/// conflicts with any other grant (read or write) over the same component.
+#[derive(Default)]
+pub struct GrantTracker {
+    reads: HashMap<ComponentId, usize>,
+    writes: HashSet<ComponentId>,
+}
+
+impl GrantTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a grant over every id in `ids` at `mutability`, failing with the first
+    /// component that already has a conflicting grant outstanding. Grants either all of
+    /// `ids` or none of them.
+    pub fn acquire(&mut self, ids: &[ComponentId], mutability: Mutability) -> std::result::Result<(), ComponentId> {
+        for &id in ids {
+            if self.writes.contains(&id) || (mutability == Mutability::Write && self.reads.contains_key(&id)) {
+                return Err(id);
+            }
+        }
+        for &id in ids {
+            match mutability {
+                Mutability::Read => *self.reads.entry(id).or_insert(0) += 1,
+                Mutability::Write => {
+                    self.writes.insert(id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Releases a grant previously returned by `acquire` for the same `ids`/`mutability`.
+    pub fn release(&mut self, ids: &[ComponentId], mutability: Mutability) {
+        for &id in ids {
+            match mutability {
+                Mutability::Read => {
+                    if let Some(count) = self.reads.get_mut(&id) {
+                        *count -= 1;
+                        if *count == 0 {
+                            self.reads.remove(&id);
+                        }
+                    }
+                }
+                Mutability::Write => {
+                    self.writes.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+/// A scoped, access-tracked view into a `World`, granted a fixed set of components at a
+/// fixed `Mutability` through a shared `GrantTracker`. The grant is released when the
+/// `Shard` is dropped.
+pub struct Shard<'w> {
+    world: &'w World,
+    ids: Vec<ComponentId>,
+    mutability: Mutability,
+    tracker: Arc<Mutex<GrantTracker>>,
+}
+
+impl<'w> Shard<'w> {
+    /// Acquires a new grant over `ids` at `mutability` from `tracker`, failing with the
+    /// first conflicting component if one is already granted incompatibly.
+    pub fn new(world: &'w World, tracker: Arc<Mutex<GrantTracker>>, ids: Vec<ComponentId>, mutability: Mutability) -> std::result::Result<Self, ComponentId> {
+        tracker.lock().unwrap().acquire(&ids, mutability)?;
+        Ok(Self { world, ids, mutability, tracker })
+    }
+
+    pub fn ids(&self) -> &[ComponentId] {
+        &self.ids
+    }
+
+    pub fn mutability(&self) -> Mutability {
+        self.mutability
+    }
+
+    /// Hands out another immutable `Shard` over the same components and `World`, backed by
+    /// its own grant on the same tracker. Only valid for read grants — reads never conflict
+    /// with each other, so this is how a read-only system fans work across worker threads.
+    ///
+    /// # Panics
+    /// Panics if this `Shard` holds a write grant.
+    pub fn reborrow_shared(&self) -> Shard<'w> {
+        assert_eq!(self.mutability, Mutability::Read, "reborrow_shared requires a read-only grant");
+        self.tracker.lock().unwrap().acquire(&self.ids, Mutability::Read).expect("existing read grant must still be acquirable");
+        Shard {
+            world: self.world,
+            ids: self.ids.clone(),
+            mutability: self.mutability,
+            tracker: self.tracker.clone(),
+        }
+    }
+
+    /// Iterates every entity matching `Q` among the components this `Shard` was granted.
+    pub fn query<Q: QueryData>(&self) -> Result<'_, Q> {
+        let archetypes = self
+            .world
+            .archetypes()
+            .iter()
+            .filter(|(_, table)| self.ids.iter().all(|&id| table.has_column(id)))
+            .map(|(id, _)| id)
+            .collect();
+        Result::new(self.world, self.ids.clone(), archetypes)
+    }
+
+    /// Decomposes this `Shard` into its raw parts without releasing its grant, for handing
+    /// ownership of a shard across an FFI boundary to a host scheduler that manages `Shard`
+    /// lifetimes itself instead of relying on `Drop`. The grant stays held on `tracker` until
+    /// whatever's on the other side of the boundary reconstructs a `Shard` via
+    /// `from_raw_parts` and drops it.
+    pub fn into_raw_parts(self) -> RawShard<'w> {
+        // Wrapped in `ManuallyDrop` so `self`'s own `Drop` (which would release the grant
+        // `raw` is carrying onward) never runs, without also skipping the destructor for
+        // `ids`/`tracker` the way a bare `mem::forget(self)` would: each field is read out
+        // by value below, handing its ownership to `raw` instead of leaking it.
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `world` is `Copy`; `ids` and `tracker` are read out exactly once each and
+        // never touched again — `this` is never used after this block, so nothing double-
+        // frees or double-drops them.
+        unsafe {
+            RawShard {
+                world: this.world as *const World,
+                ids: std::ptr::read(&this.ids),
+                mutability: this.mutability,
+                tracker: std::ptr::read(&this.tracker),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Reconstructs a `Shard` from parts produced by `into_raw_parts`, resuming ownership of
+    /// its still-outstanding grant — dropping the returned `Shard` releases it exactly once.
+    ///
+    /// # Safety
+    /// `raw` must have come from `Shard::into_raw_parts` and not already been passed to this
+    /// function (each grant may only be resumed once); `raw.world` must still point at a live
+    /// `World` — the same one the grant's `ids` were validated against — that outlives the
+    /// returned `Shard`'s `'w`.
+    pub unsafe fn from_raw_parts(raw: RawShard<'w>) -> Self {
+        Self {
+            world: unsafe { &*raw.world },
+            ids: raw.ids,
+            mutability: raw.mutability,
+            tracker: raw.tracker,
+        }
+    }
+}
+
+/// The raw components of a `Shard`, as produced by `Shard::into_raw_parts` and consumed by
+/// `Shard::from_raw_parts`. Exists so a `Shard` can cross an FFI boundary (e.g. into a C host's
+/// custom scheduler) as a plain data payload instead of a Rust value with a live borrow.
+///
+/// Carrying a `RawShard` around without ever passing it back to `from_raw_parts` leaks its
+/// grant on `tracker` forever (nothing else releases it); passing the same `RawShard` to
+/// `from_raw_parts` twice would double-release it, which is exactly what that function's
+/// safety contract forbids.
+pub struct RawShard<'w> {
+    pub world: *const World,
+    pub ids: Vec<ComponentId>,
+    pub mutability: Mutability,
+    pub tracker: Arc<Mutex<GrantTracker>>,
+    _marker: PhantomData<&'w World>,
+}
+
+impl Drop for Shard<'_> {
+    fn drop(&mut self) {
+        self.tracker.lock().unwrap().release(&self.ids, self.mutability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Position(f32, f32);
+
+    #[test]
+    fn two_shared_read_shards_query_the_same_world_concurrently() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+        let id = world.registry_mut().register::<Position>();
+
+        let tracker = Arc::new(Mutex::new(GrantTracker::new()));
+        let shard_a = Shard::new(&world, tracker.clone(), vec![id], Mutability::Read).unwrap();
+        let shard_b = shard_a.reborrow_shared();
+
+        // Neither shard's grant blocks the other; queries against both interleave freely.
+        let mut a_iter = shard_a.query::<&Position>();
+        let mut b_iter = shard_b.query::<&Position>();
+        assert_eq!(a_iter.next(), Some(&Position(1.0, 0.0)));
+        assert_eq!(b_iter.next(), Some(&Position(1.0, 0.0)));
+        assert_eq!(a_iter.next(), Some(&Position(2.0, 0.0)));
+        assert_eq!(b_iter.next(), Some(&Position(2.0, 0.0)));
+    }
+
+    #[test]
+    fn shard_round_trips_through_raw_parts_and_still_queries() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+        let id = world.registry_mut().register::<Position>();
+
+        let tracker = Arc::new(Mutex::new(GrantTracker::new()));
+        let shard = Shard::new(&world, tracker.clone(), vec![id], Mutability::Read).unwrap();
+        let raw = shard.into_raw_parts();
+        let shard = unsafe { Shard::from_raw_parts(raw) };
+
+        let mut results = shard.query::<&Position>();
+        assert_eq!(results.next(), Some(&Position(1.0, 0.0)));
+        assert_eq!(results.next(), Some(&Position(2.0, 0.0)));
+        drop(shard);
+
+        // The grant round-tripped, rather than leaking or being double-released: a fresh
+        // grant over the same component is acquirable again now that it's dropped.
+        assert!(Shard::new(&world, tracker, vec![id], Mutability::Write).is_ok());
+    }
+
+    #[test]
+    fn into_raw_parts_does_not_leak_the_tracker_arc_or_the_ids_vec() {
+        let world = World::new();
+        let tracker = Arc::new(Mutex::new(GrantTracker::new()));
+        let shard = Shard::new(&world, tracker.clone(), Vec::new(), Mutability::Read).unwrap();
+        // +1 for `tracker` itself, +1 for the grant held by `shard`.
+        assert_eq!(Arc::strong_count(&tracker), 2);
+
+        let raw = shard.into_raw_parts();
+        // Decomposing into raw parts must move the `Arc` out, not clone-then-forget a
+        // second handle onto it — the count stays exactly what it was (one handle now
+        // owned by `raw`, one by the local `tracker` binding), instead of climbing forever
+        // on every `into_raw_parts` call.
+        assert_eq!(Arc::strong_count(&tracker), 2);
+
+        let shard = unsafe { Shard::from_raw_parts(raw) };
+        drop(shard);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn write_grant_rejects_overlapping_grant() {
+        let mut world = World::new();
+        let id = world.registry_mut().register::<Position>();
+
+        let tracker = Arc::new(Mutex::new(GrantTracker::new()));
+        let _writer = Shard::new(&world, tracker.clone(), vec![id], Mutability::Write).unwrap();
+        match Shard::new(&world, tracker, vec![id], Mutability::Read) {
+            Err(conflicting) => assert_eq!(conflicting, id),
+            Ok(_) => panic!("expected the read grant to conflict with the outstanding write grant"),
+        };
+    }
+}
@@ -0,0 +1,347 @@
+//! The archetype registry: one archetype per unique `Spec`, backed by one `Table`.
+
+use crate::ecs::component::{ComponentId, Registry, Spec};
+use crate::ecs::storage::column::NotCloneable;
+use crate::ecs::storage::table::Table;
+use std::collections::HashMap;
+
+/// Identifies a single archetype (and its backing `Table`) within an `Archetypes` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArchetypeId(pub(crate) usize);
+
+impl ArchetypeId {
+    /// The archetype for entities with no components.
+    pub const EMPTY: ArchetypeId = ArchetypeId(0);
+
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Owns every archetype `Table` in a `World` and maps component specs to them.
+pub struct Archetypes {
+    specs: Vec<Spec>,
+    tables: Vec<Table>,
+    by_spec: HashMap<Spec, ArchetypeId>,
+    by_component: HashMap<ComponentId, Vec<ArchetypeId>>,
+    /// "Add this component" / "remove this component" edges out of each archetype, populated
+    /// lazily by `add_edge`/`remove_edge`. Archetype relationships are structural (they only
+    /// depend on which `Spec`s exist, never on live entity data), so once an edge is cached
+    /// it's correct forever — there's nothing here that ever needs invalidating.
+    add_edges: HashMap<(ArchetypeId, ComponentId), ArchetypeId>,
+    remove_edges: HashMap<(ArchetypeId, ComponentId), ArchetypeId>,
+    /// Fired from `get_or_create`, the single place a new `Table` is actually allocated, so
+    /// `add_edge`/`remove_edge` migrations trigger it too. Empty by default, so the common case
+    /// of nobody watching costs one `is_empty` check per creation.
+    on_created: Vec<CreatedCallback>,
+}
+
+type CreatedCallback = Box<dyn FnMut(ArchetypeId, &Spec)>;
+
+/// Per-archetype and total byte usage, returned by `Archetypes::memory_report`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryReport {
+    pub per_archetype: HashMap<ArchetypeId, usize>,
+    pub total_bytes: usize,
+    /// The fraction of `total_bytes` that isn't holding live row data — `0.0` means every
+    /// allocated byte is occupied, higher values mean more slack from over-`grow`n columns.
+    pub overhead_ratio: f64,
+}
+
+impl Default for Archetypes {
+    fn default() -> Self {
+        let mut archetypes = Self {
+            specs: Vec::new(),
+            tables: Vec::new(),
+            by_spec: HashMap::new(),
+            by_component: HashMap::new(),
+            add_edges: HashMap::new(),
+            remove_edges: HashMap::new(),
+            on_created: Vec::new(),
+        };
+        archetypes.get_or_create(&Registry::new(), Spec::empty());
+        archetypes
+    }
+}
+
+impl Archetypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finds (or lazily creates) the archetype matching `spec`.
+    pub fn get_or_create(&mut self, registry: &Registry, spec: Spec) -> ArchetypeId {
+        if let Some(&id) = self.by_spec.get(&spec) {
+            return id;
+        }
+        let id = ArchetypeId(self.tables.len());
+        self.tables.push(Table::new(registry, spec.ids()));
+        for &component in spec.ids() {
+            self.by_component.entry(component).or_default().push(id);
+        }
+        self.specs.push(spec.clone());
+        self.by_spec.insert(spec.clone(), id);
+        if !self.on_created.is_empty() {
+            for callback in &mut self.on_created {
+                callback(id, &spec);
+            }
+        }
+        id
+    }
+
+    /// Registers `callback` to run, on the caller's thread, every time `get_or_create` (directly
+    /// or via `add_edge`/`remove_edge`) allocates a brand-new archetype. Never fires for a spec
+    /// that already has an archetype.
+    pub fn on_created(&mut self, callback: impl FnMut(ArchetypeId, &Spec) + 'static) {
+        self.on_created.push(Box::new(callback));
+    }
+
+    /// Finds (or lazily computes and caches) the archetype reached by adding `component` to
+    /// `from`'s spec. Repeated migrations of the same kind reuse the cached edge and skip both
+    /// the spec rebuild and the `by_spec` hash lookup.
+    pub fn add_edge(&mut self, registry: &Registry, from: ArchetypeId, component: ComponentId) -> ArchetypeId {
+        if let Some(&to) = self.add_edges.get(&(from, component)) {
+            return to;
+        }
+        let mut ids = self.specs[from.0].ids().to_vec();
+        ids.push(component);
+        let to = self.get_or_create(registry, Spec::new(ids));
+        self.add_edges.insert((from, component), to);
+        to
+    }
+
+    /// Finds (or lazily computes and caches) the archetype reached by removing `component` from
+    /// `from`'s spec. See `add_edge` for the caching rationale.
+    pub fn remove_edge(&mut self, registry: &Registry, from: ArchetypeId, component: ComponentId) -> ArchetypeId {
+        if let Some(&to) = self.remove_edges.get(&(from, component)) {
+            return to;
+        }
+        let ids: Vec<ComponentId> = self.specs[from.0].ids().iter().copied().filter(|&id| id != component).collect();
+        let to = self.get_or_create(registry, Spec::new(ids));
+        self.remove_edges.insert((from, component), to);
+        to
+    }
+
+    /// Iterates every archetype (and its `Spec`) that contains `component`, in creation order.
+    pub fn containing(&self, component: ComponentId) -> impl Iterator<Item = (ArchetypeId, &Spec)> {
+        self.by_component
+            .get(&component)
+            .into_iter()
+            .flatten()
+            .map(|&id| (id, self.spec(id)))
+    }
+
+    pub fn find(&self, spec: &Spec) -> Option<ArchetypeId> {
+        self.by_spec.get(spec).copied()
+    }
+
+    pub fn spec(&self, id: ArchetypeId) -> &Spec {
+        &self.specs[id.0]
+    }
+
+    pub fn table(&self, id: ArchetypeId) -> &Table {
+        &self.tables[id.0]
+    }
+
+    pub fn table_mut(&mut self, id: ArchetypeId) -> &mut Table {
+        &mut self.tables[id.0]
+    }
+
+    /// Mutably borrows two distinct archetypes' tables at once.
+    ///
+    /// Returns `None` if `a == b`, since that would alias.
+    pub fn tables_mut2(&mut self, a: ArchetypeId, b: ArchetypeId) -> Option<(&mut Table, &mut Table)> {
+        if a == b {
+            return None;
+        }
+        if a.0 < b.0 {
+            let (left, right) = self.tables.split_at_mut(b.0);
+            Some((&mut left[a.0], &mut right[0]))
+        } else {
+            let (left, right) = self.tables.split_at_mut(a.0);
+            Some((&mut right[0], &mut left[b.0]))
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ArchetypeId, &Table)> {
+        self.tables.iter().enumerate().map(|(i, t)| (ArchetypeId(i), t))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ArchetypeId, &mut Table)> {
+        self.tables.iter_mut().enumerate().map(|(i, t)| (ArchetypeId(i), t))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Sums `Table::memory_usage`/`live_bytes` across every archetype, for the dhat memory
+    /// benchmark and in-engine diagnostics that want to see fragmentation, not just totals.
+    pub fn memory_report(&self) -> MemoryReport {
+        let per_archetype = self.iter().map(|(id, table)| (id, table.memory_usage())).collect();
+        let total_bytes: usize = self.tables.iter().map(Table::memory_usage).sum();
+        let live_bytes: usize = self.tables.iter().map(Table::live_bytes).sum();
+        let overhead_ratio = if total_bytes == 0 {
+            0.0
+        } else {
+            (total_bytes - live_bytes) as f64 / total_bytes as f64
+        };
+        MemoryReport { per_archetype, total_bytes, overhead_ratio }
+    }
+
+    /// Deep-copies every table via `Table::try_clone`, for a full, independent duplicate of
+    /// this `World`'s component data (e.g. a rollback-netcode snapshot). The edge caches and
+    /// `by_spec`/`by_component` indices are plain data keyed by `ArchetypeId`/`ComponentId`,
+    /// so they're copied as-is rather than rebuilt. Fails with the first non-cloneable
+    /// component encountered (tables are visited in creation order).
+    pub fn try_clone(&self) -> Result<Archetypes, NotCloneable> {
+        let tables = self.tables.iter().map(Table::try_clone).collect::<Result<_, NotCloneable>>()?;
+        Ok(Archetypes {
+            specs: self.specs.clone(),
+            tables,
+            by_spec: self.by_spec.clone(),
+            by_component: self.by_component.clone(),
+            add_edges: self.add_edges.clone(),
+            remove_edges: self.remove_edges.clone(),
+            // Callbacks aren't `Clone`; a clone starts with no observers of its own, matching
+            // `World::try_clone`'s existing scoping of what does and doesn't carry over.
+            on_created: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::Registry;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component)]
+    struct A;
+    #[derive(Component)]
+    struct B;
+
+    #[test]
+    fn get_or_create_reuses_matching_spec() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+        let mut archetypes = Archetypes::new();
+
+        let id1 = archetypes.get_or_create(&registry, Spec::new(vec![a, b]));
+        let id2 = archetypes.get_or_create(&registry, Spec::new(vec![b, a]));
+        assert_eq!(id1, id2);
+        assert_eq!(archetypes.len(), 2); // empty archetype + this one
+    }
+
+    #[test]
+    fn containing_indexes_archetypes_by_component() {
+        #[derive(Component)]
+        struct C;
+
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+        let c = registry.register::<C>();
+        let mut archetypes = Archetypes::new();
+
+        let ab = archetypes.get_or_create(&registry, Spec::new(vec![a, b]));
+        let a_only = archetypes.get_or_create(&registry, Spec::new(vec![a]));
+        archetypes.get_or_create(&registry, Spec::new(vec![c]));
+
+        let with_a: Vec<_> = archetypes.containing(a).map(|(id, _)| id).collect();
+        assert_eq!(with_a, vec![ab, a_only]);
+        assert_eq!(archetypes.containing(b).count(), 1);
+    }
+
+    #[test]
+    fn add_and_remove_edges_round_trip_and_are_cached() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let b = registry.register::<B>();
+        let mut archetypes = Archetypes::new();
+
+        let a_only = archetypes.get_or_create(&registry, Spec::new(vec![a]));
+        let ab = archetypes.get_or_create(&registry, Spec::new(vec![a, b]));
+
+        let via_edge = archetypes.add_edge(&registry, a_only, b);
+        assert_eq!(via_edge, ab);
+        // Second call must hit the cache rather than recomputing: same answer, no new archetype.
+        let len_before = archetypes.len();
+        assert_eq!(archetypes.add_edge(&registry, a_only, b), ab);
+        assert_eq!(archetypes.len(), len_before);
+
+        let back = archetypes.remove_edge(&registry, ab, b);
+        assert_eq!(back, a_only);
+        assert_eq!(archetypes.remove_edge(&registry, ab, b), a_only);
+    }
+
+    #[test]
+    fn try_clone_deep_copies_tables_and_mutating_the_copy_does_not_affect_the_original() {
+        #[derive(Component, Debug, Clone, PartialEq)]
+        struct Score(Vec<u32>);
+
+        let mut registry = Registry::new();
+        let id = registry.register_cloneable::<Score>();
+        let mut archetypes = Archetypes::new();
+        let archetype = archetypes.get_or_create(&registry, Spec::new(vec![id]));
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let e0 = entities.alloc();
+        let table = archetypes.table_mut(archetype);
+        unsafe {
+            let value = Score(vec![1, 2, 3]);
+            table.write_component(id, (&value as *const Score).cast(), 0);
+            std::mem::forget(value);
+        }
+        table.finish_push(e0);
+
+        let mut cloned = archetypes.try_clone().unwrap();
+        unsafe {
+            let ptr = cloned.table_mut(archetype).column_mut(id).unwrap().get_mut(0).unwrap() as *mut Score;
+            (*ptr).0.push(4);
+        }
+
+        let original = unsafe { &*(archetypes.table(archetype).column(id).unwrap().get(0).unwrap() as *const Score) };
+        assert_eq!(original, &Score(vec![1, 2, 3]));
+        let copy = unsafe { &*(cloned.table(archetype).column(id).unwrap().get(0).unwrap() as *const Score) };
+        assert_eq!(copy, &Score(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn memory_report_totals_every_archetype_and_computes_an_overhead_ratio() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let mut archetypes = Archetypes::new();
+        let archetype = archetypes.get_or_create(&registry, Spec::new(vec![a]));
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let table = archetypes.table_mut(archetype);
+        for _ in 0..4 {
+            let e = entities.alloc();
+            unsafe {
+                table.write_component(a, (&A as *const A).cast(), 0);
+            }
+            table.finish_push(e);
+        }
+
+        let report = archetypes.memory_report();
+        assert_eq!(report.per_archetype[&archetype], archetypes.table(archetype).memory_usage());
+        assert_eq!(report.total_bytes, archetypes.iter().map(|(_, t)| t.memory_usage()).sum::<usize>());
+        assert!((0.0..=1.0).contains(&report.overhead_ratio));
+    }
+
+    #[test]
+    fn try_clone_rejects_a_non_cloneable_component() {
+        let mut registry = Registry::new();
+        let a = registry.register::<A>();
+        let mut archetypes = Archetypes::new();
+        archetypes.get_or_create(&registry, Spec::new(vec![a]));
+
+        assert!(archetypes.try_clone().is_err());
+    }
+}
@@ -0,0 +1,977 @@
+//! Type-erased, contiguous storage for a single component type.
+
+use crate::ecs::component::Info;
+use crate::ecs::storage::mem::{self, SharedAllocator};
+use std::alloc::Layout;
+use std::ptr::NonNull;
+
+/// A growable, type-erased `Vec<C>` for some component type `C`, described at runtime by
+/// an `Info` (layout + drop glue) rather than a generic parameter.
+///
+/// Rows are stored contiguously and packed (no gaps); removal is always swap-remove, which
+/// is why callers must be told which row moved so they can fix up their own indices.
+pub struct Column {
+    name: &'static str,
+    layout: Layout,
+    drop_fn: Option<unsafe fn(*mut u8)>,
+    clone_fn: Option<unsafe fn(*const u8, *mut u8)>,
+    data: NonNull<u8>,
+    capacity: usize,
+    len: usize,
+    allocator: SharedAllocator,
+    /// The world tick each row was pushed at, and the tick it was last mutated through a
+    /// `query::Mut` at, one entry per row (kept in step with `data`/`len` by every push and
+    /// swap-remove). Plain `Vec<u64>`s rather than raw-allocated like `data`, since ticks
+    /// aren't type-erased component bytes and don't need `Column`'s custom layout dance.
+    added_ticks: Vec<u64>,
+    changed_ticks: Vec<u64>,
+    /// Whether each row is logically removed from queries without migrating the entity to a
+    /// different archetype — see `World::disable_component`. One entry per row, kept in step
+    /// with `data`/`len` the same way `added_ticks`/`changed_ticks` are.
+    disabled: Vec<bool>,
+    /// How many times `get`/`get_mut` returned a row, for `World::access_stats`. Both are
+    /// `AtomicU64`, not a plain `Cell<u64>`/`u64` bumped through `&self`/`&mut self` the way
+    /// the rest of `Column`'s bookkeeping is: `query::Result::into_par_iter` hands out rows
+    /// from the *same* `Column` to several rayon workers at once (only ever disjoint rows,
+    /// never this counter), through a `*mut Column` obtained once up front rather than a
+    /// fresh `&mut self` borrow per worker — see `ParIter`'s safety comment. A non-atomic
+    /// counter bumped that way is a real, observable data race (lost updates), not just a
+    /// theoretical aliasing gap; `Ordering::Relaxed` is enough since these only ever need to
+    /// not lose an increment, not order anything else.
+    #[cfg(feature = "stats")]
+    reads: std::sync::atomic::AtomicU64,
+    #[cfg(feature = "stats")]
+    writes: std::sync::atomic::AtomicU64,
+}
+
+// SAFETY: `Column` only stores `Send + Sync` component bytes (enforced by `Component`
+// bounds at every public entry point that constructs one).
+unsafe impl Send for Column {}
+unsafe impl Sync for Column {}
+
+impl Column {
+    pub fn new(info: &Info) -> Self {
+        Self::with_allocator(info, mem::global())
+    }
+
+    /// Like `new`, but draws its backing buffer from `allocator` instead of the global
+    /// allocator — e.g. a per-world arena for better locality and bulk free.
+    pub fn with_allocator(info: &Info, allocator: SharedAllocator) -> Self {
+        let layout = info.layout();
+        Self {
+            name: info.name(),
+            layout,
+            drop_fn: info.drop_fn(),
+            clone_fn: info.clone_fn(),
+            data: Self::dangling_for(layout),
+            capacity: 0,
+            len: 0,
+            allocator,
+            added_ticks: Vec::new(),
+            changed_ticks: Vec::new(),
+            disabled: Vec::new(),
+            #[cfg(feature = "stats")]
+            reads: std::sync::atomic::AtomicU64::new(0),
+            #[cfg(feature = "stats")]
+            writes: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    /// A dangling pointer for a zero-sized type must still be aligned to *that type's*
+    /// alignment, not just any nonnull address — `NonNull<u8>::dangling()` (align 1) isn't
+    /// enough for a `#[repr(align(N))]` tag with N > 1, even though no byte of it is ever
+    /// actually read or written.
+    fn dangling_for(layout: Layout) -> NonNull<u8> {
+        mem::dangling_for(layout)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn is_zst(&self) -> bool {
+        self.layout.size() == 0
+    }
+
+    /// Reports how many more rows can be pushed before the next `push` would call `grow`
+    /// again. Exposed mainly so tests can observe that a ZST column never actually
+    /// allocates: it jumps straight to `usize::MAX` on its first `grow` and stays there.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn grow(&mut self, min_capacity: usize) {
+        if self.is_zst() {
+            // No bytes to store means no allocation to make — every row lives entirely in
+            // `len`. `capacity` only needs to satisfy `len == capacity` never holding again.
+            self.capacity = usize::MAX;
+            return;
+        }
+        let new_capacity = (self.capacity.max(1) * 2).max(min_capacity);
+        let new_layout = array_layout(self.layout, new_capacity);
+        let new_data = if self.capacity == 0 {
+            self.allocator.alloc(new_layout)
+        } else {
+            let old_layout = array_layout(self.layout, self.capacity);
+            unsafe { self.allocator.realloc(self.data.as_ptr(), old_layout, new_layout.size()) }
+        };
+        self.data = NonNull::new(new_data).unwrap_or_else(|| std::alloc::handle_alloc_error(new_layout));
+        self.capacity = new_capacity;
+    }
+
+    /// Row `index`'s byte offset into the backing allocation.
+    fn offset(&self, index: usize) -> usize {
+        self.layout.size() * index
+    }
+
+    /// # Safety
+    /// `ptr` must point to a valid, initialized value matching this column's layout, and
+    /// ownership of it is transferred into the column (the caller must not drop it).
+    ///
+    /// `tick` becomes the new row's added *and* changed tick, matching how a freshly spawned
+    /// or inserted component is both.
+    pub unsafe fn push(&mut self, ptr: *const u8, tick: u64) {
+        if self.len == self.capacity {
+            self.grow(self.len + 1);
+        }
+        let dst = self.data.as_ptr().add(self.offset(self.len));
+        std::ptr::copy_nonoverlapping(ptr, dst, self.layout.size());
+        self.len += 1;
+        self.added_ticks.push(tick);
+        self.changed_ticks.push(tick);
+        self.disabled.push(false);
+    }
+
+    /// Bulk-appends every element of `values` in one contiguous copy instead of one `push`
+    /// per element — the fast path for mass-spawning entities that all share this single
+    /// component type (see `World::spawn_column`).
+    ///
+    /// # Safety
+    /// `C`'s layout must match this column's, and ownership of every element in `values`
+    /// transfers into the column. `values` itself is never dropped element-by-element (its
+    /// backing allocation is freed, but its length is zeroed first so `Vec`'s own `Drop`
+    /// doesn't also drop the values this call just moved out from under it).
+    pub unsafe fn extend<C>(&mut self, mut values: Vec<C>, tick: u64) {
+        let count = values.len();
+        if count == 0 {
+            return;
+        }
+        if self.len + count > self.capacity {
+            self.grow(self.len + count);
+        }
+        let dst = self.data.as_ptr().add(self.offset(self.len));
+        std::ptr::copy_nonoverlapping(values.as_ptr().cast::<u8>(), dst, self.layout.size() * count);
+        values.set_len(0);
+        self.len += count;
+        self.added_ticks.extend(std::iter::repeat_n(tick, count));
+        self.changed_ticks.extend(std::iter::repeat_n(tick, count));
+        self.disabled.extend(std::iter::repeat_n(false, count));
+    }
+
+    /// The tick this row's value was pushed at.
+    pub fn added_tick(&self, index: usize) -> u64 {
+        self.added_ticks[index]
+    }
+
+    /// The tick this row's value was last pushed or mutated through a `query::Mut` at.
+    pub fn changed_tick(&self, index: usize) -> u64 {
+        self.changed_ticks[index]
+    }
+
+    /// Records that row `index` was mutated at `tick`, called by `query::Mut::deref_mut`.
+    pub fn mark_changed(&mut self, index: usize, tick: u64) {
+        self.changed_ticks[index] = tick;
+    }
+
+    /// Logically removes row `index` from queries without moving or dropping its value —
+    /// see `World::disable_component`. Reversible via `enable`.
+    pub fn disable(&mut self, index: usize) {
+        self.disabled[index] = true;
+    }
+
+    /// Undoes a prior `disable`, making row `index` visible to queries again.
+    pub fn enable(&mut self, index: usize) {
+        self.disabled[index] = false;
+    }
+
+    /// Whether row `index` has been `disable`d.
+    pub fn is_disabled(&self, index: usize) -> bool {
+        self.disabled[index]
+    }
+
+    pub fn get(&self, index: usize) -> Option<*const u8> {
+        let ptr = (index < self.len).then(|| unsafe { self.data.as_ptr().add(self.offset(index)) as *const u8 });
+        #[cfg(feature = "stats")]
+        if ptr.is_some() {
+            self.reads.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<*mut u8> {
+        let ptr = (index < self.len).then(|| unsafe { self.data.as_ptr().add(self.offset(index)) });
+        #[cfg(feature = "stats")]
+        if ptr.is_some() {
+            self.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    /// A read-only view of `len` contiguous rows starting at `start`, for callers processing
+    /// a run of rows at once (e.g. `query::Result::for_each_chunk`) rather than one at a time
+    /// through `get`.
+    ///
+    /// # Safety
+    /// `C` must match this column's actual component type, and `start + len` must not exceed
+    /// `self.len()`.
+    pub unsafe fn slice<C>(&self, start: usize, len: usize) -> &[C] {
+        debug_assert!(start + len <= self.len);
+        let ptr = self.data.as_ptr().add(self.offset(start)) as *const C;
+        std::slice::from_raw_parts(ptr, len)
+    }
+
+    /// Like `slice`, but for mutation.
+    ///
+    /// # Safety
+    /// Same as `slice`.
+    pub unsafe fn slice_mut<C>(&mut self, start: usize, len: usize) -> &mut [C] {
+        debug_assert!(start + len <= self.len);
+        let ptr = self.data.as_ptr().add(self.offset(start)) as *mut C;
+        std::slice::from_raw_parts_mut(ptr, len)
+    }
+
+    /// Times `get` has returned a row since the last `reset_stats`.
+    #[cfg(feature = "stats")]
+    pub fn reads(&self) -> u64 {
+        self.reads.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Times `get_mut` has returned a row since the last `reset_stats`.
+    #[cfg(feature = "stats")]
+    pub fn writes(&self) -> u64 {
+        self.writes.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Zeroes this column's read/write counters, e.g. at the start of a new frame.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.reads.store(0, std::sync::atomic::Ordering::Relaxed);
+        self.writes.store(0, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Overwrites row `index` with raw bytes, dropping whatever value was there first.
+    ///
+    /// Unlike `push`/`get_mut`, this is a safe entry point: it checks that `index` is in
+    /// bounds and that `bytes` is exactly one element wide before touching memory, so a
+    /// caller can't corrupt the column by miscounting either.
+    pub fn write_bytes(&mut self, index: usize, bytes: &[u8]) -> Result<(), WriteBytesError> {
+        if index >= self.len {
+            return Err(WriteBytesError::OutOfBounds { index, len: self.len });
+        }
+        if bytes.len() != self.layout.size() {
+            return Err(WriteBytesError::SizeMismatch {
+                expected: self.layout.size(),
+                actual: bytes.len(),
+            });
+        }
+        unsafe {
+            let dst = self.data.as_ptr().add(self.offset(index));
+            self.drop_at(dst);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+        }
+        Ok(())
+    }
+
+    /// Overwrites every row in order with `values`, dropping each old value first — the bulk
+    /// counterpart to looping `write_bytes`/`get_mut` over every row, for something like a
+    /// system that recomputes a whole `Visible` column each frame instead of writing it row
+    /// by row. Fails without writing anything past the shortfall if `values` doesn't yield
+    /// exactly `self.len()` items — rows already overwritten by that point stay overwritten.
+    ///
+    /// # Safety
+    /// `C` must match this column's actual component type (in practice: the same type
+    /// `Registry::register::<C>` was called with to build this column's `Info`).
+    pub unsafe fn fill<C>(&mut self, mut values: impl Iterator<Item = C>) -> Result<(), FillColumnError> {
+        for index in 0..self.len {
+            let Some(value) = values.next() else {
+                return Err(FillColumnError::TooFewValues { provided: index, expected: self.len });
+            };
+            let dst = self.data.as_ptr().add(self.offset(index)) as *mut C;
+            std::ptr::drop_in_place(dst);
+            dst.write(value);
+        }
+        if values.next().is_some() {
+            return Err(FillColumnError::TooManyValues { expected: self.len });
+        }
+        Ok(())
+    }
+
+    /// Swaps rows `a` and `b`'s bytes (and their added/changed ticks) in place. Neither row
+    /// is dropped or moved out of the column — this is a pure reorder, for something like
+    /// `World::sort_table_by` arranging a table by a component key.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.len && b < self.len);
+        if a == b {
+            return;
+        }
+        if !self.is_zst() {
+            unsafe {
+                let a_ptr = self.data.as_ptr().add(self.offset(a));
+                let b_ptr = self.data.as_ptr().add(self.offset(b));
+                std::ptr::swap_nonoverlapping(a_ptr, b_ptr, self.layout.size());
+            }
+        }
+        self.added_ticks.swap(a, b);
+        self.changed_ticks.swap(a, b);
+        self.disabled.swap(a, b);
+    }
+
+    /// Removes row `index` by swapping the last row into its place (or just truncating if
+    /// it *is* the last row). Returns `true` if a different row was moved into `index`.
+    pub fn swap_remove_and_drop(&mut self, index: usize) -> bool {
+        assert!(index < self.len);
+        let last = self.len - 1;
+        unsafe {
+            let removed = self.data.as_ptr().add(self.offset(index));
+            self.drop_at(removed);
+            let moved = index != last;
+            if moved {
+                let src = self.data.as_ptr().add(self.offset(last));
+                std::ptr::copy_nonoverlapping(src, removed, self.layout.size());
+            }
+            self.len -= 1;
+            self.added_ticks.swap_remove(index);
+            self.changed_ticks.swap_remove(index);
+            self.disabled.swap_remove(index);
+            moved
+        }
+    }
+
+    /// # Safety
+    /// `ptr` must be valid for writes of this column's layout and receives ownership of the
+    /// removed value (caller becomes responsible for it, e.g. by moving it elsewhere).
+    pub unsafe fn swap_remove_into(&mut self, index: usize, out: *mut u8) -> bool {
+        assert!(index < self.len);
+        let last = self.len - 1;
+        let removed = self.data.as_ptr().add(self.offset(index));
+        std::ptr::copy_nonoverlapping(removed, out, self.layout.size());
+        let moved = index != last;
+        if moved {
+            let src = self.data.as_ptr().add(self.offset(last));
+            std::ptr::copy_nonoverlapping(src, removed, self.layout.size());
+        }
+        self.len -= 1;
+        self.added_ticks.swap_remove(index);
+        self.changed_ticks.swap_remove(index);
+        self.disabled.swap_remove(index);
+        moved
+    }
+
+    /// Moves row `index`'s value into `dest` (via `dest.push`) then removes it from this
+    /// column by swap-remove, *without* dropping it — ownership has already transferred to
+    /// `dest`. Returns `true` if a different row was moved into `index` to fill the gap.
+    ///
+    /// # Safety
+    /// `dest`'s layout must match this column's (in practice: both were created from the
+    /// same component's `Info`).
+    pub unsafe fn move_into(&mut self, index: usize, dest: &mut Column, tick: u64) -> bool {
+        assert!(index < self.len);
+        let src = self.data.as_ptr().add(self.offset(index));
+        let disabled = self.disabled[index];
+        dest.push(src, tick);
+        if disabled {
+            dest.disable(dest.len - 1);
+        }
+        let last = self.len - 1;
+        let moved = index != last;
+        if moved {
+            let from = self.data.as_ptr().add(self.offset(last));
+            std::ptr::copy_nonoverlapping(from, src, self.layout.size());
+        }
+        self.len -= 1;
+        self.added_ticks.swap_remove(index);
+        self.changed_ticks.swap_remove(index);
+        self.disabled.swap_remove(index);
+        moved
+    }
+
+    /// Moves every row of `other` into `self` via a single contiguous copy, leaving `other`
+    /// empty — the bulk counterpart to `move_into`, for migrating or merging a whole table's
+    /// rows at once (e.g. `World::merge`, or moving all entities of one archetype into a
+    /// superset archetype) instead of one `move_into` call per row.
+    ///
+    /// # Safety
+    /// `other`'s layout must match this column's (in practice: both were created from the
+    /// same component's `Info`), same requirement as `move_into`.
+    pub unsafe fn append(&mut self, other: &mut Column) {
+        let count = other.len;
+        if count == 0 {
+            return;
+        }
+        if self.len + count > self.capacity {
+            self.grow(self.len + count);
+        }
+        let dst = self.data.as_ptr().add(self.offset(self.len));
+        std::ptr::copy_nonoverlapping(other.data.as_ptr(), dst, self.layout.size() * count);
+        self.len += count;
+        other.len = 0;
+        self.added_ticks.append(&mut other.added_ticks);
+        self.changed_ticks.append(&mut other.changed_ticks);
+        self.disabled.append(&mut other.disabled);
+    }
+
+    /// Total bytes this column has allocated: its main data buffer's `capacity` slots (zero
+    /// for a ZST, which never allocates one) plus the per-row tick/disabled bookkeeping
+    /// vectors' own allocations. For `Archetypes::memory_report`.
+    pub fn memory_usage(&self) -> usize {
+        let data = if self.is_zst() { 0 } else { self.capacity * self.layout.size() };
+        data + self.added_ticks.capacity() * std::mem::size_of::<u64>()
+            + self.changed_ticks.capacity() * std::mem::size_of::<u64>()
+            + self.disabled.capacity() * std::mem::size_of::<bool>()
+    }
+
+    /// Like `memory_usage`, but counting only occupied rows (`len` instead of `capacity`) —
+    /// `memory_usage`'s live-data numerator for surfacing fragmentation.
+    pub fn live_bytes(&self) -> usize {
+        let data = if self.is_zst() { 0 } else { self.len * self.layout.size() };
+        data + self.len * (std::mem::size_of::<u64>() * 2 + std::mem::size_of::<bool>())
+    }
+
+    unsafe fn drop_at(&self, ptr: *mut u8) {
+        if let Some(drop_fn) = self.drop_fn {
+            drop_fn(ptr);
+        }
+    }
+
+    /// Deep-copies every row into a new `Column` via the component type's real `Clone`
+    /// impl, for `Table::try_clone` (and in turn `Archetypes::try_clone`) to build an
+    /// independent copy of a `World`'s data — e.g. a rollback-netcode snapshot.
+    ///
+    /// Fails rather than bit-copying if this column's component was only ever registered
+    /// via `Registry::register` — bit-copying a `Clone` type that owns a heap allocation
+    /// (a `Vec`, a `String`) would alias it between the original and the "clone" instead of
+    /// duplicating it, and silently succeeding on that would be worse than refusing.
+    pub fn try_clone(&self) -> Result<Column, NotCloneable> {
+        let clone_fn = self.clone_fn.ok_or(NotCloneable { name: self.name })?;
+        let mut cloned = Self {
+            name: self.name,
+            layout: self.layout,
+            drop_fn: self.drop_fn,
+            clone_fn: self.clone_fn,
+            data: Self::dangling_for(self.layout),
+            capacity: 0,
+            len: 0,
+            allocator: self.allocator.clone(),
+            added_ticks: self.added_ticks.clone(),
+            changed_ticks: self.changed_ticks.clone(),
+            disabled: self.disabled.clone(),
+            #[cfg(feature = "stats")]
+            reads: std::sync::atomic::AtomicU64::new(0),
+            #[cfg(feature = "stats")]
+            writes: std::sync::atomic::AtomicU64::new(0),
+        };
+        if self.len > 0 {
+            cloned.grow(self.len);
+            for i in 0..self.len {
+                unsafe {
+                    let src = self.data.as_ptr().add(self.offset(i)) as *const u8;
+                    let dst = cloned.data.as_ptr().add(cloned.offset(i));
+                    clone_fn(src, dst);
+                }
+            }
+            cloned.len = self.len;
+        }
+        Ok(cloned)
+    }
+}
+
+/// Returned by `Column::try_clone` (and the `Table`/`Archetypes` clones built on top of it)
+/// when a column's component type was never registered with `Registry::register_cloneable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotCloneable {
+    pub name: &'static str,
+}
+
+impl std::fmt::Display for NotCloneable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "component `{}` isn't cloneable — register it with Registry::register_cloneable", self.name)
+    }
+}
+
+impl std::error::Error for NotCloneable {}
+
+impl Drop for Column {
+    fn drop(&mut self) {
+        if self.drop_fn.is_some() {
+            for i in 0..self.len {
+                unsafe {
+                    let ptr = self.data.as_ptr().add(self.offset(i));
+                    self.drop_at(ptr);
+                }
+            }
+        }
+        if self.capacity > 0 && !self.is_zst() {
+            // A ZST column's `data` was never allocated (see `new`/`grow`), so there's
+            // nothing here for `dealloc` to free.
+            let layout = array_layout(self.layout, self.capacity);
+            unsafe { self.allocator.dealloc(self.data.as_ptr(), layout) };
+        }
+    }
+}
+
+/// Why a `Column::write_bytes` call was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteBytesError {
+    /// `index` was not an occupied row.
+    OutOfBounds { index: usize, len: usize },
+    /// `bytes` wasn't exactly one element's worth of this column's type.
+    SizeMismatch { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for WriteBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds { index, len } => write!(f, "row {index} out of bounds (len {len})"),
+            Self::SizeMismatch { expected, actual } => {
+                write!(f, "expected {expected} bytes for one element, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WriteBytesError {}
+
+/// Why a `Column::fill`/`Table::fill_column` call was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillColumnError {
+    /// `values` yielded fewer items than this column has rows.
+    TooFewValues { provided: usize, expected: usize },
+    /// `values` yielded more items than this column has rows.
+    TooManyValues { expected: usize },
+}
+
+impl std::fmt::Display for FillColumnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewValues { provided, expected } => write!(f, "expected {expected} values, got only {provided}"),
+            Self::TooManyValues { expected } => write!(f, "expected exactly {expected} values, got more"),
+        }
+    }
+}
+
+impl std::error::Error for FillColumnError {}
+
+fn array_layout(elem: Layout, count: usize) -> Layout {
+    let size = elem.size().checked_mul(count).expect("column allocation overflow");
+    Layout::from_size_align(size, elem.align().max(1)).expect("invalid column layout")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::Registry;
+    use rusty_engine_macros::Component;
+    use std::alloc;
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    impl Drop for Health {
+        fn drop(&mut self) {}
+    }
+
+    #[derive(Component)]
+    #[repr(align(8))]
+    struct Enemy;
+
+    #[test]
+    fn zst_column_never_allocates_and_reports_correct_len() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Enemy>();
+        let mut column = Column::new(registry.info(id));
+
+        assert_eq!(column.capacity(), 0);
+        for _ in 0..1000 {
+            unsafe { column.push((&Enemy as *const Enemy).cast(), 0) };
+        }
+        // A real allocation would grow `capacity` step by step to fit 1000 rows; jumping
+        // straight to `usize::MAX` on the very first push is only possible because `grow`
+        // short-circuits for zero-sized types instead of ever calling `alloc`.
+        assert_eq!(column.capacity(), usize::MAX);
+        assert_eq!(column.len(), 1000);
+    }
+
+    #[test]
+    fn zst_column_pointers_stay_aligned_to_the_type() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Enemy>();
+        let mut column = Column::new(registry.info(id));
+
+        unsafe { column.push((&Enemy as *const Enemy).cast(), 0) };
+        let ptr = column.get(0).unwrap();
+        assert_eq!(ptr as usize % std::mem::align_of::<Enemy>(), 0);
+    }
+
+    #[test]
+    fn zst_column_swap_remove_is_just_length_bookkeeping() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Enemy>();
+        let mut column = Column::new(registry.info(id));
+
+        for _ in 0..3 {
+            unsafe { column.push((&Enemy as *const Enemy).cast(), 0) };
+        }
+
+        assert!(column.swap_remove_and_drop(0));
+        assert_eq!(column.len(), 2);
+        assert!(column.get(1).is_some());
+        assert!(column.get(2).is_none());
+    }
+
+    #[test]
+    fn extend_bulk_copies_every_value_and_ticks_them_together() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        let existing = Health(0);
+        unsafe { column.push((&existing as *const Health).cast(), 0) };
+        std::mem::forget(existing);
+
+        let values: Vec<Health> = (1..=1000).map(Health).collect();
+        unsafe { column.extend(values, 7) };
+
+        assert_eq!(column.len(), 1001);
+        for i in 1..=1000 {
+            let ptr = column.get(i).unwrap() as *const Health;
+            assert_eq!(unsafe { (*ptr).0 }, i as u32);
+            assert_eq!(column.added_tick(i), 7);
+            assert_eq!(column.changed_tick(i), 7);
+        }
+    }
+
+    #[test]
+    fn append_moves_every_row_into_the_destination_and_empties_the_source() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut from = Column::new(registry.info(id));
+        let mut into = Column::new(registry.info(id));
+
+        let existing = Health(0);
+        unsafe { into.push((&existing as *const Health).cast(), 0) };
+        std::mem::forget(existing);
+
+        for v in [1u32, 2, 3] {
+            let value = Health(v);
+            unsafe { from.push((&value as *const Health).cast(), 5) };
+            std::mem::forget(value);
+        }
+
+        unsafe { into.append(&mut from) };
+
+        assert_eq!(into.len(), 4);
+        assert_eq!(from.len(), 0);
+        for (i, expected) in [1u32, 2, 3].into_iter().enumerate() {
+            let ptr = into.get(1 + i).unwrap() as *const Health;
+            assert_eq!(unsafe { (*ptr).0 }, expected);
+            assert_eq!(into.added_tick(1 + i), 5);
+        }
+    }
+
+    #[test]
+    fn memory_usage_covers_capacity_while_live_bytes_covers_only_len() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3] {
+            let value = Health(v);
+            unsafe { column.push((&value as *const Health).cast(), 0) };
+            std::mem::forget(value);
+        }
+        column.swap_remove_and_drop(0);
+
+        // `grow` doubles past what 2 live rows need, so capacity outstrips len...
+        assert!(column.capacity() > column.len());
+        // ...which `memory_usage` (capacity-based) reflects but `live_bytes` (len-based)
+        // doesn't.
+        assert!(column.memory_usage() > column.live_bytes());
+        assert_eq!(column.live_bytes(), column.len() * (4 + 8 + 8 + 1));
+    }
+
+    #[test]
+    fn push_and_get_roundtrip() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        let value = Health(42);
+        unsafe { column.push((&value as *const Health).cast(), 0) };
+        std::mem::forget(value);
+
+        let ptr = column.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 42);
+    }
+
+    #[test]
+    fn swap_exchanges_values_and_ticks() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        for (i, v) in [1u32, 2, 3].into_iter().enumerate() {
+            let value = Health(v);
+            unsafe { column.push((&value as *const Health).cast(), i as u64) };
+            std::mem::forget(value);
+        }
+
+        column.swap(0, 2);
+
+        let ptr = column.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 3);
+        let ptr = column.get(2).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 1);
+        assert_eq!(column.added_tick(0), 2);
+        assert_eq!(column.added_tick(2), 0);
+        assert_eq!(column.len(), 3); // a pure reorder, nothing added or removed
+    }
+
+    #[test]
+    fn slice_and_slice_mut_view_a_contiguous_run_of_rows() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3, 4] {
+            let value = Health(v);
+            unsafe { column.push((&value as *const Health).cast(), 0) };
+            std::mem::forget(value);
+        }
+
+        let middle = unsafe { column.slice::<Health>(1, 2) };
+        assert_eq!(middle.iter().map(|h| h.0).collect::<Vec<_>>(), vec![2, 3]);
+
+        for health in unsafe { column.slice_mut::<Health>(1, 2) } {
+            health.0 *= 10;
+        }
+        let all = unsafe { column.slice::<Health>(0, 4) };
+        assert_eq!(all.iter().map(|h| h.0).collect::<Vec<_>>(), vec![1, 20, 30, 4]);
+    }
+
+    #[test]
+    fn swap_remove_reports_moved_row() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3] {
+            let value = Health(v);
+            unsafe { column.push((&value as *const Health).cast(), 0) };
+            std::mem::forget(value);
+        }
+
+        assert!(column.swap_remove_and_drop(0));
+        let ptr = column.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 3);
+        assert_eq!(column.len(), 2);
+    }
+
+    #[test]
+    fn write_bytes_overwrites_row_in_place() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        let value = Health(1);
+        unsafe { column.push((&value as *const Health).cast(), 0) };
+        std::mem::forget(value);
+
+        column.write_bytes(0, &99u32.to_ne_bytes()).unwrap();
+        let ptr = column.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 99);
+    }
+
+    #[test]
+    fn fill_overwrites_every_row_and_drops_each_old_value_exactly_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Component)]
+        struct Counted(u32);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut registry = Registry::new();
+        let id = registry.register::<Counted>();
+        let mut column = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3] {
+            let value = Counted(v);
+            unsafe { column.push((&value as *const Counted).cast(), 0) };
+            std::mem::forget(value);
+        }
+        DROPS.store(0, Ordering::SeqCst);
+
+        unsafe { column.fill([10u32, 20, 30].into_iter().map(Counted)) }.unwrap();
+
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+        for (i, expected) in [10u32, 20, 30].into_iter().enumerate() {
+            let ptr = column.get(i).unwrap() as *const Counted;
+            assert_eq!(unsafe { (*ptr).0 }, expected);
+        }
+    }
+
+    #[test]
+    fn fill_rejects_too_few_or_too_many_values() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3] {
+            let value = Health(v);
+            unsafe { column.push((&value as *const Health).cast(), 0) };
+            std::mem::forget(value);
+        }
+
+        assert_eq!(
+            unsafe { column.fill([Health(9), Health(9)].into_iter()) },
+            Err(FillColumnError::TooFewValues { provided: 2, expected: 3 })
+        );
+        assert_eq!(
+            unsafe { column.fill([Health(9), Health(9), Health(9), Health(9)].into_iter()) },
+            Err(FillColumnError::TooManyValues { expected: 3 })
+        );
+    }
+
+    #[test]
+    fn move_into_transfers_value_and_shrinks_source() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut source = Column::new(registry.info(id));
+        let mut dest = Column::new(registry.info(id));
+
+        for v in [1u32, 2, 3] {
+            let value = Health(v);
+            unsafe { source.push((&value as *const Health).cast(), 0) };
+            std::mem::forget(value);
+        }
+
+        let moved = unsafe { source.move_into(0, &mut dest, 5) };
+        assert!(moved);
+        assert_eq!(source.len(), 2);
+        assert_eq!(dest.len(), 1);
+
+        let ptr = source.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 3); // last row swapped into the removed slot
+
+        let ptr = dest.get(0).unwrap() as *const Health;
+        assert_eq!(unsafe { (*ptr).0 }, 1);
+        assert_eq!(dest.added_tick(0), 5);
+    }
+
+    #[test]
+    fn write_bytes_rejects_out_of_bounds_and_wrong_size() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let mut column = Column::new(registry.info(id));
+
+        assert_eq!(
+            column.write_bytes(0, &0u32.to_ne_bytes()),
+            Err(WriteBytesError::OutOfBounds { index: 0, len: 0 })
+        );
+
+        let value = Health(1);
+        unsafe { column.push((&value as *const Health).cast(), 0) };
+        std::mem::forget(value);
+
+        assert_eq!(
+            column.write_bytes(0, &[0u8; 2]),
+            Err(WriteBytesError::SizeMismatch { expected: 4, actual: 2 })
+        );
+    }
+
+    #[derive(Component, Debug, Clone, PartialEq)]
+    struct Score(Vec<u32>);
+
+    #[test]
+    fn try_clone_deep_copies_a_cloneable_column() {
+        let mut registry = Registry::new();
+        let id = registry.register_cloneable::<Score>();
+        let mut column = Column::new(registry.info(id));
+        let value = Score(vec![1, 2, 3]);
+        unsafe { column.push((&value as *const Score).cast(), 0) };
+        std::mem::forget(value);
+
+        let mut cloned = column.try_clone().unwrap();
+        assert_eq!(unsafe { &*(cloned.get(0).unwrap() as *const Score) }, &Score(vec![1, 2, 3]));
+
+        unsafe { &mut *(cloned.get_mut(0).unwrap() as *mut Score) }.0.push(4);
+        assert_eq!(unsafe { &*(column.get(0).unwrap() as *const Score) }, &Score(vec![1, 2, 3]));
+        assert_eq!(unsafe { &*(cloned.get(0).unwrap() as *const Score) }, &Score(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn try_clone_rejects_a_column_never_registered_cloneable() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+        let column = Column::new(registry.info(id));
+
+        match column.try_clone() {
+            Err(err) => assert_eq!(err.name, std::any::type_name::<Health>()),
+            Ok(_) => panic!("expected NotCloneable"),
+        }
+    }
+
+    /// A `MemAllocator` counting live (not yet deallocated) allocations, for
+    /// `custom_allocator_balances_allocations_and_deallocations` below.
+    #[derive(Default)]
+    struct CountingAllocator {
+        live: std::sync::atomic::AtomicUsize,
+    }
+
+    unsafe impl crate::ecs::storage::mem::MemAllocator for CountingAllocator {
+        fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.live.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            unsafe { alloc::alloc(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+            unsafe { alloc::realloc(ptr, old_layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.live.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+            unsafe { alloc::dealloc(ptr, layout) };
+        }
+    }
+
+    #[test]
+    fn custom_allocator_balances_allocations_and_deallocations() {
+        use std::sync::atomic::Ordering;
+        use std::sync::Arc;
+
+        let counter = Arc::new(CountingAllocator::default());
+        let mut registry = Registry::new();
+        let id = registry.register::<Health>();
+
+        {
+            let mut column = Column::with_allocator(registry.info(id), counter.clone());
+            for v in 0..500u32 {
+                let value = Health(v);
+                unsafe { column.push((&value as *const Health).cast(), 0) };
+                std::mem::forget(value);
+            }
+            assert_eq!(counter.live.load(Ordering::SeqCst), 1); // one grown buffer, not one per push
+        }
+        assert_eq!(counter.live.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,92 @@
+//! Pluggable raw memory allocation for `Column`, so a table's backing storage can come from an
+//! arena/bump allocator (better locality, bulk free) instead of always the global allocator.
+//!
+//! `Column::new`/`Table::new` still default to `GlobalAllocator`, so this is opt-in: only a
+//! caller that explicitly reaches for `Column::with_allocator`/`Table::with_allocator` (e.g. a
+//! future per-world arena) pays for the indirection of a vtable call per grow/dealloc.
+
+use std::alloc::{self, Layout};
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// A source of raw, layout-described memory for a `Column`'s backing buffer.
+///
+/// Mirrors `std::alloc::GlobalAlloc`'s contract rather than reusing that trait directly, since
+/// callers here only ever need `alloc`/`realloc`/`dealloc` (no `alloc_zeroed`) and `Column`
+/// already carries its own zero-size short-circuit, so a `Layout` passed here always has a
+/// nonzero size.
+///
+/// # Safety
+/// Implementations must return either a null pointer or one valid for `layout`, and `realloc`
+/// must preserve the first `old_layout.size().min(new_size)` bytes, matching
+/// `std::alloc::GlobalAlloc`'s requirements.
+pub unsafe trait MemAllocator: Send + Sync {
+    /// Allocates `layout`'s worth of uninitialized memory, or returns null on failure.
+    fn alloc(&self, layout: Layout) -> *mut u8;
+
+    /// Grows or shrinks a previous `alloc`/`realloc` allocation (`ptr`, `old_layout`) to
+    /// `new_size` bytes, or returns null on failure (in which case `ptr` is still valid).
+    ///
+    /// # Safety
+    /// `ptr` must currently be allocated via this allocator with `old_layout`.
+    unsafe fn realloc(&self, ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8;
+
+    /// Frees a previous `alloc`/`realloc` allocation.
+    ///
+    /// # Safety
+    /// `ptr` must currently be allocated via this allocator with `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// The default `MemAllocator`, forwarding straight to `std::alloc`'s global allocator — the
+/// same calls `Column` made directly before this module existed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GlobalAllocator;
+
+// SAFETY: forwards verbatim to `std::alloc`'s global functions, which uphold the contract.
+unsafe impl MemAllocator for GlobalAllocator {
+    fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { alloc::alloc(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe { alloc::realloc(ptr, old_layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { alloc::dealloc(ptr, layout) };
+    }
+}
+
+/// A shared handle to a `MemAllocator`, cheap to clone into every `Column` of a `Table` that
+/// opts into a non-default allocator.
+pub type SharedAllocator = Arc<dyn MemAllocator>;
+
+/// The default `SharedAllocator`: the global allocator, wrapped for `Column::new`/`Table::new`.
+pub fn global() -> SharedAllocator {
+    Arc::new(GlobalAllocator)
+}
+
+/// A dangling pointer for a zero-sized layout, aligned to `layout`'s alignment rather than
+/// just any nonnull address — shared by `Column` so it doesn't need its own copy.
+pub fn dangling_for(layout: Layout) -> NonNull<u8> {
+    if layout.size() == 0 {
+        NonNull::new(layout.align() as *mut u8).expect("a `Layout`'s alignment is never zero")
+    } else {
+        NonNull::dangling()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_allocator_round_trips_a_layout() {
+        let allocator = GlobalAllocator;
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null());
+        unsafe { allocator.dealloc(ptr, layout) };
+    }
+}
@@ -0,0 +1,14 @@
+//! Dense, archetype-based component storage.
+
+pub mod archetype;
+pub mod column;
+pub mod mem;
+#[cfg(feature = "packed-storage")]
+pub mod packed;
+pub mod table;
+
+pub use archetype::{ArchetypeId, Archetypes, MemoryReport};
+pub use mem::{GlobalAllocator, MemAllocator, SharedAllocator};
+#[cfg(feature = "packed-storage")]
+pub use packed::{ColumnLayout, PackedTable};
+pub use table::Table;
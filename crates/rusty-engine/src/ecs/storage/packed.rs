@@ -0,0 +1,328 @@
+//! `PackedTable`: an experimental Legion-style archetype layout that keeps every column's
+//! data in one allocation instead of `Table`'s one-`Column`-per-component layout.
+//!
+//! Prototype only, gated behind the `packed-storage` feature so it can be benchmarked
+//! against `Table` (see `bench::scenario::PackedVsTable`) without becoming the default —
+//! `World` still uses `Table` unconditionally.
+
+use crate::ecs::component::{ComponentId, Registry};
+use crate::ecs::entity::Entity;
+use std::alloc::{self, Layout};
+use std::collections::HashMap;
+use std::ptr::NonNull;
+
+struct ColumnSlot {
+    offset: usize,
+    layout: Layout,
+    drop_fn: Option<unsafe fn(*mut u8)>,
+}
+
+/// One column's placement within a `PackedTable`'s row, as reported by `PackedTable::layout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnLayout {
+    pub id: ComponentId,
+    pub offset: usize,
+    pub size: usize,
+    pub align: usize,
+}
+
+/// Dense storage for one archetype where every row is a single contiguous chunk (all of
+/// that entity's components back-to-back), rather than one chunk per column.
+///
+/// Trades `Table`'s "grow each column independently" flexibility for row-major locality:
+/// reading several components of the same entity touches one cache line's worth of memory
+/// instead of one per component.
+pub struct PackedTable {
+    entities: Vec<Entity>,
+    data: NonNull<u8>,
+    capacity: usize,
+    stride: usize,
+    row_align: usize,
+    columns: Vec<ColumnSlot>,
+    offsets: HashMap<ComponentId, usize>,
+}
+
+// SAFETY: `PackedTable` only stores `Send + Sync` component bytes (enforced by `Component`
+// bounds at the `World`/`Table` layer above it; this prototype makes the same assumption).
+unsafe impl Send for PackedTable {}
+unsafe impl Sync for PackedTable {}
+
+impl PackedTable {
+    /// Lays columns out in descending alignment order rather than `ids`' given order, so a
+    /// mix of alignments (e.g. `u8` and `u64` components) doesn't leave interior padding
+    /// between two small fields that a big one, placed first, would have absorbed instead.
+    /// `Layout::extend` already inserts whatever padding each field needs to stay aligned in
+    /// either order — this only affects how much of that padding ends up wasted, not
+    /// correctness.
+    pub fn new(registry: &Registry, ids: &[ComponentId]) -> Self {
+        let mut ids: Vec<ComponentId> = ids.to_vec();
+        ids.sort_by_key(|&id| std::cmp::Reverse(registry.info(id).layout().align()));
+
+        let mut layout = Layout::from_size_align(0, 1).unwrap();
+        let mut columns = Vec::with_capacity(ids.len());
+        let mut offsets = HashMap::with_capacity(ids.len());
+        for &id in &ids {
+            let info = registry.info(id);
+            let (extended, offset) = layout.extend(info.layout()).expect("packed row layout overflow");
+            layout = extended;
+            offsets.insert(id, columns.len());
+            columns.push(ColumnSlot {
+                offset,
+                layout: info.layout(),
+                drop_fn: info.drop_fn(),
+            });
+        }
+        let layout = layout.pad_to_align();
+
+        Self {
+            entities: Vec::new(),
+            data: NonNull::dangling(),
+            capacity: 0,
+            stride: layout.size(),
+            row_align: layout.align(),
+            columns,
+            offsets,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn entity(&self, row: usize) -> Entity {
+        self.entities[row]
+    }
+
+    pub fn has_column(&self, id: ComponentId) -> bool {
+        self.offsets.contains_key(&id)
+    }
+
+    /// The size in bytes of one row, padding included — e.g. for comparing how much a given
+    /// column order wastes to alignment.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Every column's placement within a row, sorted by offset — the layout `new` actually
+    /// chose (by descending alignment), for debugging or benchmarking padding waste rather
+    /// than fast-path access.
+    pub fn layout(&self) -> Vec<ColumnLayout> {
+        let mut layout: Vec<ColumnLayout> = self
+            .offsets
+            .iter()
+            .map(|(&id, &column)| {
+                let slot = &self.columns[column];
+                ColumnLayout {
+                    id,
+                    offset: slot.offset,
+                    size: slot.layout.size(),
+                    align: slot.layout.align(),
+                }
+            })
+            .collect();
+        layout.sort_by_key(|column| column.offset);
+        layout
+    }
+
+    fn array_layout(&self, count: usize) -> Layout {
+        let size = self.stride.checked_mul(count).expect("packed table allocation overflow");
+        Layout::from_size_align(size, self.row_align.max(1)).expect("invalid packed table layout")
+    }
+
+    fn grow(&mut self, min_capacity: usize) {
+        if self.stride == 0 {
+            self.capacity = usize::MAX;
+            return;
+        }
+        let new_capacity = (self.capacity.max(1) * 2).max(min_capacity);
+        let new_layout = self.array_layout(new_capacity);
+        let new_data = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(new_layout)
+            } else {
+                let old_layout = self.array_layout(self.capacity);
+                alloc::realloc(self.data.as_ptr(), old_layout, new_layout.size())
+            }
+        };
+        self.data = NonNull::new(new_data).unwrap_or_else(|| alloc::handle_alloc_error(new_layout));
+        self.capacity = new_capacity;
+    }
+
+    fn row_ptr(&self, row: usize) -> *mut u8 {
+        unsafe { self.data.as_ptr().add(row * self.stride) }
+    }
+
+    /// Writes one component's value into the pending row (`len`), ahead of `finish_push`.
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid, initialized value matching column `id`'s component
+    /// type, and ownership transfers into the table.
+    pub unsafe fn write_component(&mut self, id: ComponentId, ptr: *const u8) {
+        if self.len() == self.capacity {
+            self.grow(self.len() + 1);
+        }
+        let &column = self.offsets.get(&id).expect("value for unknown column");
+        let slot = &self.columns[column];
+        let dst = self.row_ptr(self.len()).add(slot.offset);
+        std::ptr::copy_nonoverlapping(ptr, dst, slot.layout.size());
+    }
+
+    /// Appends `entity`'s row, assuming every column has already received its value via
+    /// `write_component`. Returns the new row index.
+    pub fn finish_push(&mut self, entity: Entity) -> usize {
+        self.entities.push(entity);
+        self.entities.len() - 1
+    }
+
+    pub fn get(&self, id: ComponentId, row: usize) -> Option<*const u8> {
+        if row >= self.len() {
+            return None;
+        }
+        let &column = self.offsets.get(&id)?;
+        Some(unsafe { self.row_ptr(row).add(self.columns[column].offset) as *const u8 })
+    }
+
+    pub fn get_mut(&mut self, id: ComponentId, row: usize) -> Option<*mut u8> {
+        if row >= self.len() {
+            return None;
+        }
+        let &column = self.offsets.get(&id)?;
+        Some(unsafe { self.row_ptr(row).add(self.columns[column].offset) })
+    }
+
+    /// Removes `row`, dropping its component values. Returns the entity that was moved into
+    /// `row` to fill the gap, if any.
+    ///
+    /// Unlike `Table`, which must swap-remove each column separately, this is a single
+    /// `stride`-sized copy since every column for a row lives together.
+    pub fn swap_remove(&mut self, row: usize) -> Option<Entity> {
+        assert!(row < self.entities.len());
+        let last = self.entities.len() - 1;
+        unsafe {
+            let removed = self.row_ptr(row);
+            for slot in &self.columns {
+                if let Some(drop_fn) = slot.drop_fn {
+                    drop_fn(removed.add(slot.offset));
+                }
+            }
+            if row != last {
+                let src = self.row_ptr(last);
+                std::ptr::copy_nonoverlapping(src, removed, self.stride);
+            }
+        }
+        self.entities.swap_remove(row);
+        self.entities.get(row).copied()
+    }
+}
+
+impl Drop for PackedTable {
+    fn drop(&mut self) {
+        for row in 0..self.entities.len() {
+            let ptr = self.row_ptr(row);
+            for slot in &self.columns {
+                if let Some(drop_fn) = slot.drop_fn {
+                    unsafe { drop_fn(ptr.add(slot.offset)) };
+                }
+            }
+        }
+        if self.capacity > 0 && self.stride > 0 {
+            let layout = self.array_layout(self.capacity);
+            unsafe { alloc::dealloc(self.data.as_ptr(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::entity::Entities;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Velocity(f32, f32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Flag(u8);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Tag(u8);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Big(u64);
+
+    /// Registering (and requesting) columns in the worst order for naive layout — small,
+    /// big, small — should still end up packed as if `Big` came first: every offset aligned,
+    /// and no interior padding wasted between the two `u8`s and `Big`.
+    #[test]
+    fn columns_are_reordered_by_alignment_to_minimize_padding() {
+        let mut registry = Registry::new();
+        let flag = registry.register::<Flag>();
+        let big = registry.register::<Big>();
+        let tag = registry.register::<Tag>();
+
+        let table = PackedTable::new(&registry, &[flag, big, tag]);
+
+        for column in table.layout() {
+            assert_eq!(column.offset % column.align, 0, "column {:?} is misaligned", column.id);
+        }
+
+        // Sorted order (Big, Flag, Tag): offsets 0, 8, 9, padded to the next multiple of 8 —
+        // 16 total, wasting nothing but the unavoidable trailing pad. The naive registration
+        // order (Flag, Big, Tag) would waste 7 interior bytes aligning `Big` up from offset 1.
+        assert_eq!(table.stride(), 16);
+
+        let big_layout = table.layout().into_iter().find(|column| column.id == big).unwrap();
+        assert_eq!(big_layout.offset, 0);
+    }
+
+    #[test]
+    fn push_and_swap_remove() {
+        let mut registry = Registry::new();
+        let pos = registry.register::<Position>();
+        let vel = registry.register::<Velocity>();
+        let mut table = PackedTable::new(&registry, &[pos, vel]);
+
+        let mut entities = Entities::new();
+        let e0 = entities.alloc();
+        let e1 = entities.alloc();
+
+        // SAFETY: `Position`/`Velocity` have no drop glue, so letting `p0`/`v0`/`p1`/`v1`
+        // also go out of scope normally (rather than forgetting them) is harmless even
+        // though `table` now owns copies too.
+        unsafe {
+            let p0 = Position(1.0, 1.0);
+            table.write_component(pos, (&p0 as *const Position).cast());
+            let v0 = Velocity(0.1, 0.1);
+            table.write_component(vel, (&v0 as *const Velocity).cast());
+            table.finish_push(e0);
+
+            let p1 = Position(2.0, 2.0);
+            table.write_component(pos, (&p1 as *const Position).cast());
+            let v1 = Velocity(0.2, 0.2);
+            table.write_component(vel, (&v1 as *const Velocity).cast());
+            table.finish_push(e1);
+        }
+
+        assert_eq!(table.len(), 2);
+        let moved = table.swap_remove(0);
+        assert_eq!(moved, Some(e1));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.entity(0), e1);
+
+        let pos_ptr = table.get(pos, 0).unwrap() as *const Position;
+        assert_eq!(unsafe { &*pos_ptr }, &Position(2.0, 2.0));
+        let vel_ptr = table.get(vel, 0).unwrap() as *const Velocity;
+        assert_eq!(unsafe { &*vel_ptr }, &Velocity(0.2, 0.2));
+    }
+}
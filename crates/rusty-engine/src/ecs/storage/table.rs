@@ -0,0 +1,439 @@
+//! A `Table` holds every entity (and their component data) that shares one archetype.
+
+use crate::ecs::component::{ComponentId, ComponentMask, Registry};
+use crate::ecs::entity::Entity;
+use crate::ecs::storage::column::{Column, FillColumnError, NotCloneable};
+use crate::ecs::storage::mem::SharedAllocator;
+use std::collections::HashMap;
+
+/// Dense, column-oriented storage for all entities of a single archetype.
+///
+/// Row `i` in every column, plus `entities[i]`, together describe one entity. Rows are
+/// kept packed by swap-removal, so a table never has holes.
+pub struct Table {
+    entities: Vec<Entity>,
+    columns: HashMap<ComponentId, Column>,
+    mask: ComponentMask,
+    /// Columns that have received a value (via `write_component` or a shared-column move
+    /// inside `move_row`) for the row currently being built, checked against `columns.len()`
+    /// by `finish_push` before the row counts as committed. Catches a caller that forgot a
+    /// column (a `Set::take` that missed an id, a hand-rolled migration) as a clean panic
+    /// right there instead of as an uninitialized read whenever that row is next visited —
+    /// in release builds too, since the cost of one `assert_eq!` per row push is nothing
+    /// next to what a garbage read into unrelated heap bytes could do.
+    pending_writes: usize,
+}
+
+impl Table {
+    pub fn new(registry: &Registry, ids: &[ComponentId]) -> Self {
+        let columns = ids
+            .iter()
+            .map(|&id| (id, Column::new(registry.info(id))))
+            .collect();
+        Self {
+            entities: Vec::new(),
+            columns,
+            mask: ComponentMask::from_ids(ids.iter().copied()),
+            pending_writes: 0,
+        }
+    }
+
+    /// Like `new`, but every column draws its backing buffer from `allocator` instead of the
+    /// global allocator — e.g. a per-world arena for better locality and bulk free.
+    pub fn with_allocator(registry: &Registry, ids: &[ComponentId], allocator: SharedAllocator) -> Self {
+        let columns = ids
+            .iter()
+            .map(|&id| (id, Column::with_allocator(registry.info(id), allocator.clone())))
+            .collect();
+        Self {
+            entities: Vec::new(),
+            columns,
+            mask: ComponentMask::from_ids(ids.iter().copied()),
+            pending_writes: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn entity(&self, row: usize) -> Entity {
+        self.entities[row]
+    }
+
+    pub fn has_column(&self, id: ComponentId) -> bool {
+        self.columns.contains_key(&id)
+    }
+
+    /// Whether this table has every column named by `mask` — a query's component set AND'd
+    /// against this table's, one word at a time, instead of one `has_column` lookup per id.
+    pub fn matches(&self, mask: &ComponentMask) -> bool {
+        self.mask.contains_all(mask)
+    }
+
+    pub fn column(&self, id: ComponentId) -> Option<&Column> {
+        self.columns.get(&id)
+    }
+
+    pub fn column_mut(&mut self, id: ComponentId) -> Option<&mut Column> {
+        self.columns.get_mut(&id)
+    }
+
+    /// Writes one component's value into its column, ahead of `finish_push`.
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid, initialized value matching column `id`'s component
+    /// type, and ownership transfers into the table.
+    pub unsafe fn write_component(&mut self, id: ComponentId, ptr: *const u8, tick: u64) {
+        let column = self.columns.get_mut(&id).expect("value for unknown column");
+        column.push(ptr, tick);
+        self.pending_writes += 1;
+    }
+
+    /// Appends `entity`'s row, assuming every column has already received its value via
+    /// `write_component` (or, for a column shared with another table, `move_row`'s own
+    /// move). Returns the new row index.
+    ///
+    /// # Panics
+    /// Panics if fewer columns received a value than this table has, rather than pushing
+    /// `entity` onto a row some column never got filled in for — see `pending_writes`'s doc
+    /// comment.
+    pub fn finish_push(&mut self, entity: Entity) -> usize {
+        assert_eq!(
+            self.pending_writes,
+            self.columns.len(),
+            "Table::finish_push: only {} of {} columns were written for this row",
+            self.pending_writes,
+            self.columns.len()
+        );
+        self.pending_writes = 0;
+        self.entities.push(entity);
+        self.entities.len() - 1
+    }
+
+    /// Bulk-appends `entities.len()` rows in one contiguous copy into column `id`, for
+    /// mass-spawning entities that all carry just that single component (see
+    /// `World::spawn_column`). Returns the row the first new entity landed on.
+    ///
+    /// # Safety
+    /// `id` must name this table's only column, `C` must match its layout, and
+    /// `values.len()` must equal `entities.len()` — see `Column::extend`'s safety
+    /// requirements, which this forwards to.
+    pub unsafe fn extend_column<C>(&mut self, id: ComponentId, values: Vec<C>, entities: &[Entity], tick: u64) -> usize {
+        debug_assert_eq!(values.len(), entities.len());
+        let start = self.entities.len();
+        let column = self.columns.get_mut(&id).expect("value for unknown column");
+        column.extend(values, tick);
+        self.entities.extend_from_slice(entities);
+        start
+    }
+
+    /// Overwrites column `id`'s values in row order via `Column::fill`, dropping each old
+    /// value exactly once — the bulk counterpart to looping `get_mut` over every row, for a
+    /// system that recomputes a whole column at once instead of per entity (e.g. resetting
+    /// every entity's `Visible` flag for the frame).
+    ///
+    /// # Safety
+    /// `id` must name one of this table's columns and `C` must match its layout — see
+    /// `Column::fill`'s safety requirements, which this forwards to.
+    pub unsafe fn fill_column<C>(&mut self, id: ComponentId, values: impl Iterator<Item = C>) -> Result<(), FillColumnError> {
+        let column = self.columns.get_mut(&id).expect("value for unknown column");
+        unsafe { column.fill(values) }
+    }
+
+    /// Swaps rows `a` and `b` across every column and the entities vector, e.g. to reorder
+    /// entities within an archetype by some component key without touching which archetype
+    /// they belong to. Updates nothing else — a caller sorting a whole table this way is
+    /// responsible for fixing up each affected entity's recorded `Location` afterward.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for column in self.columns.values_mut() {
+            column.swap(a, b);
+        }
+        self.entities.swap(a, b);
+    }
+
+    /// Removes `row`, dropping its component values. Returns the entity that was moved into
+    /// `row` to fill the gap, if any.
+    pub fn swap_remove(&mut self, row: usize) -> Option<Entity> {
+        for column in self.columns.values_mut() {
+            column.swap_remove_and_drop(row);
+        }
+        self.entities.swap_remove(row);
+        self.entities.get(row).copied()
+    }
+
+    /// Moves row `row` into `dest`: every column `self` and `dest` share has its value moved
+    /// (not copied-and-dropped) across, `entity` is appended to `dest`, and `row` is then
+    /// removed from `self`. A column `self` has but `dest` doesn't (e.g. the component being
+    /// removed) is left untouched here — callers extract that value themselves first, e.g.
+    /// via `Column::swap_remove_into`, before calling this. Returns the entity swapped into
+    /// `row` to fill the gap (if any) and the row `entity` landed on in `dest`.
+    ///
+    /// # Safety
+    /// Every column `self` and `dest` share by id must hold the same component type.
+    pub unsafe fn move_row(&mut self, row: usize, entity: Entity, dest: &mut Table, tick: u64) -> (Option<Entity>, usize) {
+        for (id, column) in &mut self.columns {
+            if let Some(dest_column) = dest.columns.get_mut(id) {
+                column.move_into(row, dest_column, tick);
+                dest.pending_writes += 1;
+            }
+        }
+        let dest_row = dest.finish_push(entity);
+        self.entities.swap_remove(row);
+        (self.entities.get(row).copied(), dest_row)
+    }
+
+    /// Like `move_row`, but `self` and `dest` don't share column ids — `id_pairs` names, for
+    /// each column this row has in `self`, the id it should land under in `dest`. For
+    /// `World::merge` splicing a donor world whose component types were re-registered under
+    /// different ids in `dest`'s registry, so a plain id-keyed lookup the way `move_row` does
+    /// it wouldn't find the matching column. Every value is moved (not copied-and-dropped)
+    /// the same way `move_row`'s shared columns are. Returns the entity swapped into `row` to
+    /// fill the gap (if any) and the row `entity` landed on in `dest`.
+    ///
+    /// # Safety
+    /// For every `(source_id, dest_id)` pair in `id_pairs`, `self`'s `source_id` column and
+    /// `dest`'s `dest_id` column must hold the same component type.
+    pub unsafe fn move_row_remap(&mut self, row: usize, entity: Entity, dest: &mut Table, id_pairs: &[(ComponentId, ComponentId)], tick: u64) -> (Option<Entity>, usize) {
+        for &(source_id, dest_id) in id_pairs {
+            let column = self.columns.get_mut(&source_id).expect("id_pairs' source id is one of this table's own columns");
+            let dest_column = dest.columns.get_mut(&dest_id).expect("id_pairs' dest id is one of dest's own columns");
+            column.move_into(row, dest_column, tick);
+            dest.pending_writes += 1;
+        }
+        let dest_row = dest.finish_push(entity);
+        self.entities.swap_remove(row);
+        (self.entities.get(row).copied(), dest_row)
+    }
+
+    /// Moves every row of `self` into `dest` in one pass: each column both tables share has
+    /// its whole column moved via `Column::append` instead of one `move_row` per entity, and
+    /// `self`'s entities are appended to `dest`'s. A column `self` has but `dest` doesn't
+    /// (a component being bulk-removed) has its rows dropped instead — the whole-table
+    /// mirror of `move_row`'s per-row `swap_remove_into`. A column `dest` has but `self`
+    /// doesn't (a component being bulk-added) is left for the caller to fill in afterward,
+    /// the same as `move_row` leaves it to `write_component` first. `self` ends up empty.
+    /// Returns the row the first moved entity landed on in `dest`.
+    ///
+    /// # Safety
+    /// Every column `self` and `dest` share by id must hold the same component type.
+    pub unsafe fn move_all(&mut self, dest: &mut Table) -> usize {
+        let start = dest.entities.len();
+        for (id, column) in &mut self.columns {
+            match dest.columns.get_mut(id) {
+                Some(dest_column) => dest_column.append(column),
+                None => {
+                    for row in (0..column.len()).rev() {
+                        column.swap_remove_and_drop(row);
+                    }
+                }
+            }
+        }
+        dest.entities.append(&mut self.entities);
+        start
+    }
+
+    pub fn column_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.columns.keys().copied()
+    }
+
+    /// Total bytes this table has allocated: every column's `Column::memory_usage` plus the
+    /// `entities` vec's own allocation. For `Archetypes::memory_report`.
+    pub fn memory_usage(&self) -> usize {
+        self.columns.values().map(Column::memory_usage).sum::<usize>() + self.entities.capacity() * std::mem::size_of::<Entity>()
+    }
+
+    /// Like `memory_usage`, but counting only occupied rows — the live-data numerator for
+    /// surfacing how much of `memory_usage` is unused capacity.
+    pub fn live_bytes(&self) -> usize {
+        self.columns.values().map(Column::live_bytes).sum::<usize>() + self.entities.len() * std::mem::size_of::<Entity>()
+    }
+
+    /// Deep-copies every column via `Column::try_clone`, for `Archetypes::try_clone` to
+    /// build an independent copy of a `World`'s data. Fails with the first non-cloneable
+    /// component encountered (columns are visited in arbitrary `HashMap` order).
+    pub fn try_clone(&self) -> Result<Table, NotCloneable> {
+        let columns = self.columns.iter().map(|(&id, column)| Ok((id, column.try_clone()?))).collect::<Result<_, NotCloneable>>()?;
+        Ok(Table {
+            entities: self.entities.clone(),
+            columns,
+            mask: self.mask.clone(),
+            pending_writes: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Pos(f32, f32);
+
+    #[test]
+    fn push_and_swap_remove() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Pos>();
+        let mut table = Table::new(&registry, &[id]);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let e0 = entities.alloc();
+        let e1 = entities.alloc();
+
+        // SAFETY: `Pos` has no drop glue, so letting `p0`/`p1` also go out of scope normally
+        // (rather than forgetting them) is harmless even though `table` now owns copies too.
+        unsafe {
+            let p0 = Pos(1.0, 1.0);
+            table.write_component(id, (&p0 as *const Pos).cast(), 0);
+            table.finish_push(e0);
+            let p1 = Pos(2.0, 2.0);
+            table.write_component(id, (&p1 as *const Pos).cast(), 0);
+            table.finish_push(e1);
+        }
+
+        assert_eq!(table.len(), 2);
+        let moved = table.swap_remove(0);
+        assert_eq!(moved, Some(e1));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.entity(0), e1);
+
+        let ptr = table.column(id).unwrap().get(0).unwrap() as *const Pos;
+        assert_eq!(unsafe { &*ptr }, &Pos(2.0, 2.0));
+    }
+
+    #[derive(Component)]
+    struct Vel(#[allow(dead_code)] f32, #[allow(dead_code)] f32);
+
+    #[test]
+    #[should_panic(expected = "Table::finish_push: only 1 of 2 columns were written")]
+    fn finish_push_panics_when_a_column_was_never_written() {
+        let mut registry = Registry::new();
+        let pos = registry.register::<Pos>();
+        let vel = registry.register::<Vel>();
+        let mut table = Table::new(&registry, &[pos, vel]);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let e = entities.alloc();
+        unsafe {
+            let p = Pos(1.0, 1.0);
+            // Deliberately never writes `vel`'s column — the migration bug this guards
+            // against (a `Set::take`, or a hand-rolled migration, that misses an id).
+            table.write_component(pos, (&p as *const Pos).cast(), 0);
+        }
+        table.finish_push(e);
+    }
+
+    #[test]
+    fn fill_column_overwrites_every_row_in_order() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Pos>();
+        let mut table = Table::new(&registry, &[id]);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        for i in 0..3 {
+            let e = entities.alloc();
+            unsafe {
+                let p = Pos(i as f32, i as f32);
+                table.write_component(id, (&p as *const Pos).cast(), 0);
+                table.finish_push(e);
+            }
+        }
+
+        unsafe { table.fill_column(id, [Pos(9.0, 9.0), Pos(8.0, 8.0), Pos(7.0, 7.0)].into_iter()) }.unwrap();
+
+        for (row, expected) in [Pos(9.0, 9.0), Pos(8.0, 8.0), Pos(7.0, 7.0)].into_iter().enumerate() {
+            let ptr = table.column(id).unwrap().get(row).unwrap() as *const Pos;
+            assert_eq!(unsafe { &*ptr }, &expected);
+        }
+    }
+
+    #[test]
+    fn move_all_moves_every_row_and_leaves_the_source_empty() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Pos>();
+        let mut src = Table::new(&registry, &[id]);
+        let mut dest = Table::new(&registry, &[id]);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let e0 = entities.alloc();
+        let existing = entities.alloc();
+
+        unsafe {
+            let p = Pos(9.0, 9.0);
+            dest.write_component(id, (&p as *const Pos).cast(), 0);
+            dest.finish_push(existing);
+
+            let p0 = Pos(1.0, 1.0);
+            src.write_component(id, (&p0 as *const Pos).cast(), 0);
+            src.finish_push(e0);
+        }
+
+        let start = unsafe { src.move_all(&mut dest) };
+
+        assert_eq!(start, 1);
+        assert_eq!(src.len(), 0);
+        assert_eq!(dest.len(), 2);
+        assert_eq!(dest.entity(1), e0);
+        let ptr = dest.column(id).unwrap().get(1).unwrap() as *const Pos;
+        assert_eq!(unsafe { &*ptr }, &Pos(1.0, 1.0));
+    }
+
+    #[test]
+    fn memory_usage_is_at_least_live_bytes_and_grows_with_pushed_rows() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Pos>();
+        let mut table = Table::new(&registry, &[id]);
+        assert_eq!(table.memory_usage(), 0);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        for i in 0..8 {
+            let e = entities.alloc();
+            unsafe {
+                let p = Pos(i as f32, i as f32);
+                table.write_component(id, (&p as *const Pos).cast(), 0);
+                table.finish_push(e);
+            }
+        }
+
+        assert!(table.memory_usage() >= table.live_bytes());
+        assert!(table.memory_usage() > 0);
+    }
+
+    #[test]
+    fn swap_rows_reorders_columns_and_entities_together() {
+        let mut registry = Registry::new();
+        let id = registry.register::<Pos>();
+        let mut table = Table::new(&registry, &[id]);
+
+        let mut entities = crate::ecs::entity::Entities::new();
+        let e0 = entities.alloc();
+        let e1 = entities.alloc();
+
+        unsafe {
+            let p0 = Pos(1.0, 1.0);
+            table.write_component(id, (&p0 as *const Pos).cast(), 0);
+            table.finish_push(e0);
+            let p1 = Pos(2.0, 2.0);
+            table.write_component(id, (&p1 as *const Pos).cast(), 0);
+            table.finish_push(e1);
+        }
+
+        table.swap_rows(0, 1);
+
+        assert_eq!(table.entity(0), e1);
+        assert_eq!(table.entity(1), e0);
+        let ptr = table.column(id).unwrap().get(0).unwrap() as *const Pos;
+        assert_eq!(unsafe { &*ptr }, &Pos(2.0, 2.0));
+        let ptr = table.column(id).unwrap().get(1).unwrap() as *const Pos;
+        assert_eq!(unsafe { &*ptr }, &Pos(1.0, 1.0));
+    }
+}
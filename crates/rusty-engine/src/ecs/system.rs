@@ -0,0 +1,169 @@
+//! Systems: units of logic that run against a `World`.
+
+use crate::ecs::world::World;
+
+/// A unit of scheduled logic. Most systems produce no output (`Out = ()`) and can be added
+/// straight to a `Phase`; systems with a non-`()` output are run one-off via
+/// `World::run_system` (or piped into another system, see `IntoSystem::pipe`).
+pub trait System: Send + 'static {
+    type Out;
+
+    fn run(&mut self, world: &mut World) -> Self::Out;
+}
+
+/// Converts a value (typically a plain function or closure) into a `System`.
+pub trait IntoSystem<Marker> {
+    type System: System;
+
+    fn into_system(self) -> Self::System;
+
+    /// Pipes this system's output into `next`, which receives it as an extra `In` argument
+    /// alongside `&mut World` (see `PipedSystem`). The combined system's `Out` is `next`'s
+    /// output — e.g. `detect_collisions.pipe(resolve_collisions)` where the first returns a
+    /// `Vec<Pair>` and the second takes one as its first parameter.
+    fn pipe<N, MarkerB>(self, next: N) -> PipeSystem<Self::System, N::System>
+    where
+        Self: Sized,
+        N: IntoPipedSystem<MarkerB, <Self::System as System>::Out>,
+    {
+        PipeSystem {
+            first: self.into_system(),
+            second: next.into_piped_system(),
+        }
+    }
+}
+
+/// A `System` built from a `FnMut(&mut World) -> R`.
+pub struct FunctionSystem<F> {
+    func: F,
+}
+
+impl<F, R> System for FunctionSystem<F>
+where
+    F: FnMut(&mut World) -> R + Send + 'static,
+{
+    type Out = R;
+
+    fn run(&mut self, world: &mut World) -> R {
+        (self.func)(world)
+    }
+}
+
+/// Marker type for the blanket `FnMut(&mut World) -> R` `IntoSystem` impl.
+pub struct IsFunctionSystem;
+
+impl<F, R> IntoSystem<IsFunctionSystem> for F
+where
+    F: FnMut(&mut World) -> R + Send + 'static,
+{
+    type System = FunctionSystem<F>;
+
+    fn into_system(self) -> Self::System {
+        FunctionSystem { func: self }
+    }
+}
+
+/// A system that also consumes a value piped in from a prior system's output (see
+/// `IntoSystem::pipe`), in addition to the `&mut World` every system already gets. Modeled
+/// after `System`, but with the extra `In` parameter `run` needs — a plain closure over
+/// just `&mut World` can't accept the piped value, hence the separate trait rather than
+/// widening `System` itself.
+pub trait PipedSystem<In>: Send + 'static {
+    type Out;
+
+    fn run(&mut self, world: &mut World, input: In) -> Self::Out;
+}
+
+/// A `PipedSystem` built from a `FnMut(&mut World, In) -> R`.
+pub struct FunctionPipedSystem<F> {
+    func: F,
+}
+
+impl<F, In, R> PipedSystem<In> for FunctionPipedSystem<F>
+where
+    F: FnMut(&mut World, In) -> R + Send + 'static,
+    In: 'static,
+{
+    type Out = R;
+
+    fn run(&mut self, world: &mut World, input: In) -> R {
+        (self.func)(world, input)
+    }
+}
+
+/// Converts a value (typically a plain function or closure) into a `PipedSystem`, the
+/// piping counterpart to `IntoSystem`.
+pub trait IntoPipedSystem<Marker, In> {
+    type System: PipedSystem<In>;
+
+    fn into_piped_system(self) -> Self::System;
+}
+
+/// Marker type for the blanket `FnMut(&mut World, In) -> R` `IntoPipedSystem` impl.
+pub struct IsFunctionPipedSystem;
+
+impl<F, In, R> IntoPipedSystem<IsFunctionPipedSystem, In> for F
+where
+    F: FnMut(&mut World, In) -> R + Send + 'static,
+    In: 'static,
+{
+    type System = FunctionPipedSystem<F>;
+
+    fn into_piped_system(self) -> Self::System {
+        FunctionPipedSystem { func: self }
+    }
+}
+
+/// A `System` built by `IntoSystem::pipe`: runs `first`, then hands its output to `second`
+/// as `second`'s piped input, returning `second`'s output.
+///
+/// Neither half declares narrower `schedule::Access` here — like any plain closure system,
+/// a `PipeSystem` defaults to `Access::Exclusive` when added to a `Phase`, so its combined
+/// access is trivially the union of whatever `first` and `second` touch (safe but
+/// pessimistic, same tradeoff `add_system` already makes for a bare closure).
+pub struct PipeSystem<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> System for PipeSystem<A, B>
+where
+    A: System,
+    B: PipedSystem<A::Out>,
+{
+    type Out = B::Out;
+
+    fn run(&mut self, world: &mut World) -> B::Out {
+        let value = self.first.run(world);
+        self.second.run(world, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_system_returning_result_forwards_output() {
+        let mut system = (|_: &mut World| -> Result<u32, &'static str> { Ok(7) }).into_system();
+        let mut world = World::new();
+        assert_eq!(system.run(&mut world), Ok(7));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct LastRoll(u32);
+    impl crate::ecs::unique::Unique for LastRoll {}
+
+    #[test]
+    fn pipe_forwards_the_producers_output_into_the_consumer() {
+        let producer = |_: &mut World| -> u32 { 7 };
+        let consumer = |world: &mut World, value: u32| {
+            world.insert_unique(LastRoll(value));
+        };
+
+        let mut world = World::new();
+        producer.pipe(consumer).run(&mut world);
+
+        assert_eq!(world.unique::<LastRoll>(), Some(&LastRoll(7)));
+    }
+}
@@ -0,0 +1,379 @@
+//! Uniques: singleton, per-type values attached to a `World` — configuration, GPU handles,
+//! and other "resource" data that isn't itself an entity's component.
+
+use crate::ecs::schedule::Mutability;
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+/// Marker trait for a `Send + Sync` singleton value, storable via `World::insert_unique`
+/// and reachable from any system, exclusive or parallel.
+///
+/// No blanket impl, mirroring `Component`, so per-type storage configuration has somewhere
+/// to live later.
+pub trait Unique: Send + Sync + 'static {}
+
+/// Marker trait for a singleton value that isn't `Send` (a raw window handle, a GPU
+/// context, an `Rc`), stored separately via `World::insert_non_send_unique`.
+///
+/// Only reachable from exclusive systems — `Phase::validate` rejects a parallel system
+/// that declares `Access::NonSend`, since the parallel group isn't guaranteed to run on
+/// the thread that owns the value.
+pub trait NonSendUnique: 'static {}
+
+/// Storage for both `Send` and non-`Send` uniques, keyed by type.
+#[derive(Default)]
+pub struct Uniques {
+    send: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    non_send: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Uniques {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<U: Unique>(&mut self, value: U) {
+        self.send.insert(TypeId::of::<U>(), Box::new(value));
+    }
+
+    pub fn get<U: Unique>(&self) -> Option<&U> {
+        self.send.get(&TypeId::of::<U>()).and_then(|value| value.downcast_ref())
+    }
+
+    pub fn get_mut<U: Unique>(&mut self) -> Option<&mut U> {
+        self.send.get_mut(&TypeId::of::<U>()).and_then(|value| value.downcast_mut())
+    }
+
+    pub fn insert_non_send<U: NonSendUnique>(&mut self, value: U) {
+        self.non_send.insert(TypeId::of::<U>(), Box::new(value));
+    }
+
+    pub fn get_non_send<U: NonSendUnique>(&self) -> Option<&U> {
+        self.non_send.get(&TypeId::of::<U>()).and_then(|value| value.downcast_ref())
+    }
+
+    pub fn get_non_send_mut<U: NonSendUnique>(&mut self) -> Option<&mut U> {
+        self.non_send.get_mut(&TypeId::of::<U>()).and_then(|value| value.downcast_mut())
+    }
+
+    /// Every unique currently stored, `Send` and non-`Send` alike, by `TypeId` — e.g. for an
+    /// editor's save workflow enumerating what a `World` holds without knowing its types up
+    /// front. There's no per-type serialize hook yet (that needs a `Uniques`-side opt-in the
+    /// same way `Registry::register_cloneable` is for components, which doesn't exist here),
+    /// so a caller that needs to serialize a unique still has to downcast it itself via
+    /// `World::unique::<U>()` once it knows `U` from this id.
+    pub fn iter_type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.send.keys().copied().chain(self.non_send.keys().copied())
+    }
+
+    /// Drops every stored unique, `Send` and non-`Send` alike.
+    pub fn clear(&mut self) {
+        self.send.clear();
+        self.non_send.clear();
+    }
+}
+
+/// A read-only borrow of a `Send` unique, e.g. `Uniq::<Score>::new(world)`.
+pub struct Uniq<'w, U: Unique> {
+    value: &'w U,
+}
+
+impl<'w, U: Unique> Uniq<'w, U> {
+    /// Returns `None` if `U` hasn't been inserted via `World::insert_unique`.
+    pub fn new(world: &'w crate::ecs::world::World) -> Option<Self> {
+        world.unique::<U>().map(|value| Self { value })
+    }
+}
+
+impl<'w, U: Unique> Deref for Uniq<'w, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+/// A mutable borrow of a `Send` unique, e.g. `UniqMut::<Score>::new(world)`.
+pub struct UniqMut<'w, U: Unique> {
+    value: &'w mut U,
+}
+
+impl<'w, U: Unique> UniqMut<'w, U> {
+    /// Returns `None` if `U` hasn't been inserted via `World::insert_unique`.
+    pub fn new(world: &'w mut crate::ecs::world::World) -> Option<Self> {
+        world.unique_mut::<U>().map(|value| Self { value })
+    }
+}
+
+impl<'w, U: Unique> Deref for UniqMut<'w, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+impl<'w, U: Unique> DerefMut for UniqMut<'w, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        self.value
+    }
+}
+
+/// One element of a `UniqSet` tuple: either a `Uniq<U>` or `UniqMut<U>`, describing which
+/// unique type it names and whether its access is mutable so `UniqSet::fetch` can check the
+/// whole tuple for conflicts before touching any value.
+///
+/// # Safety
+/// `fetch` must only read (for `MUTABLE = false`) or only exclusively borrow (for
+/// `MUTABLE = true`) the unique named by `Self::Value` out of `*uniques`; `UniqSet::fetch`'s
+/// per-tuple conflict check is what makes calling it for several elements sound.
+pub unsafe trait UniqElement<'w>: Sized {
+    type Value: Unique;
+    const MUTABLE: bool;
+
+    /// # Safety
+    /// `uniques` must be valid for `'w`, and no other live element fetched from the same
+    /// `UniqSet::fetch` call may alias `Self::Value` mutably.
+    unsafe fn fetch(uniques: *mut Uniques) -> Option<Self>;
+
+    /// This element's `(TypeId, Mutability)`, for a system to report through
+    /// `schedule::Access::Uniques` so `Phase::validate` can see the same unique/write
+    /// conflicts it already sees for components.
+    fn access() -> (TypeId, Mutability) {
+        (TypeId::of::<Self::Value>(), if Self::MUTABLE { Mutability::Write } else { Mutability::Read })
+    }
+}
+
+unsafe impl<'w, U: Unique> UniqElement<'w> for Uniq<'w, U> {
+    type Value = U;
+    const MUTABLE: bool = false;
+
+    unsafe fn fetch(uniques: *mut Uniques) -> Option<Self> {
+        let value = unsafe { &*uniques }.get::<U>()?;
+        Some(Self { value })
+    }
+}
+
+unsafe impl<'w, U: Unique> UniqElement<'w> for UniqMut<'w, U> {
+    type Value = U;
+    const MUTABLE: bool = true;
+
+    unsafe fn fetch(uniques: *mut Uniques) -> Option<Self> {
+        let value = unsafe { &mut *uniques }.get_mut::<U>()?;
+        Some(Self { value })
+    }
+}
+
+/// Two or more `UniqElement`s naming the same unique type, at least one of them mutably —
+/// `(UniqMut<Score>, UniqMut<Score>)` or `(Uniq<Score>, UniqMut<Score>)` would alias the same
+/// value, so `UniqSet::fetch` rejects them instead of returning overlapping references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniqConflict {
+    pub name: &'static str,
+}
+
+impl std::fmt::Display for UniqConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unique `{}` is requested more than once, at least one mutably", self.name)
+    }
+}
+
+impl std::error::Error for UniqConflict {}
+
+/// A tuple of `Uniq<A>`/`UniqMut<B>` elements, fetched from a `World` in one call — e.g. a
+/// system wanting several globals can take `(Uniq<A>, UniqMut<B>, Uniq<C>)` as one parameter
+/// instead of three separate world lookups.
+pub trait UniqSet<'w>: Sized {
+    /// Fetches every element, or `Ok(None)` if any named unique hasn't been inserted, or
+    /// `Err` if the tuple requests the same unique more than once with at least one mutable
+    /// access.
+    fn fetch(world: &'w mut crate::ecs::world::World) -> Result<Option<Self>, UniqConflict>;
+
+    /// Every element's `(TypeId, Mutability)`, in declaration order — pass this straight
+    /// into `schedule::Access::Uniques` when adding a system with `Phase::add_system_with_access`.
+    fn access() -> Vec<(TypeId, Mutability)>;
+}
+
+macro_rules! impl_uniq_set_for_tuple {
+    ($($name:ident),+) => {
+        impl<'w, $($name: UniqElement<'w>),+> UniqSet<'w> for ($($name,)+) {
+            fn fetch(world: &'w mut crate::ecs::world::World) -> Result<Option<Self>, UniqConflict> {
+                let mut seen: Vec<(TypeId, bool)> = Vec::new();
+                $(
+                    let type_id = TypeId::of::<$name::Value>();
+                    if let Some(&(_, prior_mutable)) = seen.iter().find(|(id, _)| *id == type_id) {
+                        if prior_mutable || $name::MUTABLE {
+                            return Err(UniqConflict { name: type_name::<$name::Value>() });
+                        }
+                    }
+                    seen.push((type_id, $name::MUTABLE));
+                )+
+                let uniques: *mut Uniques = world.uniques_mut();
+                let fetch_all = || unsafe { Some(($($name::fetch(uniques)?,)+)) };
+                Ok(fetch_all())
+            }
+
+            fn access() -> Vec<(TypeId, Mutability)> {
+                vec![$($name::access()),+]
+            }
+        }
+    };
+}
+
+impl_uniq_set_for_tuple!(A, B);
+impl_uniq_set_for_tuple!(A, B, C);
+impl_uniq_set_for_tuple!(A, B, C, D);
+
+/// A read-only borrow of a non-`Send` unique, e.g. `NonSend::<Window>::new(world)`.
+pub struct NonSend<'w, U: NonSendUnique> {
+    value: &'w U,
+}
+
+impl<'w, U: NonSendUnique> NonSend<'w, U> {
+    /// Returns `None` if `U` hasn't been inserted via `World::insert_non_send_unique`.
+    pub fn new(world: &'w crate::ecs::world::World) -> Option<Self> {
+        world.non_send_unique::<U>().map(|value| Self { value })
+    }
+}
+
+impl<'w, U: NonSendUnique> Deref for NonSend<'w, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+/// A mutable borrow of a non-`Send` unique, e.g. `NonSendMut::<Window>::new(world)`.
+pub struct NonSendMut<'w, U: NonSendUnique> {
+    value: &'w mut U,
+}
+
+impl<'w, U: NonSendUnique> NonSendMut<'w, U> {
+    /// Returns `None` if `U` hasn't been inserted via `World::insert_non_send_unique`.
+    pub fn new(world: &'w mut crate::ecs::world::World) -> Option<Self> {
+        world.non_send_unique_mut::<U>().map(|value| Self { value })
+    }
+}
+
+impl<'w, U: NonSendUnique> Deref for NonSendMut<'w, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+impl<'w, U: NonSendUnique> DerefMut for NonSendMut<'w, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::schedule::{Access, Phase};
+    use crate::ecs::world::World;
+
+    struct Score(u32);
+    impl Unique for Score {}
+
+    struct Lives(u32);
+    impl Unique for Lives {}
+
+    struct Level(u32);
+    impl Unique for Level {}
+
+    struct GpuHandle(std::rc::Rc<u32>);
+    impl NonSendUnique for GpuHandle {}
+
+    #[test]
+    fn send_unique_roundtrips() {
+        let mut uniques = Uniques::new();
+        uniques.insert(Score(3));
+        uniques.get_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(uniques.get::<Score>().unwrap().0, 4);
+    }
+
+    #[test]
+    fn iter_type_ids_reports_every_stored_unique_and_clear_drops_them_all() {
+        let mut uniques = Uniques::new();
+        uniques.insert(Score(3));
+        uniques.insert(Lives(5));
+
+        let mut ids: Vec<TypeId> = uniques.iter_type_ids().collect();
+        ids.sort();
+        let mut expected = vec![TypeId::of::<Score>(), TypeId::of::<Lives>()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        uniques.clear();
+        assert!(uniques.get::<Score>().is_none());
+        assert!(uniques.get::<Lives>().is_none());
+        assert_eq!(uniques.iter_type_ids().count(), 0);
+    }
+
+    #[test]
+    fn uniq_set_tuple_reads_two_and_mutates_one_in_a_single_system() {
+        let mut world = World::new();
+        world.insert_unique(Score(3));
+        world.insert_unique(Lives(5));
+        world.insert_unique(Level(2));
+
+        let mut phase = Phase::new("update");
+        phase.add_system(|world: &mut World| {
+            let (score, mut lives, level) = <(Uniq<Score>, UniqMut<Lives>, Uniq<Level>)>::fetch(world).unwrap().unwrap();
+            lives.0 -= score.0.min(level.0);
+        });
+        phase.run(&mut world);
+
+        assert_eq!(world.unique::<Lives>().unwrap().0, 3);
+        assert_eq!(world.unique::<Score>().unwrap().0, 3);
+        assert_eq!(world.unique::<Level>().unwrap().0, 2);
+    }
+
+    #[test]
+    fn uniq_set_tuple_rejects_the_same_unique_requested_mutably_twice() {
+        let mut world = World::new();
+        world.insert_unique(Score(3));
+
+        match <(UniqMut<Score>, UniqMut<Score>)>::fetch(&mut world) {
+            Err(err) => assert_eq!(err.name, std::any::type_name::<Score>()),
+            Ok(_) => panic!("expected UniqConflict"),
+        }
+    }
+
+    #[test]
+    fn uniq_set_tuple_yields_none_when_a_unique_is_missing() {
+        let mut world = World::new();
+        world.insert_unique(Score(3));
+
+        assert!(<(Uniq<Score>, Uniq<Lives>)>::fetch(&mut world).unwrap().is_none());
+    }
+
+    #[test]
+    fn non_send_unique_roundtrips_separately_from_send_store() {
+        let mut uniques = Uniques::new();
+        uniques.insert_non_send(GpuHandle(std::rc::Rc::new(7)));
+        assert_eq!(*uniques.get_non_send::<GpuHandle>().unwrap().0, 7);
+        assert!(uniques.get::<Score>().is_none());
+    }
+
+    #[test]
+    fn non_send_unique_readable_exclusive_but_rejected_from_parallel() {
+        let mut world = World::new();
+        world.insert_non_send_unique(GpuHandle(std::rc::Rc::new(7)));
+
+        let mut phase = Phase::new("render");
+        phase.add_exclusive(|world: &mut World| {
+            let handle = NonSend::<GpuHandle>::new(world).unwrap();
+            assert_eq!(*handle.0, 7);
+        });
+        phase.run(&mut world);
+
+        phase.add_system_with_access(|_: &mut World| {}, Access::NonSend);
+        assert!(phase.validate().is_err());
+    }
+}
@@ -0,0 +1,2025 @@
+//! The `World`: owns every entity, its component data, and the registry describing it.
+
+use crate::ecs::command::CommandBuffer;
+use crate::ecs::component::{Component, ComponentId, Registry, Set, Spec, SpecDiff};
+use crate::ecs::entity::{Entities, Entity, Location, Ref, RefMut};
+use crate::ecs::storage::archetype::{ArchetypeId, Archetypes};
+use crate::ecs::storage::column::NotCloneable;
+use crate::ecs::storage::table::Table;
+use crate::ecs::system::{IntoSystem, System};
+use crate::ecs::unique::{NonSendUnique, Unique, Uniques};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Owns all entities and component storage for one simulation.
+#[derive(Default)]
+pub struct World {
+    entities: Entities,
+    registry: Registry,
+    archetypes: Archetypes,
+    uniques: Uniques,
+    strict_mode: bool,
+    tick: u64,
+    /// Fired from `add_component`/`remove_component` whenever they actually migrate an entity.
+    /// Empty by default, so the common case of nobody watching costs one `is_empty` check.
+    on_migrated: Vec<MigrationCallback>,
+    /// Per-component observers registered via `observe_added`/`observe_removed`, fired
+    /// immediately from `spawn`/`add_component`/`remove_component`/`despawn` rather than
+    /// batched like `on_migrated`. Keyed by `ComponentId` so firing one doesn't have to walk
+    /// every observer in the world, just the ones registered for the id that actually changed.
+    observers_added: HashMap<ComponentId, Vec<ObserverCallback>>,
+    observers_removed: HashMap<ComponentId, Vec<ObserverCallback>>,
+    /// Where `defragment` will resume its next row placement — see its doc comment.
+    defrag_cursor: DefragCursor,
+    /// Caches `spawn`/`spawn_and_get`/`insert_or_spawn_at`'s component ids and destination
+    /// archetype per concrete bundle type, keyed by `TypeId::of::<S>()`. A bundle's ids and
+    /// archetype never change once computed — `S::component_ids` always registers (or finds)
+    /// the same components in the same order, and archetypes are never removed — so a second
+    /// `spawn` of the same tuple type skips `Registry::register`'s per-component lookup,
+    /// `Spec::new`'s sort/dedup/hash, and `Archetypes::get_or_create`'s spec lookup entirely.
+    spec_cache: HashMap<TypeId, BundleSpec>,
+    /// Entities queued by `queue_despawn`, oldest first — drained by `process_despawn_queue`.
+    despawn_queue: VecDeque<Entity>,
+    /// Mirrors `despawn_queue`'s contents as a set so `query::Result::advance` can reject a
+    /// queued entity in O(1) without scanning the whole deque per row.
+    pending_despawn: HashSet<Entity>,
+}
+
+/// One bundle type's precomputed `spawn` inputs — see `World::spec_cache`.
+#[derive(Clone)]
+struct BundleSpec {
+    ids: Vec<ComponentId>,
+    archetype: ArchetypeId,
+}
+
+/// Resume point for `World::defragment`'s incremental sweep: the archetype it's currently
+/// sorting and the row within that archetype it's about to place next.
+#[derive(Debug, Clone, Copy, Default)]
+struct DefragCursor {
+    archetype: usize,
+    row: usize,
+}
+
+type MigrationCallback = Box<dyn FnMut(Entity, &SpecDiff)>;
+type ObserverCallback = Box<dyn FnMut(&mut World, Entity)>;
+
+/// Error returned by `World::try_spawn` in strict mode: the `Set` it was given includes a
+/// component type that hasn't been registered yet, so it would otherwise be auto-registered
+/// silently, e.g. because of a typo in the type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnregisteredComponent {
+    pub names: Vec<&'static str>,
+}
+
+impl std::fmt::Display for UnregisteredComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unregistered component types: {}", self.names.join(", "))
+    }
+}
+
+impl std::error::Error for UnregisteredComponent {}
+
+/// One component's read/write tally, returned by `World::access_stats`.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessStats {
+    pub reads: u64,
+    pub writes: u64,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like `new`, but entity ids are reused lowest-first instead of in free/alloc order.
+    ///
+    /// Use for lockstep networking or snapshot tests where entity ids must be reproducible
+    /// across runs.
+    pub fn deterministic() -> Self {
+        Self {
+            entities: Entities::deterministic(),
+            ..Self::default()
+        }
+    }
+
+    /// Resolves `S`'s component ids and destination archetype, computing and caching them in
+    /// `spec_cache` on the first call for this concrete `S` and cloning the cached values on
+    /// every call after — see `spec_cache`'s doc comment for why that's always sound.
+    fn spawn_spec<S: Set>(&mut self) -> (Vec<ComponentId>, ArchetypeId) {
+        if let Some(cached) = self.spec_cache.get(&TypeId::of::<S>()) {
+            return (cached.ids.clone(), cached.archetype);
+        }
+        let ids = S::component_ids(&mut self.registry);
+        let spec = Spec::new(ids.clone());
+        let archetype = self.archetypes.get_or_create(&self.registry, spec);
+        self.spec_cache.insert(TypeId::of::<S>(), BundleSpec { ids: ids.clone(), archetype });
+        (ids, archetype)
+    }
+
+    /// Spawns a new entity with the given component set.
+    pub fn spawn<S: Set>(&mut self, set: S) -> Entity {
+        let (ids, archetype) = self.spawn_spec::<S>();
+        let entity = self.entities.alloc();
+        let tick = self.tick;
+
+        let table = self.archetypes.table_mut(archetype);
+        // SAFETY: each pointer `set.take` yields is written into its column immediately,
+        // while the value is still alive on `take`'s stack frame; `ids` came from the same
+        // registry that created `table`'s columns, so types line up.
+        unsafe {
+            set.take(&ids, &mut |id, ptr| table.write_component(id, ptr, tick));
+        }
+        let row = table.finish_push(entity);
+        self.entities.set_location(entity, Location { archetype, row });
+        if !self.observers_added.is_empty() {
+            for &id in &ids {
+                self.notify_added(entity, id);
+            }
+        }
+        entity
+    }
+
+    /// Like `spawn`, but also returns a `RefMut` onto the row just created, so a caller that
+    /// wants to tweak a field right after spawning doesn't need a second `entity_mut` lookup
+    /// (we already know the table and row from insertion).
+    pub fn spawn_and_get<S: Set>(&mut self, set: S) -> (Entity, RefMut<'_>) {
+        let (ids, archetype) = self.spawn_spec::<S>();
+        let entity = self.entities.alloc();
+        let tick = self.tick;
+
+        let table = self.archetypes.table_mut(archetype);
+        // SAFETY: same as `spawn` above.
+        unsafe {
+            set.take(&ids, &mut |id, ptr| table.write_component(id, ptr, tick));
+        }
+        let row = table.finish_push(entity);
+        self.entities.set_location(entity, Location { archetype, row });
+
+        let table = self.archetypes.table_mut(archetype) as *mut _;
+        // SAFETY: `table` is borrowed mutably from `&mut self` for the returned `RefMut`'s
+        // lifetime, so no other reference to it can exist concurrently; `row` was just
+        // assigned above by `finish_push`.
+        let handle = unsafe { RefMut::new(entity, table, row, &self.registry) };
+        (entity, handle)
+    }
+
+    /// Spawns one entity per element of `values`, all carrying just component `C`.
+    ///
+    /// Unlike calling `spawn` in a loop, `values` is moved into its column with a single
+    /// bulk copy rather than one `push` per element — the fastest path for mass-spawning a
+    /// homogeneous batch (e.g. thousands of particles that only have a `Position`). Returns
+    /// the new entities in the same order as `values`.
+    pub fn spawn_column<C: Component>(&mut self, values: Vec<C>) -> Vec<Entity> {
+        let id = self.registry.register::<C>();
+        let spec = Spec::new(vec![id]);
+        let archetype = self.archetypes.get_or_create(&self.registry, spec);
+        let tick = self.tick;
+
+        let entities: Vec<Entity> = (0..values.len()).map(|_| self.entities.alloc()).collect();
+        let table = self.archetypes.table_mut(archetype);
+        // SAFETY: `id` is `C`'s own id, just registered above, and `Spec::new(vec![id])`
+        // guarantees this archetype's table has `id` as its only column.
+        let start_row = unsafe { table.extend_column(id, values, &entities, tick) };
+        for (offset, &entity) in entities.iter().enumerate() {
+            self.entities.set_location(entity, Location { archetype, row: start_row + offset });
+        }
+        entities
+    }
+
+    /// The current simulation tick, used by `query::Ref`/`query::Mut` to tell whether a
+    /// component was added or changed *this* tick. Advances only via `advance_tick` — a
+    /// `Schedule` run doesn't bump it on its own yet, so callers drive it explicitly.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Advances the simulation tick by one and returns the new value.
+    pub fn advance_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Registers `callback` to run, on the calling thread, whenever spawning or a structural
+    /// `add_component`/`remove_component` migration creates a brand-new archetype — e.g. to lazily
+    /// allocate a GPU buffer per archetype. Never fires for an archetype that already exists.
+    /// Costs nothing beyond an `is_empty` check when no callback is registered.
+    pub fn on_archetype_created(&mut self, callback: impl FnMut(ArchetypeId, &Spec) + 'static) {
+        self.archetypes.on_created(callback);
+    }
+
+    /// Registers `callback` to run, on the calling thread, whenever `add_component`/
+    /// `remove_component` actually migrates an entity, reporting exactly which components were
+    /// added and removed (via `Spec::diff`) — e.g. to emit "component added"/"component removed"
+    /// events for reactive systems.
+    ///
+    /// This crate has no `Set`-based batched `add_components`/`remove_components` yet (see
+    /// `add_component`'s doc comment), so today every `SpecDiff` reported this way has exactly
+    /// one id on one side and none on the other; the callback is shaped around `SpecDiff` rather
+    /// than a bare `ComponentId` so it keeps working unchanged if a batched form is added later.
+    pub fn on_components_migrated(&mut self, callback: impl FnMut(Entity, &SpecDiff) + 'static) {
+        self.on_migrated.push(Box::new(callback));
+    }
+
+    /// Registers `callback` to fire immediately, with full `&mut World` access and the
+    /// entity, the moment a `C` is added to any entity — during `spawn` as well as a
+    /// migrating `add_component`, unlike `on_components_migrated` which only sees later
+    /// migrations. Bevy calls this pattern an observer.
+    ///
+    /// If `callback` itself causes another `C` to be added (e.g. it spawns an entity that
+    /// also has `C`), that nested addition doesn't recurse into these same observers: this
+    /// id's callback list is moved out of `observers_added` for the duration of the outer
+    /// call and only merged back in once it returns, so the nested firing sees nothing
+    /// registered for `C` and just no-ops.
+    pub fn observe_added<C: Component>(&mut self, callback: impl FnMut(&mut World, Entity) + 'static) {
+        let id = self.registry.register::<C>();
+        self.observers_added.entry(id).or_default().push(Box::new(callback));
+    }
+
+    /// Like `observe_added`, but fires when `C` is removed from any entity — by a migrating
+    /// `remove_component`, or by `despawn` dropping an entity that had it. See
+    /// `observe_added`'s doc comment for the same re-entrancy guarantee.
+    pub fn observe_removed<C: Component>(&mut self, callback: impl FnMut(&mut World, Entity) + 'static) {
+        let id = self.registry.register::<C>();
+        self.observers_removed.entry(id).or_default().push(Box::new(callback));
+    }
+
+    fn notify_added(&mut self, entity: Entity, id: ComponentId) {
+        let Some(mut callbacks) = self.observers_added.remove(&id) else {
+            return;
+        };
+        for callback in &mut callbacks {
+            callback(self, entity);
+        }
+        self.observers_added.insert(id, callbacks);
+    }
+
+    fn notify_removed(&mut self, entity: Entity, id: ComponentId) {
+        let Some(mut callbacks) = self.observers_removed.remove(&id) else {
+            return;
+        };
+        for callback in &mut callbacks {
+            callback(self, entity);
+        }
+        self.observers_removed.insert(id, callbacks);
+    }
+
+    /// Drains and applies every command in `buffer` — spawns, despawns, inserts, removes, in
+    /// the order they were queued — resolving any `Reserved` targets to the entity their
+    /// `spawn` produced. Decouples command application from `Phase::run`, for a `CommandBuffer`
+    /// built up outside a schedule (e.g. by a loader assembling a scene).
+    pub fn apply_commands(&mut self, buffer: CommandBuffer) {
+        buffer.apply(self);
+    }
+
+    /// Per-component read/write counts since the last `reset_stats`, aggregated across every
+    /// archetype that has the component. Guides optimization — a component id with a huge
+    /// read count relative to writes is a caching candidate.
+    #[cfg(feature = "stats")]
+    pub fn access_stats(&self) -> HashMap<ComponentId, AccessStats> {
+        let mut stats: HashMap<ComponentId, AccessStats> = HashMap::new();
+        for (_, table) in self.archetypes.iter() {
+            for id in table.column_ids() {
+                let column = table.column(id).expect("id came from this table's own column_ids");
+                let entry = stats.entry(id).or_default();
+                entry.reads += column.reads();
+                entry.writes += column.writes();
+            }
+        }
+        stats
+    }
+
+    /// Zeroes every component's read/write counters, e.g. at the start of a new frame.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        for (_, table) in self.archetypes.iter_mut() {
+            for id in table.column_ids().collect::<Vec<_>>() {
+                table.column_mut(id).expect("id came from this table's own column_ids").reset_stats();
+            }
+        }
+    }
+
+    /// Toggles strict mode: while enabled, `try_spawn` fails instead of auto-registering an
+    /// unregistered component type. `spawn` is unaffected and always auto-registers.
+    pub fn strict_mode(&mut self, enabled: bool) {
+        self.strict_mode = enabled;
+    }
+
+    /// Like `spawn`, but in strict mode fails with `UnregisteredComponent` instead of
+    /// silently registering any component type in `set` that isn't registered yet.
+    pub fn try_spawn<S: Set>(&mut self, set: S) -> Result<Entity, UnregisteredComponent> {
+        if self.strict_mode {
+            let names: Vec<&'static str> = S::type_ids()
+                .into_iter()
+                .filter(|(type_id, _)| self.registry.id_of_type(*type_id).is_none())
+                .map(|(_, name)| name)
+                .collect();
+            if !names.is_empty() {
+                return Err(UnregisteredComponent { names });
+            }
+        }
+        Ok(self.spawn(set))
+    }
+
+    /// Spawns `entity` at its exact index and generation instead of letting the allocator
+    /// assign one — e.g. a networked client replicating an entity a server already assigned
+    /// an id to, which must land at that specific handle for deterministic replication to
+    /// hold up. Reserves any allocator slots skipped to get there as free, and overwrites
+    /// (dropping its old component data first) whatever was already at that exact index, on
+    /// any generation.
+    pub fn insert_or_spawn_at<S: Set>(&mut self, entity: Entity, set: S) -> Entity {
+        if let Some(location) = self.entities.alloc_at(entity) {
+            let table = self.archetypes.table_mut(location.archetype);
+            if let Some(moved) = table.swap_remove(location.row) {
+                self.entities.set_location(moved, location);
+            }
+        }
+
+        let (ids, archetype) = self.spawn_spec::<S>();
+        let tick = self.tick;
+
+        let table = self.archetypes.table_mut(archetype);
+        // SAFETY: same as `spawn` above.
+        unsafe {
+            set.take(&ids, &mut |id, ptr| table.write_component(id, ptr, tick));
+        }
+        let row = table.finish_push(entity);
+        self.entities.set_location(entity, Location { archetype, row });
+        if !self.observers_added.is_empty() {
+            for &id in &ids {
+                self.notify_added(entity, id);
+            }
+        }
+        entity
+    }
+
+    /// Despawns `entity`, dropping its component data. Returns `false` if it was already dead.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        // `entity` might be going away through this call without ever reaching
+        // `process_despawn_queue` — via a plain `despawn`, `despawn_where`, or `clear_all`
+        // on something `queue_despawn` already queued. Scrub it here, the one place every
+        // removal path funnels through, so `despawn_queue`/`pending_despawn` never holds a
+        // stale entry for a dead entity.
+        if self.pending_despawn.remove(&entity) {
+            self.despawn_queue.retain(|&queued| queued != entity);
+        }
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        if !self.observers_removed.is_empty() {
+            let ids: Vec<ComponentId> = self.archetypes.table(location.archetype).column_ids().collect();
+            for id in ids {
+                self.notify_removed(entity, id);
+            }
+        }
+        let table = self.archetypes.table_mut(location.archetype);
+        if let Some(moved) = table.swap_remove(location.row) {
+            self.entities.set_location(moved, location);
+        }
+        self.entities.free(entity)
+    }
+
+    /// Despawns every entity `Q` matches whose item satisfies `pred` — e.g. clearing all
+    /// off-screen `Bullet`s in one call instead of a system hand-rolling collect-then-despawn
+    /// itself. Matches are collected into a `Vec` first, since despawning while a `Query`
+    /// still borrows `self` isn't possible (see `Query`'s doc comment), then despawned one at
+    /// a time via `despawn`. Returns the number of entities despawned.
+    pub fn despawn_where<Q: crate::ecs::query::QueryData>(&mut self, mut pred: impl FnMut(Q::Item<'_>) -> bool) -> usize {
+        let mut query: crate::ecs::query::Query<Q> = crate::ecs::query::Query::new(self);
+        let mut matched = Vec::new();
+        for (entity, item) in query.iter().with_entities() {
+            if pred(item) {
+                matched.push(entity);
+            }
+        }
+        let count = matched.len();
+        for entity in matched {
+            self.despawn(entity);
+        }
+        count
+    }
+
+    /// Marks `entity` for despawn on a future `process_despawn_queue` call instead of dropping
+    /// its component data right now — spreads the cost of despawning thousands of entities in
+    /// one frame (e.g. clearing an expired particle wave) across several frames' budgets
+    /// instead of spiking one. `entity` stops matching every query as soon as this returns
+    /// (see `query::Result::advance`), even though its component data and `is_alive` status
+    /// don't change until `process_despawn_queue` actually gets to it. Returns `false` if
+    /// `entity` is already dead or already queued.
+    pub fn queue_despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.is_alive(entity) || !self.pending_despawn.insert(entity) {
+            return false;
+        }
+        self.despawn_queue.push_back(entity);
+        true
+    }
+
+    /// Despawns up to `budget` entities queued by `queue_despawn`, oldest first, via the same
+    /// `despawn` every other removal path uses. Returns how many were *actually* despawned —
+    /// `despawn` itself scrubs `entity` out of `despawn_queue`/`pending_despawn` the instant
+    /// it's removed through any path (a plain `despawn`, `despawn_where`, `clear_all`), so in
+    /// practice every popped entry is still alive; the `despawn(entity)` return value is
+    /// trusted here anyway rather than assumed, so the count stays accurate even if that
+    /// invariant is ever loosened. Less than `budget` once the queue runs dry. Call this once
+    /// per frame with whatever budget the frame can afford instead of draining the whole
+    /// queue in one call.
+    pub fn process_despawn_queue(&mut self, budget: usize) -> usize {
+        let mut processed = 0;
+        while processed < budget {
+            let Some(entity) = self.despawn_queue.pop_front() else {
+                break;
+            };
+            self.pending_despawn.remove(&entity);
+            if self.despawn(entity) {
+                processed += 1;
+            }
+        }
+        processed
+    }
+
+    /// How many entities `queue_despawn` has queued but `process_despawn_queue` hasn't gotten
+    /// to yet.
+    pub fn despawn_queue_len(&self) -> usize {
+        self.despawn_queue.len()
+    }
+
+    /// Whether `entity` is queued for despawn but not yet actually removed — checked by
+    /// `query::Result::advance` so a queued entity disappears from every query immediately
+    /// instead of lingering until its turn in `process_despawn_queue`.
+    pub(crate) fn is_pending_despawn(&self, entity: Entity) -> bool {
+        self.pending_despawn.contains(&entity)
+    }
+
+    /// Adds `value` to `entity`, migrating it into the archetype reached by `Archetypes::add_edge`.
+    /// Returns `false` (leaving `entity` untouched) if it's dead or already has a `C`.
+    ///
+    /// This crate has no `Set`-based `add_components` for attaching several components at
+    /// once yet — this is the single-component entry point the archetype edge cache (see
+    /// `Archetypes::add_edge`) exists to speed up, for callers that toggle one component at a
+    /// time (e.g. a `Stunned` marker).
+    pub fn add_component<C: Component>(&mut self, entity: Entity, value: C) -> bool {
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        let id = self.registry.register::<C>();
+        if self.archetypes.table(location.archetype).has_column(id) {
+            return false;
+        }
+        let to = self.archetypes.add_edge(&self.registry, location.archetype, id);
+        let tick = self.tick;
+        let (src, dest) = self
+            .archetypes
+            .tables_mut2(location.archetype, to)
+            .expect("add_edge always points at a different archetype, since it adds a component");
+
+        // SAFETY: `id` is `C`'s own id, just registered above, and `add_edge` guarantees
+        // `dest`'s spec is `src`'s plus exactly `id`, so `dest` has a column for it.
+        unsafe { dest.write_component(id, (&value as *const C).cast(), tick) };
+        std::mem::forget(value); // ownership moved into `dest`'s column above
+
+        // SAFETY: every column `src` and `dest` share holds the same component type, since
+        // `dest`'s spec is `src`'s plus `id` and nothing else changed.
+        let (moved, new_row) = unsafe { src.move_row(location.row, entity, dest, tick) };
+        if let Some(moved) = moved {
+            self.entities.set_location(moved, location);
+        }
+        self.entities.set_location(entity, Location { archetype: to, row: new_row });
+        if !self.on_migrated.is_empty() {
+            let diff = self.archetypes.spec(location.archetype).diff(self.archetypes.spec(to));
+            for callback in &mut self.on_migrated {
+                callback(entity, &diff);
+            }
+        }
+        if !self.observers_added.is_empty() {
+            self.notify_added(entity, id);
+        }
+        true
+    }
+
+    /// The type-erased counterpart to `add_component`, for callers (a scripting binding, a
+    /// network replication layer) that only know a component's `TypeId` and its raw bytes at
+    /// runtime, not its Rust type. `bytes` must hold exactly one value of the component type
+    /// `type_id` names, ready to be moved (not copied-and-still-owned) into the world.
+    ///
+    /// Returns `false`, leaving both `entity` and `bytes` untouched, if `entity` is dead,
+    /// `type_id` isn't registered, or `entity` already has that component.
+    ///
+    /// # Safety
+    /// `bytes` must be exactly `Registry::info`'s `layout().size()` bytes for `type_id`,
+    /// hold a valid, initialized value of that type, and the caller must not read, drop, or
+    /// otherwise use that value again afterward — ownership transfers into the table on
+    /// success exactly as it would from `add_component`'s `value: C`.
+    pub unsafe fn insert_component_raw(&mut self, entity: Entity, type_id: TypeId, bytes: &[u8]) -> bool {
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        let Some(id) = self.registry.id_of_type(type_id) else {
+            return false;
+        };
+        if self.archetypes.table(location.archetype).has_column(id) {
+            return false;
+        }
+        debug_assert_eq!(bytes.len(), self.registry.info(id).layout().size(), "insert_component_raw: byte length doesn't match the registered layout");
+
+        let to = self.archetypes.add_edge(&self.registry, location.archetype, id);
+        let tick = self.tick;
+        let (src, dest) = self
+            .archetypes
+            .tables_mut2(location.archetype, to)
+            .expect("add_edge always points at a different archetype, since it adds a component");
+
+        // SAFETY: `id` is `type_id`'s own id and `add_edge` guarantees `dest` has a column
+        // for it; `bytes` is a valid value of that type per this function's own safety
+        // contract, forwarded from the caller.
+        unsafe { dest.write_component(id, bytes.as_ptr(), tick) };
+
+        // SAFETY: every column `src` and `dest` share holds the same component type, since
+        // `dest`'s spec is `src`'s plus `id` and nothing else changed.
+        let (moved, new_row) = unsafe { src.move_row(location.row, entity, dest, tick) };
+        if let Some(moved) = moved {
+            self.entities.set_location(moved, location);
+        }
+        self.entities.set_location(entity, Location { archetype: to, row: new_row });
+        if !self.on_migrated.is_empty() {
+            let diff = self.archetypes.spec(location.archetype).diff(self.archetypes.spec(to));
+            for callback in &mut self.on_migrated {
+                callback(entity, &diff);
+            }
+        }
+        if !self.observers_added.is_empty() {
+            self.notify_added(entity, id);
+        }
+        true
+    }
+
+    /// Removes `entity`'s `C`, migrating it into the archetype reached by
+    /// `Archetypes::remove_edge`, and returns the removed value. Returns `None` (leaving
+    /// `entity` untouched) if it's dead or doesn't have a `C`.
+    ///
+    /// See `add_component`'s doc comment for why this single-component form exists rather
+    /// than a `Set`-based `remove_components`.
+    pub fn remove_component<C: Component>(&mut self, entity: Entity) -> Option<C> {
+        let location = self.entities.location(entity)?;
+        let id = self.registry.register::<C>();
+        if !self.archetypes.table(location.archetype).has_column(id) {
+            return None;
+        }
+        let to = self.archetypes.remove_edge(&self.registry, location.archetype, id);
+        let tick = self.tick;
+        let (src, dest) = self
+            .archetypes
+            .tables_mut2(location.archetype, to)
+            .expect("remove_edge always points at a different archetype, since it drops a component");
+
+        let mut value = std::mem::MaybeUninit::<C>::uninit();
+        // SAFETY: `id` is `C`'s own id and `src` has a column for it, confirmed by
+        // `has_column` above; `value` is valid for a write of exactly that layout.
+        unsafe { src.column_mut(id).expect("checked by has_column above").swap_remove_into(location.row, value.as_mut_ptr().cast()) };
+
+        // SAFETY: every column `src` and `dest` share holds the same component type, since
+        // `dest`'s spec is `src`'s minus `id` and nothing else changed; the `id` column
+        // itself was already drained above, so `move_row` leaves it alone.
+        let (moved, new_row) = unsafe { src.move_row(location.row, entity, dest, tick) };
+        if let Some(moved) = moved {
+            self.entities.set_location(moved, location);
+        }
+        self.entities.set_location(entity, Location { archetype: to, row: new_row });
+        if !self.on_migrated.is_empty() {
+            let diff = self.archetypes.spec(location.archetype).diff(self.archetypes.spec(to));
+            for callback in &mut self.on_migrated {
+                callback(entity, &diff);
+            }
+        }
+        if !self.observers_removed.is_empty() {
+            self.notify_removed(entity, id);
+        }
+        // SAFETY: `swap_remove_into` above wrote a valid `C` into `value`.
+        Some(unsafe { value.assume_init() })
+    }
+
+    /// Adds `value.clone()` to every entity matched by `state`, migrating each affected
+    /// archetype's whole table in one `Table::move_all` per source archetype instead of one
+    /// `add_component` call per entity — the efficient primitive behind bulk toggles like
+    /// "pause all physics bodies" (bulk-adding a `Paused` marker). Entities already carrying
+    /// `C`, or in an archetype `state` doesn't match, are left untouched. Returns the number
+    /// of entities migrated.
+    ///
+    /// Takes a `QueryState` rather than a live `Query`, since a `Query` already holds this
+    /// `World` mutably for its own lifetime — see `Query`'s doc comment — which would
+    /// conflict with the mutable access this needs to do the migration itself.
+    pub fn add_component_to_all<C: Component + Clone, Q: crate::ecs::query::QueryData>(&mut self, state: &crate::ecs::query::QueryState<Q>, value: C) -> usize {
+        let id = self.registry.register::<C>();
+        let mask = crate::ecs::component::ComponentMask::from_ids(state.required().iter().copied());
+        let froms: Vec<ArchetypeId> = self.archetypes.iter().filter(|(_, table)| table.matches(&mask) && !table.has_column(id)).map(|(archetype, _)| archetype).collect();
+
+        let mut migrated = 0;
+        for from in froms {
+            let count = self.archetypes.table(from).len();
+            if count == 0 {
+                continue;
+            }
+            let to = self.archetypes.add_edge(&self.registry, from, id);
+            let tick = self.tick;
+            let (src, dest) = self.archetypes.tables_mut2(from, to).expect("add_edge always points at a different archetype, since it adds a component");
+            // SAFETY: every column `src` and `dest` share holds the same component type,
+            // since `dest`'s spec is `src`'s plus `id` and nothing else changed; `id`'s own
+            // column is filled in separately below, the same as `add_component` leaves it
+            // to `write_component` first.
+            let start = unsafe { src.move_all(dest) };
+            let values: Vec<C> = std::iter::repeat_n(value.clone(), count).collect();
+            // SAFETY: `id` is `C`'s own id, just registered above, and `add_edge` guarantees
+            // `dest` has a column for it.
+            unsafe { dest.column_mut(id).expect("add_edge guarantees dest has id's column").extend(values, tick) };
+
+            let entities: Vec<Entity> = dest.entities()[start..start + count].to_vec();
+            for (offset, &entity) in entities.iter().enumerate() {
+                self.entities.set_location(entity, Location { archetype: to, row: start + offset });
+            }
+            if !self.on_migrated.is_empty() {
+                let diff = self.archetypes.spec(from).diff(self.archetypes.spec(to));
+                for &entity in &entities {
+                    for callback in &mut self.on_migrated {
+                        callback(entity, &diff);
+                    }
+                }
+            }
+            if !self.observers_added.is_empty() {
+                for &entity in &entities {
+                    self.notify_added(entity, id);
+                }
+            }
+            migrated += count;
+        }
+        migrated
+    }
+
+    /// Removes `C` from every entity matched by `state`, migrating each affected archetype's
+    /// whole table in one `Table::move_all` per source archetype instead of one
+    /// `remove_component` call per entity. Entities without `C`, or in an archetype `state`
+    /// doesn't match, are left untouched. Returns the number of entities migrated.
+    ///
+    /// See `add_component_to_all`'s doc comment for why this takes a `QueryState` rather
+    /// than a live `Query`.
+    pub fn remove_component_from_all<C: Component, Q: crate::ecs::query::QueryData>(&mut self, state: &crate::ecs::query::QueryState<Q>) -> usize {
+        let id = self.registry.register::<C>();
+        let mask = crate::ecs::component::ComponentMask::from_ids(state.required().iter().copied());
+        let froms: Vec<ArchetypeId> = self.archetypes.iter().filter(|(_, table)| table.matches(&mask) && table.has_column(id)).map(|(archetype, _)| archetype).collect();
+
+        let mut migrated = 0;
+        for from in froms {
+            let count = self.archetypes.table(from).len();
+            if count == 0 {
+                continue;
+            }
+            let to = self.archetypes.remove_edge(&self.registry, from, id);
+            let (src, dest) = self.archetypes.tables_mut2(from, to).expect("remove_edge always points at a different archetype, since it drops a component");
+            // SAFETY: every column `src` and `dest` share holds the same component type,
+            // since `dest`'s spec is `src`'s minus `id` and nothing else changed; `id`'s own
+            // column is dropped in place by `move_all` since `dest` has no such column.
+            let start = unsafe { src.move_all(dest) };
+
+            let entities: Vec<Entity> = dest.entities()[start..start + count].to_vec();
+            for (offset, &entity) in entities.iter().enumerate() {
+                self.entities.set_location(entity, Location { archetype: to, row: start + offset });
+            }
+            if !self.on_migrated.is_empty() {
+                let diff = self.archetypes.spec(from).diff(self.archetypes.spec(to));
+                for &entity in &entities {
+                    for callback in &mut self.on_migrated {
+                        callback(entity, &diff);
+                    }
+                }
+            }
+            if !self.observers_removed.is_empty() {
+                for &entity in &entities {
+                    self.notify_removed(entity, id);
+                }
+            }
+            migrated += count;
+        }
+        migrated
+    }
+
+    /// Logically removes `entity`'s `C` from queries without migrating it to a different
+    /// archetype the way `remove_component` would — a lighter-weight alternative for a
+    /// component that gets toggled on and off often enough that the migration churn would
+    /// dominate (e.g. a `Frozen` marker flipped every few frames). Reversible via
+    /// `enable_component`. Returns `false` if `entity` is dead or doesn't have a `C`.
+    pub fn disable_component<C: Component>(&mut self, entity: Entity) -> bool {
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        let id = self.registry.register::<C>();
+        let table = self.archetypes.table_mut(location.archetype);
+        let Some(column) = table.column_mut(id) else {
+            return false;
+        };
+        column.disable(location.row);
+        true
+    }
+
+    /// Undoes a prior `disable_component`, making `entity`'s `C` visible to queries again.
+    /// Returns `false` if `entity` is dead or doesn't have a `C`.
+    pub fn enable_component<C: Component>(&mut self, entity: Entity) -> bool {
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        let id = self.registry.register::<C>();
+        let table = self.archetypes.table_mut(location.archetype);
+        let Some(column) = table.column_mut(id) else {
+            return false;
+        };
+        column.enable(location.row);
+        true
+    }
+
+    /// Splices every entity from `other` into `self`, e.g. for a level chunk built on a
+    /// background thread and merged onto the main `World` once it's ready.
+    ///
+    /// `other`'s component types are re-registered against `self`'s registry (mapping ids
+    /// as needed) and its tables are drained, row by row, into matching or newly created
+    /// archetypes here via `Table::move_row_remap` — the same move-not-copy primitive
+    /// `add_component`/`remove_component` use to migrate a row between archetypes, just with
+    /// an explicit id mapping since `other`'s ids and `self`'s don't line up. `other` is left
+    /// with nothing but empty tables once every row has moved, so it drops normally (and
+    /// cheaply) when it goes out of scope at the end of this call — unlike copying the bytes
+    /// out by hand, this never needs to skip `other`'s destructor to avoid a double-drop.
+    /// Returns a map from `other`'s old `Entity` ids to the fresh ones they were given in
+    /// `self`, so callers can remap entity references embedded in components (e.g. `Parent`).
+    pub fn merge(&mut self, mut other: World) -> HashMap<Entity, Entity> {
+        let mut mapping = HashMap::with_capacity(other.len());
+        let mut component_map: HashMap<ComponentId, ComponentId> = HashMap::new();
+
+        for (_, table) in other.archetypes.iter_mut() {
+            let source_ids: Vec<ComponentId> = table.column_ids().collect();
+            let dest_ids: Vec<ComponentId> = source_ids
+                .iter()
+                .map(|&id| *component_map.entry(id).or_insert_with(|| self.registry.register_info(other.registry.info(id))))
+                .collect();
+            let id_pairs: Vec<(ComponentId, ComponentId)> = source_ids.iter().copied().zip(dest_ids.iter().copied()).collect();
+            let archetype = self.archetypes.get_or_create(&self.registry, Spec::new(dest_ids));
+
+            // Always move row 0: `move_row_remap` swap-removes it out of `table`, so row 0
+            // is a fresh row (or the table is empty) on every next iteration.
+            while !table.is_empty() {
+                let old_entity = table.entity(0);
+                let new_entity = self.entities.alloc();
+                let dest_table = self.archetypes.table_mut(archetype);
+                // SAFETY: `dest_ids` were registered from `source_ids`' own `Info`s via
+                // `register_info` above, so each pair in `id_pairs` names columns of the
+                // same component type. Merged rows are stamped with `self`'s current tick
+                // rather than carrying over `other`'s — the two worlds don't share a tick
+                // clock, so "just merged" is the only meaningful added/changed tick here.
+                let (_, new_row) = unsafe { table.move_row_remap(0, new_entity, dest_table, &id_pairs, self.tick) };
+                self.entities.set_location(new_entity, Location { archetype, row: new_row });
+                mapping.insert(old_entity, new_entity);
+            }
+        }
+
+        mapping
+    }
+
+    /// Deep-copies this `World`'s entities and component data via `Archetypes::try_clone`,
+    /// for cheap save/restore snapshots (e.g. `RollbackBuffer`). Fails with the first
+    /// non-cloneable component encountered, same as `Archetypes::try_clone`.
+    ///
+    /// Uniques and non-send uniques aren't carried over: a unique has no
+    /// `register_cloneable`-style opt-in the way components do (`Uniques` stores them as
+    /// type-erased `Box<dyn Any>`, with no per-type clone function to call), so a clone
+    /// starts with none of its own, the same way `Archetypes::try_clone` starts a clone
+    /// with no observers of its own.
+    pub fn try_clone(&self) -> Result<World, NotCloneable> {
+        Ok(World {
+            entities: self.entities.clone(),
+            registry: self.registry.clone(),
+            archetypes: self.archetypes.try_clone()?,
+            uniques: Uniques::new(),
+            strict_mode: self.strict_mode,
+            tick: self.tick,
+            on_migrated: Vec::new(),
+            observers_added: HashMap::new(),
+            observers_removed: HashMap::new(),
+            defrag_cursor: self.defrag_cursor,
+            spec_cache: self.spec_cache.clone(),
+            despawn_queue: self.despawn_queue.clone(),
+            pending_despawn: self.pending_despawn.clone(),
+        })
+    }
+
+    /// Overwrites `self` with `buffer`'s snapshot of `frame`, via `try_clone`. Returns
+    /// `false` (leaving `self` untouched) if `frame` has aged out of `buffer` or its
+    /// snapshot has a component that isn't cloneable.
+    ///
+    /// This only restores the saved state — it doesn't fast-forward back to the current
+    /// tick by re-running anything, see `RollbackBuffer`'s doc comment for why.
+    pub fn rollback_to(&mut self, buffer: &crate::ecs::rollback::RollbackBuffer, frame: u64) -> bool {
+        let Some(snapshot) = buffer.get(frame) else {
+            return false;
+        };
+        let Ok(restored) = snapshot.try_clone() else {
+            return false;
+        };
+        *self = restored;
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Where `entity`'s component data currently lives, or `None` if it's dead. Only valid
+    /// until the next structural change (spawn/despawn/`add_component`/`remove_component`)
+    /// touching its archetype, since rows shift on swap-remove.
+    pub fn location(&self, entity: Entity) -> Option<Location> {
+        self.entities.location(entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub fn registry_mut(&mut self) -> &mut Registry {
+        &mut self.registry
+    }
+
+    pub fn insert_unique<U: Unique>(&mut self, value: U) {
+        self.uniques.insert(value);
+    }
+
+    pub fn unique<U: Unique>(&self) -> Option<&U> {
+        self.uniques.get()
+    }
+
+    pub fn unique_mut<U: Unique>(&mut self) -> Option<&mut U> {
+        self.uniques.get_mut()
+    }
+
+    /// Raw access to the whole `Uniques` store, for `unique::UniqSet::fetch` to pull out
+    /// several disjoint uniques from one `&mut World` borrow via a raw pointer, the same way
+    /// `Query`/`entity::RefMut::view_mut` split one exclusive borrow into several disjoint
+    /// fetches.
+    pub(crate) fn uniques_mut(&mut self) -> *mut Uniques {
+        &mut self.uniques
+    }
+
+    /// Despawns every entity and drops every unique (`Send` and non-`Send` alike), restoring
+    /// `self` to an empty world without discarding registered component types the way a
+    /// fresh `World::new()` would. Meant for an editor's "load a new scene" workflow that
+    /// reuses one `World` instead of constructing another.
+    pub fn clear_all(&mut self) {
+        let entities: Vec<Entity> = self.archetypes.iter().flat_map(|(_, table)| table.entities().to_vec()).collect();
+        for entity in entities {
+            self.despawn(entity);
+        }
+        self.uniques.clear();
+    }
+
+    /// Inserts a non-`Send` unique (a GPU handle, a raw window pointer). Reach it back with
+    /// `NonSend`/`NonSendMut`, and keep systems that touch it out of a `Phase`'s parallel
+    /// group — see `unique::NonSendUnique`.
+    pub fn insert_non_send_unique<U: NonSendUnique>(&mut self, value: U) {
+        self.uniques.insert_non_send(value);
+    }
+
+    pub fn non_send_unique<U: NonSendUnique>(&self) -> Option<&U> {
+        self.uniques.get_non_send()
+    }
+
+    pub fn non_send_unique_mut<U: NonSendUnique>(&mut self) -> Option<&mut U> {
+        self.uniques.get_non_send_mut()
+    }
+
+    /// Starts a query for every entity matching `Q` (e.g. `world.query::<(&A, &mut B)>()`).
+    pub fn query<Q: crate::ecs::query::QueryData>(&mut self) -> crate::ecs::query::Query<'_, Q> {
+        crate::ecs::query::Query::new(self)
+    }
+
+    /// Runs a one-off system against this world and returns its output, whatever that is —
+    /// unlike `Phase`, which only accepts `Out = ()` systems.
+    pub fn run_system<M, S: IntoSystem<M>>(&mut self, system: S) -> <S::System as System>::Out {
+        system.into_system().run(self)
+    }
+
+    pub fn archetypes(&self) -> &Archetypes {
+        &self.archetypes
+    }
+
+    /// Iterates every archetype (and its backing `Table`) that has a `C` column, e.g. for
+    /// editor tooling that wants archetype metadata rather than a live entity query.
+    pub fn archetypes_with<C: Component>(&self) -> impl Iterator<Item = (ArchetypeId, &Table)> {
+        let id = self.registry.id_of::<C>();
+        id.into_iter()
+            .flat_map(move |id| self.archetypes.containing(id))
+            .map(move |(archetype, _)| (archetype, self.archetypes.table(archetype)))
+    }
+
+    /// A per-archetype entity count for every archetype with a `C` column, e.g.
+    /// `world.query_stats::<Enemy>()` for balancing/telemetry that only wants counts and
+    /// entity distribution across archetypes, not the components themselves — cheaper than
+    /// `Query::iter().count()` per archetype since it reads `Table::len` directly instead of
+    /// visiting every row. Built on the same `archetypes_with` this file's other single-
+    /// component helpers (`for_each_component`) already use, rather than a separate
+    /// `table_ids_for` lookup.
+    pub fn query_stats<C: Component>(&self) -> HashMap<ArchetypeId, usize> {
+        self.archetypes_with::<C>().map(|(archetype, table)| (archetype, table.len())).collect()
+    }
+
+    /// Per-archetype and total byte usage across every `Table`, for the dhat memory benchmark
+    /// and in-engine diagnostics — see `Archetypes::memory_report` for what's counted.
+    pub fn memory_report(&self) -> crate::ecs::storage::MemoryReport {
+        self.archetypes.memory_report()
+    }
+
+    /// Visits every live `C` across every archetype, calling `f(entity, &component)` per row.
+    ///
+    /// Lower-level than `Query`: it doesn't build a `Spec`, register access, or support
+    /// multi-component matching — just a flat walk of one component's columns, for one-off
+    /// passes like a debug "validate every position is finite" scan that don't warrant a
+    /// full query type.
+    pub fn for_each_component<C: Component>(&self, mut f: impl FnMut(Entity, &C)) {
+        let Some(id) = self.registry.id_of::<C>() else {
+            return;
+        };
+        for (_, table) in self.archetypes_with::<C>() {
+            let column = table.column(id).expect("archetype from archetypes_with has a C column");
+            for row in 0..table.len() {
+                let ptr = column.get(row).expect("row within table length") as *const C;
+                f(table.entity(row), unsafe { &*ptr });
+            }
+        }
+    }
+
+    /// Like `for_each_component`, but visits `&mut C`.
+    pub fn for_each_component_mut<C: Component>(&mut self, mut f: impl FnMut(Entity, &mut C)) {
+        let Some(id) = self.registry.id_of::<C>() else {
+            return;
+        };
+        let archetypes: Vec<ArchetypeId> = self.archetypes.containing(id).map(|(archetype, _)| archetype).collect();
+        for archetype in archetypes {
+            let table = self.archetypes.table_mut(archetype);
+            let entities: Vec<Entity> = table.entities().to_vec();
+            let column = table.column_mut(id).expect("archetype from Archetypes::containing has a C column");
+            for (row, &entity) in entities.iter().enumerate() {
+                let ptr = column.get_mut(row).expect("row within table length") as *mut C;
+                f(entity, unsafe { &mut *ptr });
+            }
+        }
+    }
+
+    /// Reorders every entity in `archetype`'s table by ascending `key`, e.g. sorting sprites
+    /// into z-order before a render phase reads them back to back. A no-op if `archetype`
+    /// has no `C` column.
+    ///
+    /// Selection sort: each of the table's `n` rows costs an `O(n)` scan to find its sorted
+    /// position, but the swap that puts it there is `Table::swap_rows`' cheap column-wise
+    /// exchange rather than a shuffle of the whole table, so this stays a fine fit for the
+    /// size of table one archetype typically holds.
+    pub fn sort_table_by<C: Component, K: Ord>(&mut self, archetype: ArchetypeId, mut key: impl FnMut(&C) -> K) {
+        let Some(id) = self.registry.id_of::<C>() else {
+            return;
+        };
+        let table = self.archetypes.table_mut(archetype);
+        if !table.has_column(id) {
+            return;
+        }
+
+        let read_key = |table: &Table, row: usize, key: &mut dyn FnMut(&C) -> K| {
+            let ptr = table.column(id).expect("checked has_column above").get(row).expect("row within table length") as *const C;
+            key(unsafe { &*ptr })
+        };
+
+        let len = table.len();
+        for i in 0..len {
+            let mut min_row = i;
+            let mut min_key = read_key(table, i, &mut key);
+            for j in (i + 1)..len {
+                let candidate_key = read_key(table, j, &mut key);
+                if candidate_key < min_key {
+                    min_row = j;
+                    min_key = candidate_key;
+                }
+            }
+            if min_row != i {
+                table.swap_rows(i, min_row);
+            }
+        }
+
+        let entities = table.entities().to_vec();
+        for (row, entity) in entities.into_iter().enumerate() {
+            self.entities.set_location(entity, Location { archetype, row });
+        }
+    }
+
+    /// Incrementally re-sorts every table's rows by ascending `Entity`, undoing the row
+    /// scrambling repeated swap-removes cause and restoring locality for entities that were
+    /// originally spawned together. Time-boxed: performs at most `budget` row placements
+    /// (selection sort's "find the next row and swap it into place" step) before returning,
+    /// resuming exactly where the previous call left off — call this with a small budget once
+    /// per frame to spread a full pass across many frames instead of paying for it all at
+    /// once.
+    ///
+    /// Returns the number of placements actually performed. This is less than `budget` only
+    /// once every table has been fully sorted since the last time it was disturbed; the next
+    /// swap-remove gives this something to do again.
+    pub fn defragment(&mut self, budget: usize) -> usize {
+        let archetype_count = self.archetypes.iter().count();
+        let mut done = 0;
+        while done < budget {
+            if self.defrag_cursor.archetype >= archetype_count {
+                self.defrag_cursor = DefragCursor::default();
+                break;
+            }
+            let archetype = ArchetypeId(self.defrag_cursor.archetype);
+            let table = self.archetypes.table_mut(archetype);
+            let len = table.len();
+            let i = self.defrag_cursor.row;
+            if i >= len {
+                self.defrag_cursor.archetype += 1;
+                self.defrag_cursor.row = 0;
+                continue;
+            }
+
+            let mut min_row = i;
+            let mut min_entity = table.entity(i);
+            for j in (i + 1)..len {
+                let candidate = table.entity(j);
+                if candidate < min_entity {
+                    min_row = j;
+                    min_entity = candidate;
+                }
+            }
+            if min_row != i {
+                table.swap_rows(i, min_row);
+                let displaced = table.entity(min_row);
+                self.entities.set_location(displaced, Location { archetype, row: min_row });
+            }
+            self.entities.set_location(min_entity, Location { archetype, row: i });
+
+            self.defrag_cursor.row += 1;
+            done += 1;
+        }
+        done
+    }
+
+    /// Whether `entity` currently has a `C`, without fetching its value. Cheaper than
+    /// `entity_ref(entity).and_then(|e| e.get::<C>()).is_some()`: it only checks the
+    /// entity's archetype `Spec`, skipping the column lookup entirely.
+    ///
+    /// `false` for a dead entity, or if `C` was never registered (in which case no entity
+    /// could have one).
+    pub fn has_component<C: Component>(&self, entity: Entity) -> bool {
+        let Some(id) = self.registry.id_of::<C>() else {
+            return false;
+        };
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        self.archetypes.spec(location.archetype).contains(id)
+    }
+
+    /// Whether `entity` currently has every component in `S`, without fetching any of their
+    /// values. Doesn't register any component type in `S` that isn't registered yet — an
+    /// unregistered component can't be present on any entity, so this reports `false` for it
+    /// the same way `has_component` would.
+    pub fn has_components<S: Set>(&self, entity: Entity) -> bool {
+        let Some(location) = self.entities.location(entity) else {
+            return false;
+        };
+        let spec = self.archetypes.spec(location.archetype);
+        S::type_ids()
+            .into_iter()
+            .all(|(type_id, _)| self.registry.id_of_type(type_id).is_some_and(|id| spec.contains(id)))
+    }
+
+    /// Returns a read-only view onto `entity`'s components, or `None` if it's dead.
+    pub fn entity_ref(&self, entity: Entity) -> Option<Ref<'_>> {
+        let location = self.entities.location(entity)?;
+        let table = self.archetypes.table(location.archetype) as *const _;
+        // SAFETY: `table` is borrowed from `&self` for `'_` and `location.row` is the row
+        // currently recorded for `entity`.
+        Some(unsafe { Ref::new(entity, table, location.row, &self.registry) })
+    }
+
+    /// Returns a mutable view onto `entity`'s components, or `None` if it's dead.
+    pub fn entity_mut(&mut self, entity: Entity) -> Option<RefMut<'_>> {
+        let location = self.entities.location(entity)?;
+        let table = self.archetypes.table_mut(location.archetype) as *mut _;
+        // SAFETY: `table` is borrowed mutably from `&mut self` for `'_`, so no other
+        // reference to it can exist concurrently.
+        Some(unsafe { RefMut::new(entity, table, location.row, &self.registry) })
+    }
+
+    /// Returns mutable views onto `N` distinct entities at once, even when they live in the
+    /// same archetype table.
+    ///
+    /// Returns `None` if any two requested entities are the same, or if any is dead.
+    ///
+    /// # Why this is sound
+    /// Every live entity has exactly one `Location` (archetype + row) at any time, and that
+    /// invariant is maintained by every mutation in this module (spawn assigns a fresh row,
+    /// despawn's swap-remove updates the moved entity's row). So distinct entities always
+    /// resolve to distinct `(archetype, row)` pairs — never the same row — which means the
+    /// raw table pointers handed to each `RefMut` never alias, regardless of whether they
+    /// point into the same `Table`.
+    pub fn get_many_mut<const N: usize>(&mut self, entities: [Entity; N]) -> Option<[RefMut<'_>; N]> {
+        for i in 0..N {
+            for j in (i + 1)..N {
+                if entities[i] == entities[j] {
+                    return None;
+                }
+            }
+        }
+
+        let mut locations = [None; N];
+        for (slot, &entity) in locations.iter_mut().zip(entities.iter()) {
+            *slot = self.entities.location(entity);
+        }
+        if locations.iter().any(Option::is_none) {
+            return None;
+        }
+        let locations = locations.map(Option::unwrap);
+
+        let archetypes: *mut Archetypes = &mut self.archetypes;
+        let registry = &self.registry;
+        Some(std::array::from_fn(|i| {
+            let location = locations[i];
+            // SAFETY: see doc comment above — `location`s are pairwise distinct, so the
+            // `*mut Table` handed to each `RefMut` never overlaps another's row.
+            let table = unsafe { (*archetypes).table_mut(location.archetype) as *mut _ };
+            unsafe { RefMut::new(entities[i], table, location.row, registry) }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::query::Query;
+    use rusty_engine_macros::Component;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Velocity(f32, f32);
+
+    #[derive(Component, Debug, PartialEq, Clone)]
+    struct Health(i32);
+
+    #[test]
+    fn spawn_and_read_back() {
+        let mut world = World::new();
+        let e = world.spawn((Position(1.0, 2.0), Velocity(0.5, 0.5)));
+        let r = world.entity_ref(e).unwrap();
+        assert_eq!(r.get::<Position>(), Some(&Position(1.0, 2.0)));
+        assert_eq!(r.get::<Velocity>(), Some(&Velocity(0.5, 0.5)));
+    }
+
+    #[test]
+    fn spawn_and_get_returns_a_handle_onto_the_new_row() {
+        let mut world = World::new();
+        let (e, mut handle) = world.spawn_and_get((Position(1.0, 2.0), Velocity(0.5, 0.5)));
+        assert_eq!(handle.entity(), e);
+        handle.get_mut::<Position>().unwrap().0 += 10.0;
+
+        let r = world.entity_ref(e).unwrap();
+        assert_eq!(r.get::<Position>(), Some(&Position(11.0, 2.0)));
+        assert_eq!(r.get::<Velocity>(), Some(&Velocity(0.5, 0.5)));
+    }
+
+    #[test]
+    fn repeated_spawns_of_the_same_bundle_type_reuse_the_cached_spec() {
+        let mut world = World::new();
+        let e1 = world.spawn((Position(1.0, 2.0), Velocity(0.5, 0.5)));
+        let (ids, archetype) = world.spawn_spec::<(Position, Velocity)>();
+        let e2 = world.spawn((Position(3.0, 4.0), Velocity(1.0, 1.0)));
+
+        // Both spawns land in the archetype the cache reports, and the cached ids agree with
+        // what a fresh `Spec::new` over them would normalize to.
+        assert_eq!(world.entities.location(e1).unwrap().archetype, archetype);
+        assert_eq!(world.entities.location(e2).unwrap().archetype, archetype);
+        assert_eq!(&Spec::new(ids), world.archetypes.spec(archetype));
+    }
+
+    #[test]
+    fn on_archetype_created_fires_once_per_distinct_spec() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut world = World::new();
+        let seen: Rc<RefCell<Vec<Spec>>> = Rc::new(RefCell::new(Vec::new()));
+        let recorder = Rc::clone(&seen);
+        world.on_archetype_created(move |_id, spec| recorder.borrow_mut().push(spec.clone()));
+
+        world.spawn(Position(1.0, 2.0)); // new archetype: (Position)
+        world.spawn(Position(3.0, 4.0)); // same archetype: no callback
+        world.spawn((Position(0.0, 0.0), Velocity(0.5, 0.5))); // new archetype: (Position, Velocity)
+        world.spawn(Health(10)); // new archetype: (Health)
+
+        assert_eq!(seen.borrow().len(), 3);
+    }
+
+    #[test]
+    fn on_archetype_created_fires_for_archetypes_reached_by_structural_migration() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut world = World::new();
+        let created = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&created);
+        world.on_archetype_created(move |_id, _spec| counter.set(counter.get() + 1));
+
+        let e = world.spawn(Position(1.0, 2.0)); // (Position)
+        assert_eq!(created.get(), 1);
+        world.add_component(e, Velocity(0.0, 0.0)); // migrates to the new (Position, Velocity) archetype
+        assert_eq!(created.get(), 2);
+        world.remove_component::<Velocity>(e); // back to the already-existing (Position) archetype
+        assert_eq!(created.get(), 2);
+    }
+
+    #[test]
+    fn on_components_migrated_reports_added_and_removed_ids_across_a_combined_migration() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut world = World::new();
+        let diffs: Rc<RefCell<Vec<SpecDiff>>> = Rc::new(RefCell::new(Vec::new()));
+        let recorder = Rc::clone(&diffs);
+        world.on_components_migrated(move |_entity, diff| recorder.borrow_mut().push(diff.clone()));
+
+        let velocity_id = world.registry_mut().register::<Velocity>();
+        let health_id = world.registry_mut().register::<Health>();
+        let e = world.spawn(Position(1.0, 2.0)); // no migration: this is just a spawn
+
+        world.add_component(e, Velocity(0.0, 0.0));
+        world.add_component(e, Health(10));
+        world.remove_component::<Position>(e);
+
+        assert_eq!(diffs.borrow().len(), 3);
+        assert_eq!(diffs.borrow()[0], SpecDiff { added: vec![velocity_id], removed: vec![] });
+        assert_eq!(diffs.borrow()[1], SpecDiff { added: vec![health_id], removed: vec![] });
+        assert_eq!(diffs.borrow()[2], SpecDiff { added: vec![], removed: vec![world.registry_mut().register::<Position>()] });
+    }
+
+    #[test]
+    fn observers_fire_across_spawns_migrations_and_despawns() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut world = World::new();
+        let added = Rc::new(Cell::new(0));
+        let removed = Rc::new(Cell::new(0));
+        let added_counter = Rc::clone(&added);
+        let removed_counter = Rc::clone(&removed);
+        world.observe_added::<Velocity>(move |_world, _entity| added_counter.set(added_counter.get() + 1));
+        world.observe_removed::<Velocity>(move |_world, _entity| removed_counter.set(removed_counter.get() + 1));
+
+        let e1 = world.spawn(Position(1.0, 2.0)); // no Velocity: observers stay quiet
+        assert_eq!(added.get(), 0);
+
+        let e2 = world.spawn((Position(0.0, 0.0), Velocity(0.5, 0.5))); // spawn with Velocity
+        assert_eq!(added.get(), 1);
+
+        world.add_component(e1, Velocity(1.0, 1.0)); // migration adds Velocity
+        assert_eq!(added.get(), 2);
+
+        world.remove_component::<Velocity>(e1); // migration removes Velocity
+        assert_eq!(removed.get(), 1);
+
+        world.despawn(e2); // despawn drops a live Velocity
+        assert_eq!(removed.get(), 2);
+    }
+
+    #[test]
+    fn observers_guard_against_reentrant_spawns_of_the_same_component() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut world = World::new();
+        let fired = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&fired);
+        world.observe_added::<Health>(move |world, _entity| {
+            counter.set(counter.get() + 1);
+            world.spawn(Health(1)); // would recurse into this same observer if not guarded
+        });
+
+        world.spawn(Health(10));
+
+        assert_eq!(fired.get(), 1);
+    }
+
+    #[test]
+    fn has_component_and_has_components_report_presence_across_archetypes() {
+        let mut world = World::new();
+        let position_only = world.spawn(Position(1.0, 0.0));
+        let both = world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+
+        assert!(world.has_component::<Position>(position_only));
+        assert!(!world.has_component::<Velocity>(position_only));
+        assert!(world.has_component::<Position>(both));
+        assert!(world.has_component::<Velocity>(both));
+
+        assert!(world.has_components::<(Position, Velocity)>(both));
+        assert!(!world.has_components::<(Position, Velocity)>(position_only));
+
+        // `Health` was never registered at all, so no entity can have one.
+        assert!(!world.has_component::<Health>(both));
+
+        world.despawn(both);
+        assert!(!world.has_component::<Position>(both));
+    }
+
+    #[test]
+    fn sort_table_by_orders_the_table_and_keeps_entity_locations_consistent() {
+        let mut world = World::new();
+        let e0 = world.spawn(Position(3.0, 0.0));
+        let e1 = world.spawn(Position(1.0, 0.0));
+        let e2 = world.spawn(Position(2.0, 0.0));
+
+        let archetype = world.location(e0).unwrap().archetype;
+        world.sort_table_by::<Position, i64>(archetype, |p| p.0 as i64);
+
+        let table = world.archetypes().table(archetype);
+        let ordered: Vec<f32> = (0..table.len())
+            .map(|row| unsafe { &*(table.column(world.registry().id_of::<Position>().unwrap()).unwrap().get(row).unwrap() as *const Position) }.0)
+            .collect();
+        assert_eq!(ordered, vec![1.0, 2.0, 3.0]);
+
+        // Every entity's recorded `Location` must still point at its own row after the sort.
+        for (entity, expected) in [(e0, 3.0), (e1, 1.0), (e2, 2.0)] {
+            let location = world.location(entity).unwrap();
+            assert_eq!(location.archetype, archetype);
+            assert_eq!(table.entity(location.row), entity);
+            assert_eq!(world.entity_ref(entity).unwrap().get::<Position>().unwrap().0, expected);
+        }
+    }
+
+    #[test]
+    fn defragment_sorts_tables_by_entity_and_keeps_locations_correct() {
+        let mut world = World::new();
+        // Scramble row order by despawning every other entity, which swap-removes the last
+        // row into the freed slot.
+        let entities: Vec<_> = (0..8).map(|i| world.spawn(Position(i as f32, 0.0))).collect();
+        for &e in entities.iter().step_by(2) {
+            world.despawn(e);
+        }
+        let survivors: Vec<_> = entities.iter().copied().skip(1).step_by(2).collect();
+        let archetype = world.location(survivors[0]).unwrap().archetype;
+        let table = world.archetypes().table(archetype);
+        assert_ne!(table.entities().to_vec(), {
+            let mut sorted = survivors.clone();
+            sorted.sort();
+            sorted
+        });
+
+        // A budget of 1 only ever performs one placement per call.
+        let mut total = 0;
+        loop {
+            let done = world.defragment(1);
+            total += done;
+            if done == 0 {
+                break;
+            }
+            assert_eq!(done, 1);
+        }
+        assert_eq!(total, survivors.len());
+
+        let table = world.archetypes().table(archetype);
+        let mut expected = survivors.clone();
+        expected.sort();
+        assert_eq!(table.entities().to_vec(), expected);
+
+        for &entity in &survivors {
+            let location = world.location(entity).unwrap();
+            assert_eq!(table.entity(location.row), entity);
+        }
+
+        // A full pass always retraces every row (it doesn't track "already sorted" across
+        // calls, so a fresh spawn/despawn is picked up automatically next cycle), but running
+        // it again on an already-sorted table is a no-op in effect: order is unchanged.
+        world.defragment(100);
+        let table = world.archetypes().table(archetype);
+        assert_eq!(table.entities().to_vec(), expected);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn access_stats_track_reads_and_writes_from_queries() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+        world.spawn(Position(3.0, 0.0));
+        let position_id = world.registry_mut().register::<Position>();
+
+        for _ in 0..2 {
+            let mut query: Query<&Position> = Query::new(&mut world);
+            let _: Vec<_> = query.iter().collect();
+        }
+
+        let mut query: Query<&mut Position> = Query::new(&mut world);
+        for position in query.iter() {
+            position.0 += 1.0;
+        }
+
+        let stats = world.access_stats();
+        assert_eq!(stats[&position_id], AccessStats { reads: 6, writes: 3 });
+
+        world.reset_stats();
+        let stats = world.access_stats();
+        assert_eq!(stats[&position_id], AccessStats::default());
+    }
+
+    #[test]
+    fn spawn_column_bulk_spawns_match_one_at_a_time_spawns() {
+        let mut world = World::new();
+        let values: Vec<Position> = (0..1000).map(|i| Position(i as f32, i as f32 * 2.0)).collect();
+        let entities = world.spawn_column(values);
+
+        assert_eq!(entities.len(), 1000);
+        for (i, entity) in entities.iter().enumerate() {
+            let r = world.entity_ref(*entity).unwrap();
+            assert_eq!(r.get::<Position>(), Some(&Position(i as f32, i as f32 * 2.0)));
+        }
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        assert_eq!(query.iter().count(), 1000);
+    }
+
+    #[test]
+    fn ref_view_fetches_two_components_in_one_call() {
+        let mut world = World::new();
+        let e = world.spawn((Position(1.0, 2.0), Velocity(0.5, 0.5)));
+
+        let r = world.entity_ref(e).unwrap();
+        let (position, velocity) = r.view::<(&Position, &Velocity)>().unwrap();
+        assert_eq!(position, &Position(1.0, 2.0));
+        assert_eq!(velocity, &Velocity(0.5, 0.5));
+
+        assert!(r.view::<(&Position, &Health)>().is_none());
+    }
+
+    #[test]
+    fn ref_mut_view_mut_fetches_two_components_disjointly() {
+        let mut world = World::new();
+        let e = world.spawn((Position(1.0, 2.0), Velocity(0.5, 0.5)));
+
+        let mut r = world.entity_mut(e).unwrap();
+        let (position, velocity) = r.view_mut::<(&mut Position, &mut Velocity)>().unwrap();
+        position.0 += 1.0;
+        velocity.0 += 1.0;
+
+        let r = world.entity_ref(e).unwrap();
+        assert_eq!(r.get::<Position>(), Some(&Position(2.0, 2.0)));
+        assert_eq!(r.get::<Velocity>(), Some(&Velocity(1.5, 0.5)));
+    }
+
+    #[test]
+    fn try_spawn_rejects_unregistered_components_in_strict_mode() {
+        let mut world = World::new();
+        world.strict_mode(true);
+
+        let err = world.try_spawn(Position(1.0, 0.0)).unwrap_err();
+        assert_eq!(err.names, vec![std::any::type_name::<Position>()]);
+    }
+
+    #[test]
+    fn try_spawn_succeeds_in_lax_mode_and_after_registering() {
+        let mut world = World::new();
+        assert!(world.try_spawn(Position(1.0, 0.0)).is_ok());
+
+        world.strict_mode(true);
+        world.registry_mut().register::<Velocity>();
+        assert!(world.try_spawn(Velocity(0.5, 0.5)).is_ok());
+    }
+
+    #[test]
+    fn merge_splices_entities_and_remaps_ids() {
+        let mut main = World::new();
+        main.spawn(Position(1.0, 0.0));
+        main.spawn(Position(2.0, 0.0));
+        main.spawn(Position(3.0, 0.0));
+
+        let mut chunk = World::new();
+        let c0 = chunk.spawn(Position(4.0, 0.0));
+        let c1 = chunk.spawn((Position(5.0, 0.0), Velocity(1.0, 0.0)));
+
+        let mapping = main.merge(chunk);
+        assert_eq!(mapping.len(), 2);
+
+        assert_eq!(main.len(), 5);
+        let new_c0 = mapping[&c0];
+        let new_c1 = mapping[&c1];
+        assert_eq!(main.entity_ref(new_c0).unwrap().get::<Position>(), Some(&Position(4.0, 0.0)));
+        assert_eq!(main.entity_ref(new_c1).unwrap().get::<Position>(), Some(&Position(5.0, 0.0)));
+        assert_eq!(main.entity_ref(new_c1).unwrap().get::<Velocity>(), Some(&Velocity(1.0, 0.0)));
+
+        let position_count: usize = main.archetypes_with::<Position>().map(|(_, table)| table.len()).sum();
+        assert_eq!(position_count, 5);
+    }
+
+    #[test]
+    fn merge_drops_every_moved_component_exactly_once_and_leaks_nothing_from_the_donor() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Component)]
+        struct Counted(#[allow(dead_code)] u32);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut main = World::new();
+        let mut chunk = World::new();
+        for v in 0..5u32 {
+            chunk.spawn(Counted(v));
+        }
+        DROPS.store(0, Ordering::SeqCst);
+
+        let mapping = main.merge(chunk);
+
+        // Moved into `main`, not yet dropped.
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+        assert_eq!(mapping.len(), 5);
+
+        drop(main);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn run_system_returns_output() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn(Position(2.0, 0.0));
+
+        let count = world.run_system(|w: &mut World| w.archetypes_with::<Position>().count());
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn archetypes_with_finds_matching_archetypes_only() {
+        let mut world = World::new();
+        world.spawn(Position(0.0, 0.0));
+        world.spawn((Position(0.0, 0.0), Velocity(0.0, 0.0)));
+        world.spawn(Velocity(0.0, 0.0));
+
+        assert_eq!(world.archetypes_with::<Position>().count(), 2);
+        assert_eq!(world.archetypes_with::<Velocity>().count(), 2);
+    }
+
+    #[test]
+    fn query_stats_reports_per_archetype_counts() {
+        let mut world = World::new();
+        world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(0.0, 0.0));
+        world.spawn((Position(0.0, 0.0), Velocity(0.0, 0.0)));
+        world.spawn(Velocity(0.0, 0.0));
+
+        let extra = world.spawn(Position(1.0, 0.0));
+        let position_only = world.location(extra).unwrap().archetype;
+        let stats = world.query_stats::<Position>();
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[&position_only], 3);
+        assert_eq!(stats.values().sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn memory_report_total_is_at_least_the_theoretical_minimum_for_known_spawns() {
+        let mut world = World::new();
+        const COUNT: usize = 100;
+        for i in 0..COUNT {
+            world.spawn(Position(i as f32, 0.0));
+        }
+
+        let report = world.memory_report();
+        let minimum = COUNT * std::mem::size_of::<Position>();
+        assert!(report.total_bytes >= minimum, "{} < theoretical minimum {minimum}", report.total_bytes);
+        assert_eq!(report.per_archetype.values().sum::<usize>(), report.total_bytes);
+    }
+
+    #[test]
+    fn for_each_component_visits_every_archetype_and_matches_a_query_sum() {
+        use crate::ecs::query::Query;
+
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+        world.spawn((Position(3.0, 0.0), Velocity(0.0, 0.0), Health(1)));
+        world.spawn(Velocity(0.0, 0.0));
+
+        let mut callback_sum = 0.0;
+        world.for_each_component::<Position>(|_, p| callback_sum += p.0);
+
+        let mut query: Query<&Position> = Query::new(&mut world);
+        let query_sum: f32 = query.iter().map(|p| p.0).sum();
+
+        assert_eq!(callback_sum, query_sum);
+        assert_eq!(callback_sum, 6.0);
+    }
+
+    #[test]
+    fn for_each_component_mut_writes_through_every_archetype() {
+        let mut world = World::new();
+        world.spawn(Position(1.0, 0.0));
+        world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+
+        world.for_each_component_mut::<Position>(|_, p| p.0 += 100.0);
+
+        let mut callback_sum = 0.0;
+        world.for_each_component::<Position>(|_, p| callback_sum += p.0);
+        assert_eq!(callback_sum, 203.0);
+    }
+
+    #[test]
+    fn despawn_removes_entity() {
+        let mut world = World::new();
+        let e = world.spawn(Position(0.0, 0.0));
+        assert!(world.despawn(e));
+        assert!(!world.is_alive(e));
+        assert!(world.entity_ref(e).is_none());
+    }
+
+    #[test]
+    fn clear_all_despawns_every_entity_and_drops_every_unique() {
+        #[derive(Debug, PartialEq)]
+        struct UniqueScore(u32);
+        impl crate::ecs::unique::Unique for UniqueScore {}
+
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn((Position(2.0, 0.0), Velocity(0.0, 0.0)));
+        world.insert_unique(UniqueScore(7));
+        assert_eq!(world.unique::<UniqueScore>(), Some(&UniqueScore(7)));
+
+        world.clear_all();
+
+        assert!(!world.is_alive(a));
+        assert!(!world.is_alive(b));
+        assert_eq!(world.len(), 0);
+        assert!(world.unique::<UniqueScore>().is_none());
+    }
+
+    #[test]
+    fn despawn_returns_true_once_then_false_for_the_same_handle() {
+        let mut world = World::new();
+        let e = world.spawn(Position(0.0, 0.0));
+
+        assert!(world.despawn(e));
+        // The entity is already gone, so a second despawn of the same stale handle is a
+        // no-op — callers (e.g. a recursive hierarchy despawn walking shared children) can
+        // trust this to avoid double-processing.
+        assert!(!world.despawn(e));
+    }
+
+    #[test]
+    fn despawn_where_despawns_only_entities_matching_the_predicate() {
+        let mut world = World::new();
+        let off_screen_left = world.spawn(Position(-5.0, 0.0));
+        let off_screen_right = world.spawn((Position(-1.0, 0.0), Velocity(0.0, 0.0))); // different archetype, still matched
+        let on_screen = world.spawn(Position(1.0, 0.0));
+
+        let despawned = world.despawn_where::<&Position>(|position| position.0 < 0.0);
+
+        assert_eq!(despawned, 2);
+        assert!(!world.is_alive(off_screen_left));
+        assert!(!world.is_alive(off_screen_right));
+        assert!(world.is_alive(on_screen));
+    }
+
+    #[test]
+    fn queue_despawn_hides_entities_from_queries_immediately_but_removes_them_over_several_budgets() {
+        let mut world = World::new();
+        let survivor = world.spawn(Position(0.0, 0.0));
+        let queued: Vec<_> = (0..10).map(|i| world.spawn(Position(i as f32, 0.0))).collect();
+
+        for &entity in &queued {
+            assert!(world.queue_despawn(entity));
+        }
+        // Queuing twice is a no-op, not a double-despawn.
+        assert!(!world.queue_despawn(queued[0]));
+        assert_eq!(world.despawn_queue_len(), 10);
+
+        // Every queued entity is gone from queries right away, even though it's still alive
+        // and its component data hasn't actually been dropped yet.
+        let mut query: Query<&Position> = Query::new(&mut world);
+        assert_eq!(query.iter().collect::<Vec<_>>(), vec![&Position(0.0, 0.0)]);
+        for &entity in &queued {
+            assert!(world.is_alive(entity));
+        }
+
+        let mut total_processed = 0;
+        for _ in 0..3 {
+            total_processed += world.process_despawn_queue(3);
+        }
+        assert_eq!(total_processed, 9);
+        assert_eq!(world.despawn_queue_len(), 1);
+        for &entity in &queued[..9] {
+            assert!(!world.is_alive(entity));
+        }
+        assert!(world.is_alive(queued[9]));
+
+        // Draining past the end returns fewer than the budget instead of panicking.
+        assert_eq!(world.process_despawn_queue(5), 1);
+        assert_eq!(world.process_despawn_queue(5), 0);
+        assert!(!world.is_alive(queued[9]));
+        assert!(world.is_alive(survivor));
+    }
+
+    #[test]
+    fn despawning_a_queued_entity_directly_scrubs_it_from_the_despawn_queue() {
+        let mut world = World::new();
+        let e = world.spawn(Position(0.0, 0.0));
+
+        assert!(world.queue_despawn(e));
+        assert_eq!(world.despawn_queue_len(), 1);
+
+        assert!(world.despawn(e));
+        // `despawn` scrubbed the stale entry itself, rather than leaving it for
+        // `process_despawn_queue` to trip over later.
+        assert_eq!(world.despawn_queue_len(), 0);
+
+        // Nothing left to despawn, so the budgeted call reports zero actually despawned
+        // rather than counting the already-dead entry it would otherwise have popped.
+        assert_eq!(world.process_despawn_queue(10), 0);
+    }
+
+    #[test]
+    fn despawn_where_does_not_touch_entities_queue_despawn_already_hid_from_its_query() {
+        let mut world = World::new();
+        let queued = world.spawn(Position(-1.0, 0.0));
+        let unqueued = world.spawn(Position(-1.0, 0.0));
+        world.queue_despawn(queued);
+
+        // `queued` is invisible to the query `despawn_where` runs internally (see
+        // `query::Result::advance`), so only `unqueued` is despawned here even though both
+        // match the predicate — `queued` is left alone, still pending.
+        assert_eq!(world.despawn_where::<&Position>(|position| position.0 < 0.0), 1);
+        assert!(!world.is_alive(unqueued));
+        assert_eq!(world.despawn_queue_len(), 1);
+        assert!(world.is_alive(queued));
+    }
+
+    #[test]
+    fn clear_all_scrubs_the_despawn_queue_since_it_does_not_go_through_a_query() {
+        let mut world = World::new();
+        let queued = world.spawn(Position(0.0, 0.0));
+        world.spawn(Position(1.0, 0.0));
+        world.queue_despawn(queued);
+
+        // Unlike `despawn_where`, `clear_all` walks the archetypes directly rather than
+        // through a `Query`, so it still reaches `queued` and despawns it via `despawn` —
+        // which scrubs the now-stale queue entry.
+        world.clear_all();
+        assert_eq!(world.despawn_queue_len(), 0);
+        assert_eq!(world.process_despawn_queue(10), 0);
+    }
+
+    #[test]
+    fn insert_or_spawn_at_lands_on_the_exact_handle_and_reserves_skipped_slots() {
+        let mut world = World::new();
+        let replicated = crate::ecs::Entity::new(9, 2);
+
+        let entity = world.insert_or_spawn_at(replicated, Position(1.0, 2.0));
+        assert_eq!(entity, replicated);
+        assert!(world.is_alive(replicated));
+        assert_eq!(world.entity_ref(replicated).unwrap().get::<Position>(), Some(&Position(1.0, 2.0)));
+
+        // The 9 indices skipped to reach the replicated entity's slot land in the local
+        // allocator's free pool, so ordinary spawns still hand them out rather than jumping
+        // straight past them.
+        for _ in 0..9 {
+            let local = world.spawn(Position(0.0, 0.0));
+            assert!(local.index() < replicated.index());
+        }
+    }
+
+    #[test]
+    fn insert_or_spawn_at_overwrites_whatever_already_lived_at_that_slot() {
+        let mut world = World::new();
+        let original = world.spawn((Position(1.0, 0.0), Velocity(2.0, 0.0)));
+
+        let replacement = crate::ecs::Entity::new(original.index(), original.generation() + 1);
+        world.insert_or_spawn_at(replacement, Position(9.0, 9.0));
+
+        assert!(!world.is_alive(original));
+        assert!(world.is_alive(replacement));
+        let r = world.entity_ref(replacement).unwrap();
+        assert_eq!(r.get::<Position>(), Some(&Position(9.0, 9.0)));
+        assert_eq!(r.get::<Velocity>(), None);
+    }
+
+    #[test]
+    fn add_component_migrates_entity_and_keeps_existing_data() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn(Position(2.0, 0.0));
+
+        assert!(world.add_component(a, Velocity(3.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), Some(&Velocity(3.0, 0.0)));
+        // `b` stayed behind in the old archetype and must be unaffected by `a`'s migration.
+        assert_eq!(world.entity_ref(b).unwrap().get::<Position>(), Some(&Position(2.0, 0.0)));
+        assert_eq!(world.entity_ref(b).unwrap().get::<Velocity>(), None);
+
+        assert!(!world.add_component(a, Velocity(9.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), Some(&Velocity(3.0, 0.0)));
+    }
+
+    #[test]
+    fn insert_component_raw_migrates_the_entity_and_the_value_reads_back_typed() {
+        use std::any::TypeId;
+
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        world.registry_mut().register::<Velocity>();
+
+        // `Velocity` has no drop glue, so letting `velocity` also go out of scope normally
+        // (rather than forgetting it) is harmless even though `world` now owns a copy too.
+        let velocity = Velocity(3.0, 4.0);
+        let bytes = unsafe { std::slice::from_raw_parts((&velocity as *const Velocity).cast::<u8>(), std::mem::size_of::<Velocity>()) };
+        assert!(unsafe { world.insert_component_raw(a, TypeId::of::<Velocity>(), bytes) });
+
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), Some(&Velocity(3.0, 4.0)));
+
+        let other = Velocity(9.0, 9.0);
+        let other_bytes = unsafe { std::slice::from_raw_parts((&other as *const Velocity).cast::<u8>(), std::mem::size_of::<Velocity>()) };
+        assert!(!unsafe { world.insert_component_raw(a, TypeId::of::<Velocity>(), other_bytes) });
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), Some(&Velocity(3.0, 4.0)));
+    }
+
+    #[test]
+    fn remove_component_returns_value_and_keeps_survivors() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1.0, 0.0), Velocity(2.0, 0.0)));
+        let b = world.spawn((Position(3.0, 0.0), Velocity(4.0, 0.0)));
+
+        assert_eq!(world.remove_component::<Velocity>(a), Some(Velocity(2.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), None);
+        assert_eq!(world.entity_ref(b).unwrap().get::<Velocity>(), Some(&Velocity(4.0, 0.0)));
+
+        assert_eq!(world.remove_component::<Velocity>(a), None);
+        assert_eq!(world.remove_component::<Health>(b), None);
+    }
+
+    #[test]
+    fn add_component_to_all_bulk_migrates_a_whole_matched_archetype_at_once() {
+        use crate::ecs::query::QueryState;
+
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn(Position(2.0, 0.0));
+        // Doesn't match `state` (no `Position`) — must be left untouched.
+        let c = world.spawn(Velocity(0.0, 0.0));
+
+        let state: QueryState<&Position> = QueryState::new(world.registry_mut());
+        let migrated = world.add_component_to_all(&state, Health(100));
+
+        assert_eq!(migrated, 2);
+        assert_eq!(world.entity_ref(a).unwrap().get::<Health>(), Some(&Health(100)));
+        assert_eq!(world.entity_ref(b).unwrap().get::<Health>(), Some(&Health(100)));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+        assert_eq!(world.entity_ref(c).unwrap().get::<Health>(), None);
+
+        // Entities that already have `Health` aren't touched a second time.
+        assert_eq!(world.add_component_to_all(&state, Health(1)), 0);
+        assert_eq!(world.entity_ref(a).unwrap().get::<Health>(), Some(&Health(100)));
+    }
+
+    #[test]
+    fn remove_component_from_all_bulk_migrates_a_whole_matched_archetype_at_once() {
+        use crate::ecs::query::QueryState;
+
+        let mut world = World::new();
+        let a = world.spawn((Position(1.0, 0.0), Health(10)));
+        let b = world.spawn((Position(2.0, 0.0), Health(20)));
+        let c = world.spawn(Position(3.0, 0.0));
+
+        let state: QueryState<&Health> = QueryState::new(world.registry_mut());
+        let migrated = world.remove_component_from_all::<Health, _>(&state);
+
+        assert_eq!(migrated, 2);
+        assert_eq!(world.entity_ref(a).unwrap().get::<Health>(), None);
+        assert_eq!(world.entity_ref(b).unwrap().get::<Health>(), None);
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+        assert_eq!(world.entity_ref(c).unwrap().get::<Position>(), Some(&Position(3.0, 0.0)));
+
+        assert_eq!(world.remove_component_from_all::<Health, _>(&state), 0);
+    }
+
+    #[test]
+    fn disable_component_hides_it_without_migrating_and_enable_undoes_it() {
+        let mut world = World::new();
+        let a = world.spawn((Position(1.0, 0.0), Velocity(2.0, 0.0)));
+        let before = world.location(a).unwrap();
+
+        assert!(world.disable_component::<Velocity>(a));
+        // No archetype migration happened — same table, same row, value still readable
+        // directly (only queries skip it).
+        assert_eq!(world.location(a), Some(before));
+        assert_eq!(world.entity_ref(a).unwrap().get::<Velocity>(), Some(&Velocity(2.0, 0.0)));
+
+        assert!(world.enable_component::<Velocity>(a));
+        assert_eq!(world.location(a), Some(before));
+
+        assert!(!world.disable_component::<Health>(a));
+        let dead = world.spawn(Position(0.0, 0.0));
+        world.despawn(dead);
+        assert!(!world.disable_component::<Position>(dead));
+    }
+
+    #[test]
+    fn repeated_add_remove_of_same_component_reuses_cached_edges() {
+        let mut world = World::new();
+        let e = world.spawn(Position(0.0, 0.0));
+
+        for i in 0..5 {
+            assert!(world.add_component(e, Velocity(i as f32, 0.0)));
+            assert_eq!(world.remove_component::<Velocity>(e), Some(Velocity(i as f32, 0.0)));
+        }
+        assert_eq!(world.entity_ref(e).unwrap().get::<Position>(), Some(&Position(0.0, 0.0)));
+    }
+
+    #[test]
+    fn get_many_mut_same_table() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn(Position(2.0, 0.0));
+
+        {
+            let [mut ra, mut rb] = world.get_many_mut([a, b]).unwrap();
+            std::mem::swap(&mut ra.get_mut::<Position>().unwrap().0, &mut rb.get_mut::<Position>().unwrap().0);
+        }
+
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(2.0, 0.0)));
+        assert_eq!(world.entity_ref(b).unwrap().get::<Position>(), Some(&Position(1.0, 0.0)));
+    }
+
+    #[test]
+    fn get_many_mut_different_tables() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn((Position(2.0, 0.0), Velocity(1.0, 1.0)));
+
+        {
+            let [mut ra, mut rb] = world.get_many_mut([a, b]).unwrap();
+            ra.get_mut::<Position>().unwrap().0 = 9.0;
+            rb.get_mut::<Velocity>().unwrap().0 = 9.0;
+        }
+
+        assert_eq!(world.entity_ref(a).unwrap().get::<Position>(), Some(&Position(9.0, 0.0)));
+        assert_eq!(world.entity_ref(b).unwrap().get::<Velocity>(), Some(&Velocity(9.0, 1.0)));
+    }
+
+    #[test]
+    fn get_many_mut_rejects_duplicate_entity() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        assert!(world.get_many_mut([a, a]).is_none());
+    }
+
+    #[test]
+    fn get_many_mut_rejects_dead_entity() {
+        let mut world = World::new();
+        let a = world.spawn(Position(1.0, 0.0));
+        let b = world.spawn(Position(2.0, 0.0));
+        world.despawn(b);
+        assert!(world.get_many_mut([a, b]).is_none());
+    }
+
+    #[derive(Component, Debug, PartialEq, Clone, Copy)]
+    struct Score(u32);
+
+    #[test]
+    fn try_clone_deep_copies_and_mutating_the_original_does_not_affect_the_clone() {
+        let mut world = World::new();
+        world.registry_mut().register_cloneable::<Score>();
+        let entity = world.spawn(Score(1));
+
+        let cloned = world.try_clone().unwrap();
+        world.entity_mut(entity).unwrap().get_mut::<Score>().unwrap().0 = 2;
+
+        assert_eq!(world.entity_ref(entity).unwrap().get::<Score>(), Some(&Score(2)));
+        assert_eq!(cloned.entity_ref(entity).unwrap().get::<Score>(), Some(&Score(1)));
+    }
+
+    #[test]
+    fn try_clone_rejects_a_non_cloneable_component() {
+        let mut world = World::new();
+        world.spawn(Score(1)); // registered plain, not via register_cloneable
+        assert!(world.try_clone().is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! rusty-engine: a small archetype-based ECS and supporting game engine scaffolding.
+
+// Lets derive macros emit `rusty_engine::...` paths that also resolve from inside this crate.
+extern crate self as rusty_engine;
+
+pub mod bench;
+pub mod core;
+pub mod ecs;
+
+pub use rusty_engine_macros::Component;